@@ -95,6 +95,9 @@ pub struct TypeMeta {
     pub auths: Vec<Auth>,
     pub rate: Option<Rate>,
     pub version: String,
+    /// sha256 of the finalized typegraph, computed without this field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
 }
 
 #[cfg_attr(feature = "codegen", derive(JsonSchema))]
@@ -104,6 +107,8 @@ pub enum EffectType {
     Create,
     Update,
     Delete,
+    Upsert,
+    #[serde(alias = "read")]
     None,
 }
 
@@ -112,6 +117,9 @@ pub enum EffectType {
 pub struct Effect {
     pub effect: Option<EffectType>,
     pub idempotent: bool,
+    /// dotted path of the key identifying the retries of a mutation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
 #[cfg_attr(feature = "codegen", derive(JsonSchema))]
@@ -134,10 +142,24 @@ pub struct Policy {
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct PolicyIndicesByEffect {
+    #[serde(alias = "read")]
     pub none: Option<u32>,
     pub create: Option<u32>,
     pub delete: Option<u32>,
     pub update: Option<u32>,
+    pub upsert: Option<u32>,
+}
+
+#[cfg_attr(feature = "codegen", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PolicyIndicesAll {
+    pub all: Vec<u32>,
+}
+
+#[cfg_attr(feature = "codegen", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PolicyIndicesAny {
+    pub any: Vec<u32>,
 }
 
 #[cfg_attr(feature = "codegen", derive(JsonSchema))]
@@ -146,6 +168,9 @@ pub struct PolicyIndicesByEffect {
 #[serde(untagged)]
 pub enum PolicyIndices {
     Policy(u32),
+    // must come before `EffectPolicies`, whose fields are all optional
+    All(PolicyIndicesAll),
+    Any(PolicyIndicesAny),
     EffectPolicies(PolicyIndicesByEffect),
 }
 
@@ -0,0 +1,21 @@
+// Copyright Metatype OÜ, licensed under the Elastic License 2.0.
+// SPDX-License-Identifier: Elastic-2.0
+
+#[cfg(feature = "codegen")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(feature = "codegen", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GrpcMatData {
+    /// fully-qualified, e.g. `helloworld.Greeter`
+    pub service: String,
+    pub method: String,
+}
+
+#[cfg_attr(feature = "codegen", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GrpcRuntimeData {
+    pub proto_file: String,
+    pub endpoint: String,
+}
@@ -0,0 +1,20 @@
+// Copyright Metatype OÜ, licensed under the Elastic License 2.0.
+// SPDX-License-Identifier: Elastic-2.0
+
+#[cfg(feature = "codegen")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(feature = "codegen", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KafkaPublishMatData {
+    pub topic: String,
+    /// path of the message key in the arguments
+    pub key_path: Option<Vec<String>>,
+}
+
+#[cfg_attr(feature = "codegen", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KafkaRuntimeData {
+    pub broker_secret: String,
+}
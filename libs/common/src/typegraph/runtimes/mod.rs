@@ -8,7 +8,9 @@ use serde::{Deserialize, Serialize};
 
 use self::deno::DenoRuntimeData;
 use self::graphql::GraphQLRuntimeData;
+use self::grpc::GrpcRuntimeData;
 use self::http::HTTPRuntimeData;
+use self::kafka::KafkaRuntimeData;
 use self::prisma::PrismaRuntimeData;
 use self::python::PythonRuntimeData;
 use self::random::RandomRuntimeData;
@@ -18,7 +20,9 @@ use self::wasmedge::WasmEdgeRuntimeData;
 
 pub mod deno;
 pub mod graphql;
+pub mod grpc;
 pub mod http;
+pub mod kafka;
 pub mod prisma;
 pub mod python;
 pub mod random;
@@ -45,8 +49,10 @@ pub enum KnownRuntime {
     Deno(DenoRuntimeData),
     #[serde(rename = "graphql")]
     GraphQL(GraphQLRuntimeData),
+    Grpc(GrpcRuntimeData),
     #[serde(rename = "http")]
     HTTP(HTTPRuntimeData),
+    Kafka(KafkaRuntimeData),
     #[serde(rename = "python_wasi")]
     PythonWasi(PythonRuntimeData),
     Random(RandomRuntimeData),
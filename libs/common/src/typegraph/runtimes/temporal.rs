@@ -9,5 +9,5 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TemporalRuntimeData {
     pub name: String,
-    pub host: String,
+    pub host_secret: String,
 }
@@ -59,6 +59,10 @@ pub struct TypeNodeBase {
     #[serde(default, rename = "enum")]
     pub enumeration: Option<Vec<String>>, // JSON-serialized values
     #[serde(default)]
+    pub examples: Option<Vec<String>>, // JSON-serialized values
+    #[serde(default)]
+    pub deprecated: Option<String>,
+    #[serde(default)]
     pub config: IndexMap<String, serde_json::Value>,
     pub as_id: bool,
 }
@@ -70,6 +74,23 @@ pub struct OptionalTypeData {
     pub item: u32,
     #[serialize_always]
     pub default_value: Option<serde_json::Value>,
+    /// The default value is an explicit `null`, which `default_value` cannot
+    /// tell apart from no default.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub null_default: bool,
+    /// Unset when the value may be either omitted or null.
+    #[serde(default)]
+    pub kind: Option<OptionalKind>,
+}
+
+#[cfg_attr(feature = "codegen", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionalKind {
+    /// may be omitted, but not null
+    Absent,
+    /// must be present, may be null
+    Nullable,
 }
 
 #[cfg_attr(feature = "codegen", derive(JsonSchema))]
@@ -111,6 +132,7 @@ pub enum StringFormat {
     DateTime,
     // Path,
     Phone,
+    Binary,
 }
 
 #[cfg_attr(feature = "codegen", derive(JsonSchema))]
@@ -122,6 +144,8 @@ pub struct StringTypeData {
     pub max_length: Option<u32>,
     pub pattern: Option<String>,
     pub format: Option<StringFormat>,
+    /// maximum size in bytes of a binary string
+    pub max_size: Option<u32>,
 }
 
 #[cfg_attr(feature = "codegen", derive(JsonSchema))]
@@ -141,6 +165,9 @@ pub struct ObjectTypeData {
     pub properties: IndexMap<String, u32>,
     #[serde(default)]
     pub required: Vec<String>,
+    /// recursion depth hint for the self-referential types
+    #[serde(default)]
+    pub max_depth: Option<u32>,
 }
 
 #[cfg_attr(feature = "codegen", derive(JsonSchema))]
@@ -154,6 +181,22 @@ pub struct ArrayTypeData {
     pub unique_items: Option<bool>,
 }
 
+/// User-defined scalar, checked and serialized by materializers
+#[cfg_attr(feature = "codegen", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScalarTypeData {
+    pub validator: u32,
+    pub serializer: u32,
+}
+
+/// Dictionary with string keys
+#[cfg_attr(feature = "codegen", derive(JsonSchema))]
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MapTypeData {
+    pub key: u32,
+    pub value: u32,
+}
+
 #[cfg_attr(feature = "codegen", derive(JsonSchema))]
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -174,6 +217,8 @@ pub struct UnionTypeData {
     /// Array of indexes of the nodes that are used as subschemes in the
     /// anyOf field of JSON Schema.
     pub any_of: Vec<u32>,
+    /// Name of the string field used to tell the variants apart.
+    pub discriminator: Option<String>,
 }
 
 #[cfg_attr(feature = "codegen", derive(JsonSchema))]
@@ -237,6 +282,18 @@ pub enum TypeNode {
         #[serde(flatten)]
         data: ArrayTypeData,
     },
+    Map {
+        #[serde(flatten)]
+        base: TypeNodeBase,
+        #[serde(flatten)]
+        data: MapTypeData,
+    },
+    Scalar {
+        #[serde(flatten)]
+        base: TypeNodeBase,
+        #[serde(flatten)]
+        data: ScalarTypeData,
+    },
     Function {
         #[serde(flatten)]
         base: TypeNodeBase,
@@ -274,6 +331,8 @@ impl TypeNode {
             | File { base, .. }
             | Object { base, .. }
             | Array { base, .. }
+            | Map { base, .. }
+            | Scalar { base, .. }
             | Function { base, .. }
             | Union { base, .. }
             | Either { base, .. }
@@ -292,6 +351,8 @@ impl TypeNode {
             | File { base, .. }
             | Object { base, .. }
             | Array { base, .. }
+            | Map { base, .. }
+            | Scalar { base, .. }
             | Function { base, .. }
             | Union { base, .. }
             | Either { base, .. }
@@ -310,6 +371,8 @@ impl TypeNode {
             File { .. } => "file",
             Object { .. } => "object",
             Array { .. } => "array",
+            Map { .. } => "map",
+            Scalar { .. } => "scalar",
             Function { .. } => "function",
             Union { .. } => "union",
             Either { .. } => "either",
@@ -9,8 +9,8 @@ use serde_json::Value;
 
 use super::{
     visitor::{Path, PathSegment, TypeVisitor, VisitResult},
-    ArrayTypeData, EitherTypeData, FloatTypeData, Injection, IntegerTypeData, ObjectTypeData,
-    StringTypeData, UnionTypeData,
+    ArrayTypeData, EitherTypeData, FloatTypeData, Injection, IntegerTypeData, MapTypeData,
+    ObjectTypeData, StringTypeData, UnionTypeData,
 };
 
 pub fn validate_typegraph(tg: &Typegraph) -> Vec<ValidatorError> {
@@ -47,7 +47,7 @@ impl Typegraph {
             let node = self.types.get(*idx as usize).unwrap();
             match node {
                 TypeNode::Union {
-                    data: UnionTypeData { any_of: variants },
+                    data: UnionTypeData { any_of: variants, .. },
                     ..
                 }
                 | TypeNode::Either {
@@ -214,6 +214,8 @@ impl Typegraph {
             }),
             TypeNode::String { data, .. } => self.validate_string(data, value),
             TypeNode::File { .. } => bail!("Literal file not supported"),
+            // checked by the validator materializer at runtime
+            TypeNode::Scalar { .. } => Ok(()),
             TypeNode::Optional { data, .. } => {
                 if value.is_null() {
                     Ok(())
@@ -222,6 +224,7 @@ impl Typegraph {
                 }
             }
             TypeNode::Array { data, .. } => self.validate_array(data, value),
+            TypeNode::Map { data, .. } => self.validate_map(data, value),
             TypeNode::Object { data, .. } => self.validate_object(data, value),
             TypeNode::Function { .. } => Err(anyhow!("Unexpected function type")),
             TypeNode::Union { data, .. } => self.validate_union(data, value),
@@ -308,6 +311,17 @@ impl Typegraph {
         Ok(())
     }
 
+    fn validate_map(&self, data: &MapTypeData, value: &Value) -> Result<()> {
+        let map = value
+            .as_object()
+            .ok_or_else(|| anyhow!("Expected a map got '{}'", to_string(value)))?;
+        for (key, item) in map {
+            self.validate_value(data.key, &Value::String(key.clone()))?;
+            self.validate_value(data.value, item)?;
+        }
+        Ok(())
+    }
+
     fn validate_array(&self, data: &ArrayTypeData, value: &Value) -> Result<()> {
         let array = value
             .as_array()
@@ -61,6 +61,7 @@ impl<'a, V: TypeVisitor + Sized> TypegraphTraversal<'a, V> {
                 TypeNode::Optional { data, .. } => self.visit_optional(type_idx, data.item),
                 TypeNode::Object { data, .. } => self.visit_object(type_idx, &data.properties),
                 TypeNode::Array { data, .. } => self.visit_array(type_idx, data.items),
+                TypeNode::Map { data, .. } => self.visit_map(type_idx, data.value),
                 TypeNode::Union { data, .. } => self.visit_union(type_idx, &data.any_of),
                 TypeNode::Either { data, .. } => self.visit_either(type_idx, &data.one_of),
                 TypeNode::Function { data, .. } => {
@@ -71,6 +72,7 @@ impl<'a, V: TypeVisitor + Sized> TypegraphTraversal<'a, V> {
                 | TypeNode::Integer { .. }
                 | TypeNode::String { .. }
                 | TypeNode::File { .. }
+                | TypeNode::Scalar { .. }
                 | TypeNode::Any { .. } => {
                     // scalar types -- no children
                     None
@@ -103,6 +105,17 @@ impl<'a, V: TypeVisitor + Sized> TypegraphTraversal<'a, V> {
         )
     }
 
+    fn visit_map(&mut self, type_idx: u32, value_type_idx: u32) -> Option<V::Return> {
+        self.visit_child(
+            PathSegment {
+                from: type_idx,
+                edge: Edge::MapValue,
+            },
+            value_type_idx,
+            false,
+        )
+    }
+
     fn visit_object(
         &mut self,
         type_idx: u32,
@@ -206,6 +219,7 @@ impl<'a> Display for PathSegment<'a> {
         match self.edge {
             Edge::ObjectProp(name) => write!(f, "{}", name)?,
             Edge::ArrayItem => write!(f, "[]")?,
+            Edge::MapValue => write!(f, "{{}}")?,
             Edge::OptionalItem => write!(f, "*")?,
             Edge::FunctionInput => write!(f, "[in]")?,
             Edge::FunctionOutput => write!(f, "[out]")?,
@@ -230,6 +244,7 @@ impl<'a> Display for Path<'a> {
 pub enum Edge<'a> {
     ObjectProp(&'a str),
     ArrayItem,
+    MapValue,
     OptionalItem,
     FunctionInput,
     FunctionOutput,
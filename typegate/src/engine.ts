@@ -23,7 +23,14 @@ import { Validator } from "./typecheck/common.ts";
 import { generateValidator } from "./typecheck/result.ts";
 import { ComputationEngine } from "./engine/computation_engine.ts";
 import { isIntrospectionQuery } from "./services/graphql_service.ts";
-import { ObjectNode } from "./type_node.ts";
+import {
+  CustomScalarNode,
+  isCustomScalar,
+  isFunction,
+  isOptional,
+  ObjectNode,
+  Type,
+} from "./type_node.ts";
 import { RestSchemaGenerator } from "./typecheck/rest_schema_generator.ts";
 
 /**
@@ -108,6 +115,7 @@ const effectToMethod = {
   "create": "POST",
   "update": "PUT",
   "delete": "DELETE",
+  "upsert": "PUT",
 };
 
 export interface EndpointToSchemaMap {
@@ -297,13 +305,96 @@ export class Engine {
     while (waitlist.length > 0) {
       const stage = waitlist.shift()!;
       stagesMat.push(
-        ...stage.props.runtime.materialize(stage, waitlist, verbose),
+        ...stage.props.runtime.materialize(stage, waitlist, verbose)
+          .map((s) => this.withCustomScalars(s, verbose)),
       );
     }
 
     return stagesMat;
   }
 
+  /**
+   * Checks the custom scalar arguments of a function stage with their
+   * validator materializer, and serializes a custom scalar result with the
+   * serializer materializer.
+   */
+  private withCustomScalars(
+    stage: ComputeStage,
+    verbose: boolean,
+  ): ComputeStage {
+    const { typeIdx, materializer, resolver, runtime } = stage.props;
+    // introspection stages run against the introspection typegraph
+    if (
+      materializer == null || resolver == null ||
+      !this.tg.runtimeReferences.includes(runtime)
+    ) {
+      return stage;
+    }
+    const funcType = this.tg.type(typeIdx);
+    if (!isFunction(funcType)) {
+      return stage;
+    }
+
+    const inputType = this.tg.type(funcType.input, Type.OBJECT);
+    const validators: [string, Resolver][] = [];
+    for (const [name, idx] of Object.entries(inputType.properties)) {
+      const scalar = this.customScalar(idx);
+      if (scalar != null) {
+        validators.push([
+          name,
+          this.scalarResolver(stage, scalar.validator, verbose),
+        ]);
+      }
+    }
+    const outputScalar = this.customScalar(funcType.output);
+    const serializer = outputScalar &&
+      this.scalarResolver(stage, outputScalar.serializer, verbose);
+
+    if (validators.length === 0 && serializer == null) {
+      return stage;
+    }
+
+    return stage.withResolver(async (args) => {
+      for (const [name, validate] of validators) {
+        const value = args[name];
+        if (value != null && !(await validate({ _: args._, value }))) {
+          throw new Error(
+            `invalid value for argument '${name}' at '${stage.id()}'`,
+          );
+        }
+      }
+      const res = await resolver(args);
+      return serializer == null || res == null
+        ? res
+        : await serializer({ _: args._, value: res });
+    });
+  }
+
+  /** The custom scalar at `typeIdx`, if any, optional or not */
+  private customScalar(typeIdx: number): CustomScalarNode | null {
+    let typ = this.tg.type(typeIdx);
+    if (isOptional(typ)) {
+      typ = this.tg.type(typ.item);
+    }
+    return isCustomScalar(typ) ? typ : null;
+  }
+
+  /** Resolver of a custom scalar materializer, on its own runtime */
+  private scalarResolver(
+    stage: ComputeStage,
+    matIdx: number,
+    verbose: boolean,
+  ): Resolver {
+    const materializer = this.tg.materializer(matIdx);
+    const runtime = this.tg.runtimeReferences[materializer.runtime];
+    const [matStage] = runtime.materialize(
+      new ComputeStage({ ...stage.props, runtime, materializer }),
+      [],
+      verbose,
+    );
+    return matStage.props.resolver!;
+  }
+
   optimize(stages: ComputeStage[], _verbose: boolean): ComputeStage[] {
     for (const _stage of stages) {
       // optimize
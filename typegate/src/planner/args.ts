@@ -7,7 +7,6 @@ import { TypeGraph } from "../typegraph/mod.ts";
 import {
   Context,
   Parents,
-  PolicyIdx,
   StageId,
   TypeIdx,
   Variables,
@@ -17,6 +16,7 @@ import {
   ArrayNode,
   getVariantTypesIndexes,
   ObjectNode,
+  OptionalNode,
   Type,
   TypeNode,
   UnionNode,
@@ -31,6 +31,7 @@ import { generateValidator } from "../typecheck/input.ts";
 import { getParentId } from "../utils/stage_id.ts";
 import { BadContext } from "../errors.ts";
 import { selectInjection } from "./injection_utils.ts";
+import { PolicyCheck, policyCheckForEffect } from "./policies.ts";
 
 class MandatoryArgumentError extends Error {
   constructor(argDetails: string) {
@@ -40,6 +41,17 @@ class MandatoryArgumentError extends Error {
   }
 }
 
+/**
+ * Value of an omitted optional argument: its default, or `null` when there is
+ * none (`null_default` tells an explicit `null` default apart)
+ */
+function optionalDefault(typ: OptionalNode): unknown {
+  if (typ.null_default) {
+    return null;
+  }
+  return typ.default_value ?? null;
+}
+
 export interface ComputeArgParams {
   variables: Variables;
   parent: Parents;
@@ -59,7 +71,7 @@ export interface ComputeArg<T = unknown> {
 }
 
 export interface ArgTypePolicies {
-  policyIndices: PolicyIdx[];
+  policyIndices: PolicyCheck[];
   argDetails: string;
 }
 
@@ -247,8 +259,7 @@ class ArgumentCollector {
     if (astNode == null) {
       if (typ.type === Type.OPTIONAL) {
         this.addPoliciesFrom(typ.item);
-        const { default_value: defaultValue } = typ;
-        const value = defaultValue ?? null;
+        const value = optionalDefault(typ);
         return () => value;
       }
 
@@ -336,6 +347,14 @@ class ArgumentCollector {
       case Type.EITHER:
         return this.collectGeneralUnionArg(astNode, typ);
 
+      case Type.MAP:
+      case Type.ANY:
+      case Type.SCALAR: {
+        // the value is checked by the input validator
+        const value = this.getJsonValueFromRoot(valueNode, astNode.name.value);
+        return () => value;
+      }
+
       default:
         throw new Error(`unknown variable type '${typ.type}'`);
     }
@@ -358,6 +377,9 @@ class ArgumentCollector {
       case Kind.FLOAT:
         return Number(node.value);
 
+      case Kind.NULL:
+        return null;
+
       case Kind.OBJECT: {
         const fields = node.fields;
         const argumentObjectValue: Record<string, JSONValue> = {};
@@ -570,8 +592,8 @@ class ArgumentCollector {
     } else {
       this.addPoliciesFrom(typ.item);
     }
-    const { default_value: defaultValue } = typ;
-    return () => defaultValue;
+    const value = optionalDefault(typ);
+    return () => value;
   }
 
   /** Collect the value of an injected parameter. */
@@ -664,7 +686,7 @@ class ArgumentCollector {
       const { [name]: value } = parent;
       if (value == null) {
         if (typ.type === Type.OPTIONAL) {
-          return typ.default_value;
+          return optionalDefault(typ);
         }
 
         const suggestions = `available fields from parent are: ${
@@ -686,10 +708,7 @@ class ArgumentCollector {
     this.policies.set(typeIdx, {
       argDetails: this.currentNodeDetails,
       policyIndices: typ.policies.map((p) => {
-        if (typeof p === "number") {
-          return p;
-        }
-        const polIdx = p[this.effect];
+        const polIdx = policyCheckForEffect(p, this.effect);
         if (polIdx == null) {
           // not authorized
           console.log("argument not authorized with effect", this.effect);
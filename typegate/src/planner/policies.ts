@@ -12,7 +12,12 @@ import {
   StageId,
   TypeIdx,
 } from "../types.ts";
-import { EffectType, PolicyIndices } from "../types/typegraph.ts";
+import {
+  EffectType,
+  PolicyIndices,
+  PolicyIndicesAll,
+  PolicyIndicesAny,
+} from "../types/typegraph.ts";
 import { ensure } from "../utils.ts";
 import { getLogger } from "../log.ts";
 import { Type } from "../type_node.ts";
@@ -35,6 +40,20 @@ type CheckResult = { authorized: true } | {
   policyIdx: PolicyIdx | null;
 };
 
+/** a policy or a group of policies, for a given effect */
+export type PolicyCheck = PolicyIdx | PolicyIndicesAll | PolicyIndicesAny;
+
+/** null if the effect is not authorized */
+export function policyCheckForEffect(
+  policy: PolicyIndices,
+  effect: EffectType | "none",
+): PolicyCheck | null {
+  if (typeof policy === "number" || "all" in policy || "any" in policy) {
+    return policy;
+  }
+  return policy[effect] ?? null;
+}
+
 export class OperationPolicies {
   // should be private -- but would not be testable
   functions: Map<StageId, SubtreeData>;
@@ -108,6 +127,7 @@ export class OperationPolicies {
       "create": new Set(),
       "update": new Set(),
       "delete": new Set(),
+      "upsert": new Set(),
     };
 
     const cache = new Map<PolicyIdx, boolean | null>();
@@ -167,7 +187,7 @@ export class OperationPolicies {
             continue;
           }
           const policies = (this.policyLists.get(typeIdx) ?? []).map((p) =>
-            typeof p === "number" ? p : p[effect] ?? null
+            policyCheckForEffect(p, effect)
           );
 
           if (policies.some((idx) => idx == null)) {
@@ -177,7 +197,7 @@ export class OperationPolicies {
           }
 
           const res = await this.checkTypePolicies(
-            policies as number[],
+            policies as PolicyCheck[],
             effect,
             getResolverResult,
           );
@@ -243,7 +263,7 @@ export class OperationPolicies {
   }
 
   private async checkTypePolicies(
-    policies: PolicyIdx[],
+    policies: PolicyCheck[],
     effect: EffectType | "none",
     getResolverResult: GetResolverResult,
   ): Promise<CheckResult> {
@@ -251,8 +271,12 @@ export class OperationPolicies {
       return { authorized: true };
     }
 
-    for (const polIdx of policies) {
-      const res = await getResolverResult(polIdx, effect);
+    for (const policy of policies) {
+      const [res, polIdx] = await this.evaluatePolicy(
+        policy,
+        effect,
+        getResolverResult,
+      );
       if (res == null) {
         continue;
       }
@@ -265,6 +289,37 @@ export class OperationPolicies {
 
     return { authorized: false, policyIdx: null };
   }
+
+  /**
+   * `all`: denies if any policy denies, allows if all of them allow;
+   * `any`: allows if any policy allows, denies if all of them deny;
+   * no decision otherwise.
+   * Returns the decision with the index of the deciding policy.
+   */
+  private async evaluatePolicy(
+    policy: PolicyCheck,
+    effect: EffectType | "none",
+    getResolverResult: GetResolverResult,
+  ): Promise<[boolean | null, PolicyIdx]> {
+    if (typeof policy === "number") {
+      return [await getResolverResult(policy, effect), policy];
+    }
+
+    const [indices, decisive]: [PolicyIdx[], boolean] = "all" in policy
+      ? [policy.all, false]
+      : [policy.any, true];
+    let undecided = false;
+    for (const polIdx of indices) {
+      const res = await getResolverResult(polIdx, effect);
+      if (res == null) {
+        undecided = true;
+      } else if (res === decisive) {
+        return [res, polIdx];
+      }
+    }
+    const last = indices[indices.length - 1];
+    return [undecided ? null : !decisive, last];
+  }
 }
 
 interface SubtreeData {
@@ -332,6 +387,10 @@ export class OperationPoliciesBuilder {
 function* iterIndices(indices: PolicyIndices): IterableIterator<number> {
   if (typeof indices === "number") {
     yield indices;
+  } else if ("all" in indices) {
+    yield* indices.all;
+  } else if ("any" in indices) {
+    yield* indices.any;
   } else {
     for (const idx of Object.values(indices) as number[]) {
       yield idx;
@@ -101,6 +101,11 @@ class FieldBuilder {
       tags.push("@unique");
     }
 
+    // on an object type, `db_name` is the name of the table
+    if (typeNode.type !== Type.OBJECT && typeNode.config?.db_name) {
+      tags.push(`@map(${toPrismaString(typeNode.config.db_name as string)})`);
+    }
+
     if (typeNode.config?.auto) {
       // TODO check database support
       if (typeNode.type === Type.INTEGER) {
@@ -131,8 +136,14 @@ class FieldBuilder {
   #getScalarTypeNameAndTags(
     typeNode: TypeNode,
   ): [string, string[]] | null {
+    const dbType = typeNode.config?.db_type as string | undefined;
+    const nativeTags = dbType ? [`@${this.source}.${dbType}`] : [];
+
     switch (typeNode.type) {
       case Type.STRING:
+        if (dbType) {
+          return ["String", nativeTags];
+        }
         return this.#getStringTypeAndTags(typeNode);
 
       case Type.BOOLEAN:
@@ -142,7 +153,11 @@ class FieldBuilder {
         return ["Int", []];
 
       case Type.FLOAT:
-        return ["Float", []];
+        // native decimal types require the Decimal scalar
+        if (dbType?.startsWith("Decimal")) {
+          return ["Decimal", nativeTags];
+        }
+        return ["Float", nativeTags];
 
       case Type.OBJECT:
         return null;
@@ -396,7 +411,13 @@ export class SchemaGenerator {
     }
 
     const idFields = modelFields.filter((field) => field.tags.includes("@id"));
-    ensure(idFields.length > 0, "no @id field found");
+    const compositeId = typeNode.config?.composite_id as string[] | undefined;
+    if (compositeId != null) {
+      ensure(idFields.length === 0, "conflicting @id and composite id");
+      tags.push(`@@id([${compositeId.join(", ")}])`);
+    } else {
+      ensure(idFields.length > 0, "no @id field found");
+    }
 
     if (idFields.length > 1) {
       const names = idFields.map((field) => field.name).join(", ");
@@ -406,6 +427,19 @@ export class SchemaGenerator {
       }
     }
 
+    const indexes = (typeNode.config?.indexes ?? []) as string[][];
+    for (const fields of indexes) {
+      tags.push(`@@index(${toPrismaList(fields)})`);
+    }
+    const uniqueIndexes = (typeNode.config?.unique_indexes ?? []) as string[][];
+    for (const fields of uniqueIndexes) {
+      tags.push(`@@unique(${toPrismaList(fields)})`);
+    }
+
+    if (typeNode.config?.db_name) {
+      tags.push(`@@map(${toPrismaString(typeNode.config.db_name as string)})`);
+    }
+
     const formattedFields = modelFields.map((field) =>
       `    ${field.stringify()}\n`
     )
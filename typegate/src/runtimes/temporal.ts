@@ -18,7 +18,7 @@ export class TemporalRuntime extends Runtime {
   static async init(
     params: RuntimeInitParams,
   ): Promise<Runtime> {
-    const { typegraph, args } = params as RuntimeInitParams<
+    const { typegraph, args, secretManager } = params as RuntimeInitParams<
       TemporalRuntimeData
     >;
     const typegraphName = TypeGraph.formatName(typegraph);
@@ -26,7 +26,7 @@ export class TemporalRuntime extends Runtime {
 
     nativeVoid(
       await native.temporal_register({
-        url: args.host as string,
+        url: secretManager.secretOrFail(args.host_secret),
         namespace: "default",
         client_id,
       }),
@@ -8,13 +8,15 @@ import { Runtime } from "./Runtime.ts";
 import { ComputeStage } from "../engine.ts";
 import {
   isArray,
+  isCustomScalar,
   isEither,
   isFunction,
+  isLeaf,
   isObject,
   isOptional,
   isQuantifier,
-  isScalar,
   isUnion,
+  LeafNode,
   ObjectNode,
   Type,
   TypeNode,
@@ -37,11 +39,18 @@ const SCALAR_TYPE_MAP = {
   "float": "Float",
   "string": "String",
   "file": "File",
+  "map": "Map",
+  "any": "JSON",
 };
 
+/** custom scalars are named after their title */
+function scalarName(type: LeafNode): string {
+  return isCustomScalar(type) ? type.title : SCALAR_TYPE_MAP[type.type];
+}
+
 function generateCustomScalar(type: TypeNode, idx: number) {
-  if (isScalar(type)) {
-    const id = type.type;
+  if (isLeaf(type)) {
+    const id = isCustomScalar(type) ? type.title : type.type;
     return {
       title: `_${id[0].toUpperCase()}${id.slice(1)}`,
       type: "object",
@@ -151,9 +160,9 @@ export class TypeGraphRuntime extends Runtime {
               getChildTypes(this.tg.types[type.input]),
               ({ type, idx }) => {
                 hasUnion ||= isUnion(type) || isEither(type);
-                if (isScalar(type)) {
+                if (isLeaf(type)) {
                   scalarTypeIndices.add(idx);
-                  this.scalarIndex.set(type.type, idx);
+                  this.scalarIndex.set(scalarName(type), idx);
                   return false;
                 }
                 if (filter(type)) {
@@ -173,9 +182,9 @@ export class TypeGraphRuntime extends Runtime {
             }
             // type is either a regular type or an input type reused in the output
             hasUnion ||= isUnion(type) || isEither(type);
-            if (isScalar(type)) {
+            if (isLeaf(type)) {
               scalarTypeIndices.add(idx);
-              this.scalarIndex.set(type.type, idx);
+              this.scalarIndex.set(scalarName(type), idx);
               return false;
             }
             if (filter(type)) {
@@ -187,8 +196,9 @@ export class TypeGraphRuntime extends Runtime {
 
         visitTypes(this.tg, getChildTypes(this.tg.types[0]), myVisitor);
         const distinctScalars = distinctBy(
-          [...scalarTypeIndices].map((idx) => this.tg.types[idx]),
-          (t) => t.type, // for scalars: one GraphQL type per `type` not `title`
+          [...scalarTypeIndices].map((idx) => this.tg.types[idx] as LeafNode),
+          // one GraphQL type per `type` not `title`, except for custom scalars
+          scalarName,
         );
         const scalarTypes = distinctScalars.map((type) =>
           this.formatType(type, false, false)
@@ -196,7 +206,7 @@ export class TypeGraphRuntime extends Runtime {
 
         const customScalarTypes = hasUnion
           ? distinctScalars.map((node) => {
-            const idx = this.scalarIndex.get(node.type)!;
+            const idx = this.scalarIndex.get(scalarName(node))!;
             const asObject = generateCustomScalar(node, idx);
             return this.formatType(asObject, false, false);
           })
@@ -316,11 +326,11 @@ export class TypeGraphRuntime extends Runtime {
       };
     }
 
-    if (isScalar(type)) {
+    if (isLeaf(type)) {
       return {
         ...common,
         kind: () => TypeKind.SCALAR,
-        name: () => SCALAR_TYPE_MAP[type.type],
+        name: () => scalarName(type),
         description: () => `${type.type} type`,
       };
     }
@@ -421,8 +431,8 @@ export class TypeGraphRuntime extends Runtime {
           possibleTypes: () => {
             return variants.map((idx) => {
               const variant = this.tg.types[idx];
-              if (isScalar(variant)) {
-                const idx = this.scalarIndex.get(variant.type)!;
+              if (isLeaf(variant)) {
+                const idx = this.scalarIndex.get(scalarName(variant))!;
                 const asObject = generateCustomScalar(variant, idx);
                 return this.formatType(asObject, false, false);
               } else {
@@ -446,6 +456,12 @@ export class TypeGraphRuntime extends Runtime {
       if (typeof p === "number") {
         return describeOne(p);
       }
+      if ("all" in p) {
+        return `all(${p.all.map(describeOne).join(", ")})`;
+      }
+      if ("any" in p) {
+        return `any(${p.any.map(describeOne).join(", ")})`;
+      }
       return Object.entries(p).map(
         ([eff, polIdx]) => `${eff}:${describeOne(polIdx)}`,
       ).join("; ");
@@ -10,6 +10,8 @@ export const Type = {
   FILE: "file",
   OBJECT: "object",
   ARRAY: "array",
+  MAP: "map",
+  SCALAR: "scalar",
   FUNCTION: "function",
   UNION: "union",
   EITHER: "either",
@@ -25,8 +27,10 @@ import type {
   FloatNode,
   FunctionNode,
   IntegerNode,
+  MapNode,
   ObjectNode,
   OptionalNode,
+  ScalarNode as CustomScalarNode,
   StringNode,
   TypeNode,
   UnionNode,
@@ -36,10 +40,12 @@ export type {
   AnyNode,
   ArrayNode,
   BooleanNode,
+  CustomScalarNode,
   FileNode,
   FloatNode,
   FunctionNode,
   IntegerNode,
+  MapNode,
   ObjectNode,
   OptionalNode,
   StringNode,
@@ -53,6 +59,7 @@ export type ScalarNode =
   | FloatNode
   | StringNode
   | FileNode;
+export type LeafNode = ScalarNode | MapNode | AnyNode | CustomScalarNode;
 export type QuantifierNode = OptionalNode | ArrayNode;
 
 //
@@ -94,11 +101,34 @@ export function isArray(t: TypeNode): t is ArrayNode {
   return t.type === Type.ARRAY;
 }
 
+export function isMap(t: TypeNode): t is MapNode {
+  return t.type === Type.MAP;
+}
+
+export function isAny(t: TypeNode): t is AnyNode {
+  return t.type === Type.ANY;
+}
+
+/** User-defined scalar, checked and serialized by its materializers */
+export function isCustomScalar(t: TypeNode): t is CustomScalarNode {
+  return t.type === Type.SCALAR;
+}
+
+/** Leaf values with no selection set: map and any values are returned whole */
+export function isJsonValue(t: TypeNode): t is MapNode | AnyNode {
+  return isMap(t) || isAny(t);
+}
+
 export function isScalar(t: TypeNode): t is ScalarNode {
   return isBoolean(t) || isInteger(t) || isNumber(t) || isString(t) ||
     isFile(t);
 }
 
+/** Types that take no selection set */
+export function isLeaf(t: TypeNode): t is LeafNode {
+  return isScalar(t) || isJsonValue(t) || isCustomScalar(t);
+}
+
 export function isQuantifier(t: TypeNode): t is QuantifierNode {
   return isOptional(t) || isArray(t);
 }
@@ -2,11 +2,14 @@
 // SPDX-License-Identifier: Elastic-2.0
 
 import {
+  AnyNode,
   ArrayNode,
   BooleanNode,
+  CustomScalarNode,
   FileNode,
   FloatNode,
   IntegerNode,
+  MapNode,
   ObjectNode,
   OptionalNode,
   StringNode,
@@ -131,6 +134,14 @@ export class CodeGenerator {
         "!formatValidator(value)",
         `"string does not statisfy the required format '${typeNode.format}'"`,
       );
+      if (typeNode.maxSize != null) {
+        // size of the base64-decoded content
+        this.line("else");
+        this.validation(
+          `value.length * 3 / 4 - value.match(/=*$/)[0].length > ${typeNode.maxSize}`,
+          `"expected maximum size: ${typeNode.maxSize} bytes"`,
+        );
+      }
       this.line("}");
     }
   }
@@ -213,6 +224,34 @@ export class CodeGenerator {
     return [itemType];
   }
 
+  generateMapValidator(
+    _typeNode: MapNode,
+    keyValidatorName: string,
+    valueValidatorName: string | null,
+  ) {
+    this.validation(
+      'typeof value !== "object" || value === null || Array.isArray(value)',
+      "`expected a map, got ${Array.isArray(value) ? 'array' : typeof value}`",
+    );
+    this.line("else {");
+    this.line("for (const [key, item] of Object.entries(value)) {");
+    this.line("const itemPath = path + `.${key}`");
+    this.line(`${keyValidatorName}(key, itemPath, errors, context)`);
+    if (valueValidatorName != null) {
+      this.line(`${valueValidatorName}(item, itemPath, errors, context)`);
+    }
+    this.line("}");
+    this.line("}");
+  }
+
+  // custom scalars are checked by their validator materializer
+  generateAnyValidator(_typeNode: AnyNode | CustomScalarNode) {
+    this.validation(
+      "value === undefined",
+      '"expected a value"',
+    );
+  }
+
   generateObjectValidator(
     typeNode: ObjectNode,
     propValidatorNames: Record<string, string>,
@@ -45,6 +45,7 @@ const formatValidators: Record<StringFormat, FormatValidator> = {
   phone: validator.isMobilePhone, // ??
   date: validator.isDate,
   "date-time": validator.isISO8601,
+  binary: validator.isBase64,
 };
 
 export const validationContext: ValidationContext = {
@@ -75,6 +75,18 @@ export class InputValidationCompiler {
             cg.generateArrayValidator(typeNode, functionName(typeNode.items));
             queue.push(typeNode.items);
             break;
+          case "map":
+            cg.generateMapValidator(
+              typeNode,
+              functionName(typeNode.key),
+              functionName(typeNode.value),
+            );
+            queue.push(typeNode.key, typeNode.value);
+            break;
+          case "any":
+          case "scalar":
+            cg.generateAnyValidator(typeNode);
+            break;
           case "object":
             cg.generateObjectValidator(
               typeNode,
@@ -94,6 +94,17 @@ export class RestSchemaGenerator {
           outputSchema = { type: "array", items: schema };
           break;
         }
+        case "map": {
+          const schema = this.#generateHelper(typeNode.value, inProgress);
+          outputSchema = { type: "object", additionalProperties: schema };
+          break;
+        }
+        case "any":
+        case "scalar": {
+          // custom scalars are checked by their validator materializer
+          outputSchema = {};
+          break;
+        }
         case "object": {
           const properties = {} as any;
           for (const [key, idx] of Object.entries(typeNode.properties)) {
@@ -8,7 +8,13 @@ import {
   SelectionSetNode,
 } from "graphql/ast";
 import { FieldNode, Kind } from "graphql";
-import { isScalar, ObjectNode, Type } from "../type_node.ts";
+import {
+  isCustomScalar,
+  isJsonValue,
+  isScalar,
+  ObjectNode,
+  Type,
+} from "../type_node.ts";
 import { TypeGraph } from "../typegraph/mod.ts";
 import { CodeGenerator } from "./code_generator.ts";
 import { getChildTypes } from "../typegraph/visitor.ts";
@@ -130,6 +136,43 @@ export class ResultValidationCompiler {
               break;
           }
         }
+      } else if (isJsonValue(typeNode) || isCustomScalar(typeNode)) {
+        if (entry.selectionSet != null) {
+          throw new Error(
+            `Unexpected selection set for type '${typeNode.type}' at '${entry.path}'`,
+          );
+        }
+
+        if (typeNode.type === "map") {
+          const keyValidatorName = this.validatorName(typeNode.key);
+          queue.push({
+            name: keyValidatorName,
+            typeIdx: typeNode.key,
+            path: entry.path,
+          });
+          // values with a structure are returned whole: there is no
+          // selection set to validate them against
+          const valueType = this.tg.type(typeNode.value);
+          let valueValidatorName: string | null = null;
+          if (
+            isScalar(valueType) || isJsonValue(valueType) ||
+            isCustomScalar(valueType)
+          ) {
+            valueValidatorName = this.validatorName(typeNode.value);
+            queue.push({
+              name: valueValidatorName,
+              typeIdx: typeNode.value,
+              path: entry.path,
+            });
+          }
+          cg.generateMapValidator(
+            typeNode,
+            keyValidatorName,
+            valueValidatorName,
+          );
+        } else {
+          cg.generateAnyValidator(typeNode);
+        }
       } else {
         // TODO: cannot check enum - perhaps we should disable enums for non-scalar types??
 
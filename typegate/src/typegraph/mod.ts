@@ -95,6 +95,8 @@ const GRAPHQL_SCALAR_TYPES = {
   [Type.INTEGER]: "Int",
   [Type.FLOAT]: "Float",
   [Type.STRING]: "String",
+  [Type.MAP]: "Map",
+  [Type.ANY]: "JSON",
 } as Partial<Record<TypeNode["type"], string>>;
 
 export class TypeGraph {
@@ -45,6 +45,8 @@ export function getChildTypes(type: TypeNode): number[] {
       return [type.item];
     case Type.ARRAY:
       return [type.items];
+    case Type.MAP:
+      return [type.key, type.value];
     case Type.OBJECT:
       return Object.values(type.properties);
     case Type.FUNCTION:
@@ -62,6 +64,8 @@ export function getChildTypes(type: TypeNode): number[] {
 export const Edge = {
   OPTIONAL_ITEM: "[item]",
   ARRAY_ITEMS: "[items]",
+  MAP_KEY: "[key]",
+  MAP_VALUE: "[value]",
   FUNCTION_INPUT: "[in]",
   FUNCTION_OUTPUT: "[out]",
   // OBJECT_PROPERTY: <property name>
@@ -73,6 +77,8 @@ export function getEdges(type: TypeNode): Record<string, number> {
       return { [Edge.OPTIONAL_ITEM]: type.item };
     case Type.ARRAY:
       return { [Edge.ARRAY_ITEMS]: type.items };
+    case Type.MAP:
+      return { [Edge.MAP_KEY]: type.key, [Edge.MAP_VALUE]: type.value };
     case Type.UNION:
     case Type.EITHER: {
       const variants = type.type == Type.UNION ? type.anyOf : type.oneOf;
@@ -11,12 +11,16 @@ export type OptionalNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
   as_id: boolean;
   item: number;
   default_value?: any;
+  null_default?: boolean;
+  kind?: OptionalKind | null;
 };
 export type BooleanNode = {
   type: "boolean";
@@ -26,6 +30,8 @@ export type BooleanNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
@@ -39,6 +45,8 @@ export type FloatNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
@@ -57,6 +65,8 @@ export type IntegerNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
@@ -75,6 +85,8 @@ export type StringNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
@@ -83,6 +95,7 @@ export type StringNode = {
   maxLength?: number | null;
   pattern?: string | null;
   format?: StringFormat | null;
+  maxSize?: number | null;
 };
 export type FileNode = {
   type: "file";
@@ -92,6 +105,8 @@ export type FileNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
@@ -108,6 +123,8 @@ export type ObjectNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
@@ -116,6 +133,7 @@ export type ObjectNode = {
     [k: string]: number;
   };
   required?: string[];
+  max_depth?: number | null;
 };
 export type ArrayNode = {
   type: "array";
@@ -125,6 +143,8 @@ export type ArrayNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
@@ -134,6 +154,40 @@ export type ArrayNode = {
   minItems?: number | null;
   uniqueItems?: boolean | null;
 };
+export type MapNode = {
+  type: "map";
+  title: string;
+  runtime: number;
+  policies: PolicyIndices[];
+  description?: string | null;
+  injection?: Injection | null;
+  enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
+  config?: {
+    [k: string]: unknown;
+  };
+  as_id: boolean;
+  key: number;
+  value: number;
+};
+export type ScalarNode = {
+  type: "scalar";
+  title: string;
+  runtime: number;
+  policies: PolicyIndices[];
+  description?: string | null;
+  injection?: Injection | null;
+  enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
+  config?: {
+    [k: string]: unknown;
+  };
+  as_id: boolean;
+  validator: number;
+  serializer: number;
+};
 export type FunctionNode = {
   type: "function";
   title: string;
@@ -142,6 +196,8 @@ export type FunctionNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
@@ -160,11 +216,14 @@ export type UnionNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
   as_id: boolean;
   anyOf: number[];
+  discriminator?: string | null;
 };
 export type EitherNode = {
   type: "either";
@@ -174,6 +233,8 @@ export type EitherNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
@@ -188,6 +249,8 @@ export type AnyNode = {
   description?: string | null;
   injection?: Injection | null;
   enum?: string[] | null;
+  examples?: string[] | null;
+  deprecated?: string | null;
   config?: {
     [k: string]: unknown;
   };
@@ -202,11 +265,17 @@ export type TypeNode =
   | FileNode
   | ObjectNode
   | ArrayNode
+  | MapNode
+  | ScalarNode
   | FunctionNode
   | UnionNode
   | EitherNode
   | AnyNode;
-export type PolicyIndices = number | PolicyIndicesByEffect;
+export type PolicyIndices =
+  | number
+  | PolicyIndicesAll
+  | PolicyIndicesAny
+  | PolicyIndicesByEffect;
 export type Injection = {
   source: "static";
   data: InjectionDataFor_String;
@@ -229,6 +298,7 @@ export type InjectionDataFor_String = SingleValueFor_String | {
 export type InjectionDataForUint32 = SingleValueForUint32 | {
   [k: string]: number;
 };
+export type OptionalKind = "absent" | "nullable";
 export type StringFormat =
   | "uuid"
   | "email"
@@ -238,8 +308,9 @@ export type StringFormat =
   | "ean"
   | "date"
   | "date-time"
-  | "phone";
-export type EffectType = "create" | "update" | "delete" | "none";
+  | "phone"
+  | "binary";
+export type EffectType = "create" | "update" | "delete" | "upsert" | "none";
 export type TGRuntime = KnownRuntime | UnknownRuntime;
 export type KnownRuntime = {
   name: "deno";
@@ -247,9 +318,15 @@ export type KnownRuntime = {
 } | {
   name: "graphql";
   data: GraphQLRuntimeData;
+} | {
+  name: "grpc";
+  data: GrpcRuntimeData;
 } | {
   name: "http";
   data: HTTPRuntimeData;
+} | {
+  name: "kafka";
+  data: KafkaRuntimeData;
 } | {
   name: "python_wasi";
   data: PythonRuntimeData;
@@ -317,11 +394,18 @@ export interface Typegraph {
   policies: Policy[];
   meta: TypeMeta;
 }
+export interface PolicyIndicesAll {
+  all: number[];
+}
+export interface PolicyIndicesAny {
+  any: number[];
+}
 export interface PolicyIndicesByEffect {
   none?: number | null;
   create?: number | null;
   delete?: number | null;
   update?: number | null;
+  upsert?: number | null;
 }
 export interface SingleValueFor_String {
   value: string;
@@ -340,6 +424,7 @@ export interface Materializer {
 export interface Effect {
   effect?: EffectType | null;
   idempotent: boolean;
+  idempotency_key?: string | null;
 }
 export interface DenoRuntimeData {
   worker: string;
@@ -350,11 +435,18 @@ export interface DenoRuntimeData {
 export interface GraphQLRuntimeData {
   endpoint: string;
 }
+export interface GrpcRuntimeData {
+  proto_file: string;
+  endpoint: string;
+}
 export interface HTTPRuntimeData {
   endpoint: string;
   cert_secret?: string | null;
   basic_auth_secret?: string | null;
 }
+export interface KafkaRuntimeData {
+  broker_secret: string;
+}
 export interface PythonRuntimeData {
   config?: string | null;
 }
@@ -395,7 +487,7 @@ export interface S3RuntimeData {
 }
 export interface TemporalRuntimeData {
   name: string;
-  host: string;
+  host_secret: string;
 }
 export interface WasmEdgeRuntimeData {
   config?: string | null;
@@ -422,6 +514,7 @@ export interface TypeMeta {
   auths: Auth[];
   rate?: Rate | null;
   version: string;
+  hash?: string | null;
 }
 export interface Queries {
   dynamic: boolean;
@@ -6,7 +6,7 @@ import { DenoRuntime } from "@typegraph/deno/src/runtimes/deno.ts";
 
 const a = t.integer();
 
-const s1 = t.struct({ a, b: t.integer({ min: 12 }) });
+const s1 = t.struct({ a, b: t.integer({ min: 12 }, { deprecated: "use a" }) });
 
 const b = t.integer({ min: 12, max: 43 });
 
@@ -6,7 +6,7 @@ from typegraph.runtimes.deno import DenoRuntime
 
 a = t.integer()
 
-s1 = t.struct({"a": a, "b": t.integer(min=12)})
+s1 = t.struct({"a": a, "b": t.integer(min=12, deprecated="use a")})
 
 b = t.integer(min=12, max=43)
 
@@ -416,3 +416,98 @@ def multiple_self_relationships(g: Graph):
     g.expose(
         createPerson=db.create(person),
     )
+
+
+@typegraph()
+def mapped_model(g: Graph):
+    db = PrismaRuntime("test", "POSTGRES")
+
+    user = t.struct(
+        {
+            "id": t.integer(as_id=True, config={"auto": True}),
+            "fullName": t.string(config={"db_name": "full_name"}),
+        },
+        name="User",
+        config={"db_name": "app_users"},
+    )
+
+    g.expose(
+        createUser=db.create(user),
+    )
+
+
+@typegraph()
+def native_types(g: Graph):
+    db = PrismaRuntime("test", "POSTGRES")
+
+    product = t.struct(
+        {
+            "id": t.integer(as_id=True, config={"auto": True}),
+            "name": t.string(config={"db_type": "VarChar(255)"}),
+            "price": t.float(config={"db_type": "Decimal(10, 2)"}),
+        },
+        name="Product",
+    )
+
+    g.expose(
+        createProduct=db.create(product),
+    )
+
+
+@typegraph()
+def scalar_list(g: Graph):
+    db = PrismaRuntime("test", "POSTGRES")
+
+    article = t.struct(
+        {
+            "id": t.integer(as_id=True, config={"auto": True}),
+            "tags": t.array(t.string()),
+        },
+        name="Article",
+    )
+
+    g.expose(
+        createArticle=db.create(article),
+    )
+
+
+@typegraph()
+def composite_id(g: Graph):
+    db = PrismaRuntime("test", "POSTGRES")
+
+    member = t.struct(
+        {
+            "tenant": t.string(),
+            "id": t.integer(),
+            "name": t.string(),
+        },
+        name="Member",
+        config={"composite_id": ["tenant", "id"]},
+    )
+
+    g.expose(
+        createMember=db.create(member),
+    )
+
+
+@typegraph()
+def indexes(g: Graph):
+    db = PrismaRuntime("test", "POSTGRES")
+
+    user = t.struct(
+        {
+            "id": t.integer(as_id=True, config={"auto": True}),
+            "email": t.string(),
+            "first_name": t.string(),
+            "last_name": t.string(),
+        },
+        name="User",
+        config={
+            "indexes": [["email"]],
+            "unique_indexes": [["first_name", "last_name"]],
+        },
+    )
+
+    g.expose(
+        createUser=db.create(user),
+    )
@@ -470,4 +470,75 @@ Meta.test("schema generation", async (t) => {
       );
     },
   );
+
+  await t.should("generate datamodel with mapped names", async () => {
+    await assertGeneratedSchema(
+      "mapped-model",
+      outdent`
+        model User {
+            id Int @id @default(autoincrement())
+            fullName String @db.Text @map("full_name")
+
+            @@map("app_users")
+        }
+      `,
+    );
+  });
+
+  await t.should("generate datamodel with native types", async () => {
+    await assertGeneratedSchema(
+      "native-types",
+      outdent`
+        model Product {
+            id Int @id @default(autoincrement())
+            name String @db.VarChar(255)
+            price Decimal @db.Decimal(10, 2)
+        }
+      `,
+    );
+  });
+
+  await t.should("generate datamodel with scalar list", async () => {
+    await assertGeneratedSchema(
+      "scalar-list",
+      outdent`
+        model Article {
+            id Int @id @default(autoincrement())
+            tags String[] @db.Text
+        }
+      `,
+    );
+  });
+
+  await t.should("generate datamodel with composite id", async () => {
+    await assertGeneratedSchema(
+      "composite-id",
+      outdent`
+        model Member {
+            tenant String @db.Text
+            id Int
+            name String @db.Text
+
+            @@id([tenant, id])
+        }
+      `,
+    );
+  });
+
+  await t.should("generate datamodel with indexes", async () => {
+    await assertGeneratedSchema(
+      "indexes",
+      outdent`
+        model User {
+            id Int @id @default(autoincrement())
+            email String @db.Text
+            first_name String @db.Text
+            last_name String @db.Text
+
+            @@index([email])
+            @@unique([first_name, last_name])
+        }
+      `,
+    );
+  });
 });
@@ -5,7 +5,7 @@ from typegraph.providers.temporal import TemporalRuntime
 @typegraph()
 def temporal(g: Graph):
     public = Policy.public()
-    temporal = TemporalRuntime("<name>", "<host>")
+    temporal = TemporalRuntime("<name>", "HOST")
     arg = t.struct({"some_field": t.string()})
 
     g.expose(
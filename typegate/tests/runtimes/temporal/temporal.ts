@@ -6,7 +6,7 @@ import { TemporalRuntime } from "@typegraph/deno/src/providers/temporal.ts";
 
 typegraph("temporal", (g) => {
   const pub = Policy.public();
-  const temporal = new TemporalRuntime("<name>", "<host>");
+  const temporal = new TemporalRuntime("<name>", "HOST");
   const arg = t.struct({ some_field: t.string() });
 
   g.expose(
@@ -0,0 +1,17 @@
+from typegraph import Policy, t, typegraph, Graph
+from typegraph.runtimes.deno import DenoRuntime
+
+
+@typegraph()
+def bytes_node(g: Graph):
+    deno = DenoRuntime()
+
+    content = t.bytes(max_size=4)
+
+    g.expose(
+        upload=deno.func(
+            t.struct({"content": content}),
+            t.integer(),
+            code="({ content }) => content.length",
+        ).with_policy(Policy.public()),
+    )
@@ -0,0 +1,32 @@
+// Copyright Metatype OÜ, licensed under the Elastic License 2.0.
+// SPDX-License-Identifier: Elastic-2.0
+
+import { gql, Meta } from "../utils/mod.ts";
+
+Meta.test("Binary strings", async (t) => {
+  const e = await t.engine("type_nodes/bytes_node.py");
+
+  await t.should("accept content within the maximum size", async () => {
+    // 4 bytes
+    await gql`
+      query {
+        upload(content: "AQIDBA==")
+      }
+    `
+      .expectData({
+        upload: 8,
+      })
+      .on(e);
+  });
+
+  await t.should("reject content over the maximum size", async () => {
+    // 5 bytes
+    await gql`
+      query {
+        upload(content: "AQIDBAU=")
+      }
+    `
+      .expectErrorContains("expected maximum size: 4 bytes")
+      .on(e);
+  });
+});
@@ -0,0 +1,24 @@
+from typegraph import Policy, t, typegraph, Graph
+from typegraph.runtimes.deno import DenoRuntime
+
+
+@typegraph()
+def map_node(g: Graph):
+    deno = DenoRuntime()
+    public = Policy.public()
+
+    scores = t.map(t.string(min=1), t.integer(min=0), name="Scores")
+    metadata = t.any(name="Metadata")
+
+    g.expose(
+        scores=deno.func(
+            t.struct({"scores": scores}),
+            scores,
+            code="({ scores }) => scores",
+        ).with_policy(public),
+        metadata=deno.func(
+            t.struct({"metadata": metadata}),
+            metadata,
+            code="({ metadata }) => metadata",
+        ).with_policy(public),
+    )
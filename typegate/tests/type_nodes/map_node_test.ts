@@ -0,0 +1,55 @@
+// Copyright Metatype OÜ, licensed under the Elastic License 2.0.
+// SPDX-License-Identifier: Elastic-2.0
+
+import { gql, Meta } from "../utils/mod.ts";
+
+Meta.test("Map and any types", async (t) => {
+  const e = await t.engine("type_nodes/map_node.py");
+
+  await t.should("accept a map argument", async () => {
+    await gql`
+      query {
+        scores(scores: { alice: 12, bob: 7 })
+      }
+    `
+      .expectData({
+        scores: { alice: 12, bob: 7 },
+      })
+      .on(e);
+  });
+
+  await t.should("validate the map values", async () => {
+    await gql`
+      query {
+        scores(scores: { alice: -1 })
+      }
+    `
+      .expectErrorContains("expected minimum value: 0, got -1")
+      .on(e);
+  });
+
+  await t.should("accept any value", async () => {
+    await gql`
+      query {
+        metadata(metadata: { tags: ["a", "b"], nested: { value: null } })
+      }
+    `
+      .expectData({
+        metadata: { tags: ["a", "b"], nested: { value: null } },
+      })
+      .on(e);
+  });
+
+  await t.should("accept map values from variables", async () => {
+    await gql`
+      query Q($scores: Map!) {
+        scores(scores: $scores)
+      }
+    `
+      .withVars({ scores: { carol: 3 } })
+      .expectData({
+        scores: { carol: 3 },
+      })
+      .on(e);
+  });
+});
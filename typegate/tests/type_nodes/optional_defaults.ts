@@ -0,0 +1,30 @@
+// Copyright Metatype OÜ, licensed under the Elastic License 2.0.
+// SPDX-License-Identifier: Elastic-2.0
+
+import { Policy, t, typegraph } from "@typegraph/deno/src/mod.ts";
+import { DenoRuntime } from "@typegraph/deno/src/runtimes/deno.ts";
+
+typegraph("optional_defaults", (g) => {
+  const deno = new DenoRuntime();
+  const pub = Policy.public();
+
+  g.expose({
+    args: deno.func(
+      t.struct({
+        omitted: t.string().optional(),
+        nulled: t.string().optional({ defaultItem: null }),
+        defaulted: t.string().optional({ defaultItem: "default" }),
+      }),
+      t.struct({
+        omitted: t.string(),
+        nulled: t.string(),
+        defaulted: t.string(),
+      }),
+      {
+        code: `(args) => Object.fromEntries(
+          ["omitted", "nulled", "defaulted"].map((k) => [k, JSON.stringify(args[k])])
+        )`,
+      },
+    ).withPolicy(pub),
+  });
+});
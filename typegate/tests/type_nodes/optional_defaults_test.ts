@@ -0,0 +1,48 @@
+// Copyright Metatype OÜ, licensed under the Elastic License 2.0.
+// SPDX-License-Identifier: Elastic-2.0
+
+import { gql, Meta } from "../utils/mod.ts";
+
+Meta.test("Optional defaults", async (t) => {
+  const e = await t.engine("type_nodes/optional_defaults.ts");
+
+  await t.should("pass null for omitted optional arguments", async () => {
+    await gql`
+      query {
+        args {
+          omitted
+          nulled
+          defaulted
+        }
+      }
+    `
+      .expectData({
+        args: {
+          omitted: "null",
+          nulled: "null",
+          defaulted: '"default"',
+        },
+      })
+      .on(e);
+  });
+
+  await t.should("pass the provided values", async () => {
+    await gql`
+      query {
+        args(omitted: "a", nulled: "b", defaulted: "c") {
+          omitted
+          nulled
+          defaulted
+        }
+      }
+    `
+      .expectData({
+        args: {
+          omitted: '"a"',
+          nulled: '"b"',
+          defaulted: '"c"',
+        },
+      })
+      .on(e);
+  });
+});
@@ -0,0 +1,33 @@
+from typegraph import Policy, t, typegraph, Graph
+from typegraph.runtimes.deno import DenoRuntime
+
+
+@typegraph()
+def scalar_node(g: Graph):
+    deno = DenoRuntime()
+    public = Policy.public()
+
+    validator = deno.func(
+        t.struct({"value": t.any()}),
+        t.boolean(),
+        code="({ value }) => typeof value === 'string' && /^\\d+\\.\\d{2} [A-Z]{3}$/.test(value)",
+    ).mat
+    serializer = deno.func(
+        t.struct({"value": t.any()}),
+        t.any(),
+        code="({ value }) => typeof value === 'string' ? value : `${(value.cents / 100).toFixed(2)} EUR`",
+    ).mat
+    money = t.scalar("Money", validator, serializer)
+
+    g.expose(
+        echo=deno.func(
+            t.struct({"amount": money}),
+            money,
+            code="({ amount }) => amount",
+        ).with_policy(public),
+        fromCents=deno.func(
+            t.struct({"cents": t.integer()}),
+            money,
+            code="({ cents }) => ({ cents })",
+        ).with_policy(public),
+    )
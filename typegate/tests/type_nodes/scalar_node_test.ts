@@ -0,0 +1,55 @@
+// Copyright Metatype OÜ, licensed under the Elastic License 2.0.
+// SPDX-License-Identifier: Elastic-2.0
+
+import { gql, Meta } from "../utils/mod.ts";
+
+Meta.test("Custom scalar types", async (t) => {
+  const e = await t.engine("type_nodes/scalar_node.py");
+
+  await t.should("accept a valid value", async () => {
+    await gql`
+      query {
+        echo(amount: "12.50 EUR")
+      }
+    `
+      .expectData({
+        echo: "12.50 EUR",
+      })
+      .on(e);
+  });
+
+  await t.should("check the value with the validator", async () => {
+    await gql`
+      query {
+        echo(amount: "twelve")
+      }
+    `
+      .expectErrorContains("invalid value for argument 'amount'")
+      .on(e);
+  });
+
+  await t.should("serialize the result with the serializer", async () => {
+    await gql`
+      query {
+        fromCents(cents: 1250)
+      }
+    `
+      .expectData({
+        fromCents: "12.50 EUR",
+      })
+      .on(e);
+  });
+
+  await t.should("accept values from variables", async () => {
+    await gql`
+      query Q($amount: Money!) {
+        echo(amount: $amount)
+      }
+    `
+      .withVars({ amount: "3.00 USD" })
+      .expectData({
+        echo: "3.00 USD",
+      })
+      .on(e);
+  });
+});
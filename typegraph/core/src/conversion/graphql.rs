@@ -0,0 +1,265 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use std::collections::BTreeSet;
+
+use common::typegraph::{EffectType, TypeNode, Typegraph};
+use indexmap::IndexMap;
+
+use crate::errors::Result;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Query,
+    Mutation,
+}
+
+impl Operation {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Operation::Query => "Query",
+            Operation::Mutation => "Mutation",
+        }
+    }
+}
+
+/// GraphQL schema (SDL) of a finalized typegraph.
+/// The functions without side effects are exposed as queries, the other ones
+/// as mutations; the namespaces get their own type for each operation.
+pub fn to_sdl(tg: &Typegraph) -> Result<String> {
+    let mut builder = SdlBuilder {
+        tg,
+        scalars: BTreeSet::new(),
+        definitions: IndexMap::new(),
+    };
+
+    let mut roots = vec![];
+    for op in [Operation::Query, Operation::Mutation] {
+        if let Some(fields) = builder.root_fields(0, op)? {
+            roots.push(format!("type {} {{\n{fields}\n}}", op.type_name()));
+        }
+    }
+
+    let scalars = builder
+        .scalars
+        .iter()
+        .map(|s| format!("scalar {s}"))
+        .collect::<Vec<_>>();
+    let blocks = (!scalars.is_empty())
+        .then(|| scalars.join("\n"))
+        .into_iter()
+        .chain(roots)
+        .chain(builder.definitions.into_values())
+        .collect::<Vec<_>>();
+
+    Ok(format!("{}\n", blocks.join("\n\n")))
+}
+
+struct SdlBuilder<'a> {
+    tg: &'a Typegraph,
+    scalars: BTreeSet<String>,
+    /// type definitions by name, in discovery order
+    definitions: IndexMap<String, String>,
+}
+
+impl<'a> SdlBuilder<'a> {
+    fn node(&self, idx: u32) -> Result<&'a TypeNode> {
+        self.tg
+            .types
+            .get(idx as usize)
+            .ok_or_else(|| format!("type index {idx} out of bounds"))
+    }
+
+    fn scalar(&mut self, name: &str) -> String {
+        self.scalars.insert(name.to_string());
+        name.to_string()
+    }
+
+    /// Fields of the root or namespace object at `idx` for the operation,
+    /// or `None` when it has none
+    fn root_fields(&mut self, idx: u32, op: Operation) -> Result<Option<String>> {
+        let TypeNode::Object { data, .. } = self.node(idx)? else {
+            return Err("expected an object as root or namespace".to_string());
+        };
+
+        let mut fields = vec![];
+        for (name, &child) in data.properties.iter() {
+            match self.node(child)? {
+                TypeNode::Function { data: func, .. } => {
+                    let effect = &self.tg.materializers[func.materializer as usize].effect;
+                    let func_op = match effect.effect {
+                        None | Some(EffectType::None) => Operation::Query,
+                        _ => Operation::Mutation,
+                    };
+                    if func_op == op {
+                        fields.push(self.field(name, child)?);
+                    }
+                }
+                TypeNode::Object { base, .. } => {
+                    let type_name = format!("{}{}", base.title, op.type_name());
+                    if let Some(namespace) = self.root_fields(child, op)? {
+                        self.definitions.insert(
+                            type_name.clone(),
+                            format!("type {type_name} {{\n{namespace}\n}}"),
+                        );
+                        fields.push(format!("  {name}: {type_name}!"));
+                    }
+                }
+                _ => return Err(format!("unexpected export type at {name:?}")),
+            }
+        }
+
+        Ok((!fields.is_empty()).then(|| fields.join("\n")))
+    }
+
+    /// Output field, with the arguments of the function types
+    fn field(&mut self, name: &str, idx: u32) -> Result<String> {
+        let node = self.node(idx)?;
+        let field = match node {
+            TypeNode::Function { data, .. } => {
+                let TypeNode::Object { data: inp, .. } = self.node(data.input)? else {
+                    return Err(format!("expected an object as input of {name:?}"));
+                };
+                let mut args = vec![];
+                for (arg, &arg_idx) in inp.properties.iter() {
+                    // injected values are not given by the client
+                    if self.node(arg_idx)?.base().injection.is_none() {
+                        args.push(format!("{arg}: {}", self.type_ref(arg_idx, true)?));
+                    }
+                }
+                let args = if args.is_empty() {
+                    String::new()
+                } else {
+                    format!("({})", args.join(", "))
+                };
+                format!("  {name}{args}: {}", self.type_ref(data.output, false)?)
+            }
+            _ => format!("  {name}: {}", self.type_ref(idx, false)?),
+        };
+
+        Ok(match &node.base().deprecated {
+            Some(reason) => format!(
+                "{field} @deprecated(reason: {})",
+                serde_json::to_string(reason).map_err(|e| e.to_string())?
+            ),
+            None => field,
+        })
+    }
+
+    fn type_ref(&mut self, idx: u32, input: bool) -> Result<String> {
+        match self.node(idx)? {
+            TypeNode::Optional { data, .. } => self.nullable_type_ref(data.item, input),
+            _ => Ok(format!("{}!", self.nullable_type_ref(idx, input)?)),
+        }
+    }
+
+    fn nullable_type_ref(&mut self, idx: u32, input: bool) -> Result<String> {
+        Ok(match self.node(idx)? {
+            TypeNode::Optional { data, .. } => self.nullable_type_ref(data.item, input)?,
+            TypeNode::Array { data, .. } => format!("[{}]", self.type_ref(data.items, input)?),
+            TypeNode::Boolean { .. } => "Boolean".to_string(),
+            TypeNode::Integer { .. } => "Int".to_string(),
+            TypeNode::Float { .. } => "Float".to_string(),
+            TypeNode::String { base, .. } if base.as_id => "ID".to_string(),
+            TypeNode::String { .. } => "String".to_string(),
+            TypeNode::File { .. } => self.scalar("File"),
+            TypeNode::Any { .. } | TypeNode::Map { .. } => self.scalar("JSON"),
+            TypeNode::Scalar { base, .. } => self.scalar(&base.title),
+            TypeNode::Object { base, data } => {
+                let name = if input {
+                    format!("{}Inp", base.title)
+                } else {
+                    base.title.clone()
+                };
+                if data.properties.is_empty() {
+                    // empty types are not valid GraphQL
+                    return Ok(self.scalar("JSON"));
+                }
+                if !self.definitions.contains_key(&name) {
+                    // reserve the name first, for the recursive types
+                    self.definitions.insert(name.clone(), String::new());
+                    let fields = data
+                        .properties
+                        .iter()
+                        .map(|(prop, &prop_idx)| {
+                            if input {
+                                Ok(format!("  {prop}: {}", self.type_ref(prop_idx, true)?))
+                            } else {
+                                self.field(prop, prop_idx)
+                            }
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    let keyword = if input { "input" } else { "type" };
+                    self.definitions.insert(
+                        name.clone(),
+                        format!("{keyword} {name} {{\n{}\n}}", fields.join("\n")),
+                    );
+                }
+                name
+            }
+            TypeNode::Union { base, data } => self.union(&base.title, &data.any_of, input)?,
+            TypeNode::Either { base, data } => self.union(&base.title, &data.one_of, input)?,
+            TypeNode::Function { .. } => {
+                return Err("function types are only allowed as fields".to_string())
+            }
+        })
+    }
+
+    /// GraphQL unions only have object members and are not allowed in
+    /// inputs; the other ones fall back to the `JSON` scalar
+    fn union(&mut self, title: &str, variants: &[u32], input: bool) -> Result<String> {
+        let all_objects = variants
+            .iter()
+            .map(|&v| self.node(v).map(|n| matches!(n, TypeNode::Object { .. })))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .all(|is_object| is_object);
+        if input || !all_objects {
+            return Ok(self.scalar("JSON"));
+        }
+
+        if !self.definitions.contains_key(title) {
+            self.definitions.insert(title.to_string(), String::new());
+            let members = variants
+                .iter()
+                .map(|&v| self.nullable_type_ref(v, false))
+                .collect::<Result<Vec<_>>>()?;
+            self.definitions.insert(
+                title.to_string(),
+                format!("union {title} = {}", members.join(" | ")),
+            );
+        }
+        Ok(title.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::core::Core;
+    use crate::Lib;
+
+    #[test]
+    fn test_graphql_sdl() -> Result<(), String> {
+        Store::reset()?;
+        let a = t::integer().build()?;
+        let b = t::integer().min(12).max(44).build()?;
+        let num_idx = t::float().build()?;
+        let array_idx = t::array(num_idx).build()?;
+        let c = t::optional(array_idx).build()?;
+
+        let s = t::struct_()
+            .prop("one", a)
+            .prop("two", b)
+            .prop("three", c)
+            .build()?;
+
+        let mat = dummy_mat()?;
+        expose_one("one", t::func(s, b, mat)?)?;
+        let sdl = Lib::typegraph_to_graphql_sdl(Lib::finalize_typegraph()?)?;
+        insta::assert_snapshot!(sdl);
+        Ok(())
+    }
+}
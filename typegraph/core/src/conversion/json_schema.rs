@@ -0,0 +1,238 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+
+use crate::errors::Result;
+use crate::types::{Type, TypeId};
+use crate::wit::core::OptionalKind;
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Standalone JSON Schema of a type; the named structs nested in it are
+/// defined under `$defs`.
+pub fn to_json_schema(type_id: TypeId) -> Result<String> {
+    let mut builder = JsonSchemaBuilder {
+        root: type_id.attrs()?.concrete_type,
+        in_root: false,
+        defs: IndexMap::new(),
+    };
+    let mut schema = builder.schema(type_id)?;
+
+    let mut doc = Map::new();
+    doc.insert("$schema".to_string(), json!(DRAFT));
+    if let Value::Object(schema) = &mut schema {
+        doc.append(schema);
+    }
+    if !builder.defs.is_empty() {
+        doc.insert("$defs".to_string(), json!(builder.defs));
+    }
+    serde_json::to_string(&doc).map_err(|e| e.to_string())
+}
+
+struct JsonSchemaBuilder {
+    root: TypeId,
+    /// whether the root struct is being converted, for the recursive types
+    in_root: bool,
+    defs: IndexMap<String, Value>,
+}
+
+impl JsonSchemaBuilder {
+    fn schema(&mut self, type_id: TypeId) -> Result<Value> {
+        let type_id = type_id.attrs()?.concrete_type;
+        Ok(match type_id.as_type()? {
+            Type::Boolean(_) => json!({ "type": "boolean" }),
+            Type::Json(_) => json!({}),
+            // custom scalar, checked by its validator materializer
+            Type::Scalar(t) => json!({ "title": t.base.name }),
+            Type::Integer(t) => {
+                let mut schema = json!({ "type": "integer" });
+                set(&mut schema, "minimum", t.data.min);
+                set(&mut schema, "maximum", t.data.max);
+                set(&mut schema, "exclusiveMinimum", t.data.exclusive_minimum);
+                set(&mut schema, "exclusiveMaximum", t.data.exclusive_maximum);
+                set(&mut schema, "multipleOf", t.data.multiple_of);
+                set(&mut schema, "enum", t.data.enumeration.clone());
+                schema
+            }
+            Type::Float(t) => {
+                let mut schema = json!({ "type": "number" });
+                set(&mut schema, "minimum", t.data.min);
+                set(&mut schema, "maximum", t.data.max);
+                set(&mut schema, "exclusiveMinimum", t.data.exclusive_minimum);
+                set(&mut schema, "exclusiveMaximum", t.data.exclusive_maximum);
+                set(&mut schema, "multipleOf", t.data.multiple_of);
+                set(&mut schema, "enum", t.data.enumeration.clone());
+                schema
+            }
+            Type::String(t) => {
+                let mut schema = json!({ "type": "string" });
+                set(&mut schema, "minLength", t.data.min);
+                set(&mut schema, "maxLength", t.data.max);
+                set(&mut schema, "pattern", t.data.pattern.clone());
+                match t.data.format.as_deref() {
+                    Some("binary") => set(&mut schema, "contentEncoding", Some("base64")),
+                    format => set(&mut schema, "format", format),
+                }
+                set(&mut schema, "enum", parse_values(&t.data.enumeration)?);
+                schema
+            }
+            Type::File(_) => json!({ "type": "string", "format": "binary" }),
+            Type::Optional(t) => {
+                let item = self.schema(t.data.of.into())?;
+                let mut schema = match t.data.kind {
+                    Some(OptionalKind::Absent) => item,
+                    _ => json!({ "anyOf": [item, { "type": "null" }] }),
+                };
+                if let Some(default) = &t.data.default_item {
+                    schema["default"] = serde_json::from_str(default).map_err(|e| e.to_string())?;
+                }
+                schema
+            }
+            Type::Array(t) => {
+                let mut schema =
+                    json!({ "type": "array", "items": self.schema(t.data.of.into())? });
+                set(&mut schema, "minItems", t.data.min);
+                set(&mut schema, "maxItems", t.data.max);
+                set(&mut schema, "uniqueItems", t.data.unique_items);
+                schema
+            }
+            Type::Map(t) => json!({
+                "type": "object",
+                "additionalProperties": self.schema(t.data.value.into())?,
+            }),
+            Type::Union(t) => json!({ "anyOf": self.schemas(&t.data.variants)? }),
+            Type::Either(t) => json!({ "oneOf": self.schemas(&t.data.variants)? }),
+            Type::Struct(t) => {
+                if type_id == self.root {
+                    if self.in_root {
+                        return Ok(json!({ "$ref": "#" }));
+                    }
+                    self.in_root = true;
+                    let schema = self.struct_schema(&t);
+                    self.in_root = false;
+                    return schema;
+                }
+                let Some(name) = t.base.name.clone() else {
+                    return self.struct_schema(&t);
+                };
+                if !self.defs.contains_key(&name) {
+                    // reserve the name first, for the recursive types
+                    self.defs.insert(name.clone(), Value::Null);
+                    let schema = self.struct_schema(&t)?;
+                    self.defs.insert(name.clone(), schema);
+                }
+                json!({ "$ref": format!("#/$defs/{name}") })
+            }
+            Type::Func(_) => return Err(format!("{} has no JSON Schema", type_id.repr()?)),
+            Type::Proxy(_) | Type::WithPolicy(_) | Type::WithInjection(_) => unreachable!(),
+        })
+    }
+
+    fn schemas(&mut self, type_ids: &[u32]) -> Result<Vec<Value>> {
+        type_ids.iter().map(|id| self.schema(id.into())).collect()
+    }
+
+    fn struct_schema(&mut self, t: &crate::types::Struct) -> Result<Value> {
+        let mut properties = Map::new();
+        for (name, prop) in t.iter_props() {
+            properties.insert(name.to_string(), self.schema(prop)?);
+        }
+        let mut schema = json!({
+            "type": "object",
+            "properties": properties,
+            "required": t.required_props()?,
+        });
+        if !t.data.additional_props {
+            schema["additionalProperties"] = json!(false);
+        }
+        set(&mut schema, "enum", parse_values(&t.data.enumeration)?);
+        Ok(schema)
+    }
+}
+
+fn set(schema: &mut Value, key: &str, value: Option<impl serde::Serialize>) {
+    if let Some(value) = value {
+        schema[key] = json!(value);
+    }
+}
+
+/// enumerations of strings and structs are stored as json strings
+fn parse_values(values: &Option<Vec<String>>) -> Result<Option<Vec<Value>>> {
+    values
+        .as_ref()
+        .map(|values| {
+            values
+                .iter()
+                .map(|v| serde_json::from_str(v).map_err(|e| e.to_string()))
+                .collect()
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::Lib;
+
+    #[test]
+    fn test_json_schema() -> Result<(), String> {
+        use serde_json::{json, Value};
+
+        Store::reset()?;
+        let code = t::string()
+            .min(3)
+            .max(8)
+            .pattern("^[A-Z]+$")
+            .format("hostname")
+            .build()?;
+        let schema: Value = serde_json::from_str(&Lib::type_to_json_schema(code)?).unwrap();
+        assert_eq!(
+            schema,
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "string",
+                "minLength": 3,
+                "maxLength": 8,
+                "pattern": "^[A-Z]+$",
+                "format": "hostname",
+            })
+        );
+
+        let user = t::struct_()
+            .propx("name", t::string())?
+            .named("User")
+            .build()?;
+        let post = t::struct_()
+            .propx("views", t::integer().min(0))?
+            .prop("author", user)
+            .prop_optional("reviewer", user)?
+            .build()?;
+        let schema: Value = serde_json::from_str(&Lib::type_to_json_schema(post)?).unwrap();
+        assert_eq!(
+            schema,
+            json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "properties": {
+                    "views": { "type": "integer", "minimum": 0 },
+                    "author": { "$ref": "#/$defs/User" },
+                    "reviewer": { "anyOf": [{ "$ref": "#/$defs/User" }, { "type": "null" }] },
+                },
+                "required": ["views", "author"],
+                "additionalProperties": false,
+                "$defs": {
+                    "User": {
+                        "type": "object",
+                        "properties": { "name": { "type": "string" } },
+                        "required": ["name"],
+                        "additionalProperties": false,
+                    },
+                },
+            })
+        );
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
+pub mod graphql;
+pub mod json_schema;
+pub mod openapi;
 pub mod params;
 pub mod policies;
 pub mod runtimes;
+pub mod type_info;
 pub mod types;
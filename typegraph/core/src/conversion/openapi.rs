@@ -0,0 +1,314 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use common::typegraph::{EffectType, StringFormat, TypeNode, Typegraph};
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+
+use crate::errors::Result;
+
+/// OpenAPI 3.0 document of a finalized typegraph, with one path per exposed
+/// function. The HTTP method is derived from the effect of the materializer;
+/// the input is given as query parameters for `GET`, as a json body otherwise.
+pub fn to_openapi(tg: &Typegraph) -> Result<String> {
+    let mut builder = OpenApiBuilder {
+        tg,
+        schemas: IndexMap::new(),
+    };
+
+    let mut paths = Map::new();
+    builder.collect_paths(0, &mut vec![], &mut paths)?;
+
+    let doc = json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": tg.types[0].base().title,
+            "version": tg.meta.version,
+        },
+        "paths": paths,
+        "components": {
+            "schemas": builder.schemas,
+        },
+    });
+    serde_json::to_string_pretty(&doc).map_err(|e| e.to_string())
+}
+
+struct OpenApiBuilder<'a> {
+    tg: &'a Typegraph,
+    /// object schemas by title, referenced from the operations
+    schemas: IndexMap<String, Value>,
+}
+
+impl<'a> OpenApiBuilder<'a> {
+    fn node(&self, idx: u32) -> Result<&'a TypeNode> {
+        self.tg
+            .types
+            .get(idx as usize)
+            .ok_or_else(|| format!("type index {idx} out of bounds"))
+    }
+
+    /// Paths of the functions of the root or namespace object at `idx`
+    fn collect_paths(
+        &mut self,
+        idx: u32,
+        path: &mut Vec<String>,
+        paths: &mut Map<String, Value>,
+    ) -> Result<()> {
+        let TypeNode::Object { data, .. } = self.node(idx)? else {
+            return Err("expected an object as root or namespace".to_string());
+        };
+
+        for (name, &child) in data.properties.iter() {
+            path.push(name.clone());
+            match self.node(child)? {
+                TypeNode::Function { data: func, .. } => {
+                    let effect = &self.tg.materializers[func.materializer as usize].effect;
+                    let method = match effect.effect {
+                        None | Some(EffectType::None) => "get",
+                        Some(EffectType::Create) => "post",
+                        Some(EffectType::Update) | Some(EffectType::Upsert) => "put",
+                        Some(EffectType::Delete) => "delete",
+                    };
+
+                    let TypeNode::Object { data: inp, .. } = self.node(func.input)? else {
+                        return Err(format!("expected an object as input of {name:?}"));
+                    };
+                    let mut operation = Map::new();
+                    operation.insert("operationId".to_string(), json!(path.join("_")));
+                    if method == "get" {
+                        let mut parameters = vec![];
+                        for (arg, &arg_idx) in inp.properties.iter() {
+                            // injected values are not given by the client
+                            if self.node(arg_idx)?.base().injection.is_some() {
+                                continue;
+                            }
+                            parameters.push(json!({
+                                "name": arg,
+                                "in": "query",
+                                "required": inp.required.contains(arg),
+                                "schema": self.schema(arg_idx)?,
+                            }));
+                        }
+                        if !parameters.is_empty() {
+                            operation.insert("parameters".to_string(), json!(parameters));
+                        }
+                    } else if !inp.properties.is_empty() {
+                        operation.insert(
+                            "requestBody".to_string(),
+                            json!({
+                                "required": true,
+                                "content": {
+                                    "application/json": {
+                                        "schema": self.schema(func.input)?,
+                                    },
+                                },
+                            }),
+                        );
+                    }
+                    operation.insert(
+                        "responses".to_string(),
+                        json!({
+                            "200": {
+                                "description": "Successful operation",
+                                "content": {
+                                    "application/json": {
+                                        "schema": self.schema(func.output)?,
+                                    },
+                                },
+                            },
+                        }),
+                    );
+
+                    paths.insert(format!("/{}", path.join("/")), json!({ method: operation }));
+                }
+                TypeNode::Object { .. } => self.collect_paths(child, path, paths)?,
+                _ => return Err(format!("unexpected export type at {name:?}")),
+            }
+            path.pop();
+        }
+        Ok(())
+    }
+
+    fn schema(&mut self, idx: u32) -> Result<Value> {
+        let node = self.node(idx)?;
+        let mut schema = match node {
+            TypeNode::Optional { data, .. } => {
+                let item = self.schema(data.item)?;
+                // no sibling keywords are allowed next to `$ref` in OpenAPI 3.0
+                return Ok(if item.get("$ref").is_some() {
+                    json!({ "allOf": [item], "nullable": true })
+                } else {
+                    let mut item = item;
+                    item["nullable"] = json!(true);
+                    item
+                });
+            }
+            TypeNode::Boolean { .. } => json!({ "type": "boolean" }),
+            TypeNode::Integer { data, .. } => {
+                let mut schema = json!({ "type": "integer", "format": "int32" });
+                set_bounds(
+                    &mut schema,
+                    data.minimum,
+                    data.maximum,
+                    data.exclusive_minimum,
+                    data.exclusive_maximum,
+                );
+                set(&mut schema, "multipleOf", data.multiple_of);
+                schema
+            }
+            TypeNode::Float { data, .. } => {
+                let mut schema = json!({ "type": "number" });
+                set_bounds(
+                    &mut schema,
+                    data.minimum,
+                    data.maximum,
+                    data.exclusive_minimum,
+                    data.exclusive_maximum,
+                );
+                set(&mut schema, "multipleOf", data.multiple_of);
+                schema
+            }
+            TypeNode::String { data, .. } => {
+                let mut schema = json!({ "type": "string" });
+                set(&mut schema, "minLength", data.min_length);
+                set(&mut schema, "maxLength", data.max_length);
+                set(&mut schema, "pattern", data.pattern.clone());
+                let format = data.format.as_ref().map(|format| match format {
+                    // base64-encoded
+                    StringFormat::Binary => json!("byte"),
+                    format => serde_json::to_value(format).unwrap(),
+                });
+                set(&mut schema, "format", format);
+                schema
+            }
+            TypeNode::File { .. } => json!({ "type": "string", "format": "binary" }),
+            TypeNode::Object { base, data } => {
+                if !self.schemas.contains_key(&base.title) {
+                    // reserve the name first, for the recursive types
+                    self.schemas.insert(base.title.clone(), Value::Null);
+                    let mut properties = Map::new();
+                    for (prop, &prop_idx) in data.properties.iter() {
+                        let prop_schema = match self.node(prop_idx)? {
+                            // computed field
+                            TypeNode::Function { data, .. } => self.schema(data.output)?,
+                            _ => self.schema(prop_idx)?,
+                        };
+                        properties.insert(prop.clone(), prop_schema);
+                    }
+                    let mut schema = json!({ "type": "object", "properties": properties });
+                    if !data.required.is_empty() {
+                        schema["required"] = json!(data.required);
+                    }
+                    self.schemas.insert(base.title.clone(), schema);
+                }
+                return Ok(json!({ "$ref": format!("#/components/schemas/{}", base.title) }));
+            }
+            TypeNode::Array { data, .. } => {
+                let mut schema = json!({ "type": "array", "items": self.schema(data.items)? });
+                set(&mut schema, "minItems", data.min_items);
+                set(&mut schema, "maxItems", data.max_items);
+                set(&mut schema, "uniqueItems", data.unique_items);
+                schema
+            }
+            TypeNode::Map { data, .. } => {
+                json!({ "type": "object", "additionalProperties": self.schema(data.value)? })
+            }
+            TypeNode::Union { data, .. } => json!({
+                "anyOf": data.any_of.iter().map(|&v| self.schema(v)).collect::<Result<Vec<_>>>()?,
+            }),
+            TypeNode::Either { data, .. } => json!({
+                "oneOf": data.one_of.iter().map(|&v| self.schema(v)).collect::<Result<Vec<_>>>()?,
+            }),
+            TypeNode::Any { .. } => json!({}),
+            // custom scalar, opaque to the clients
+            TypeNode::Scalar { base, .. } => json!({ "title": base.title }),
+            TypeNode::Function { .. } => {
+                return Err("function types are only allowed as fields".to_string())
+            }
+        };
+
+        if let Some(values) = &node.base().enumeration {
+            let values = values
+                .iter()
+                .map(|v| serde_json::from_str::<Value>(v).map_err(|e| e.to_string()))
+                .collect::<Result<Vec<_>>>()?;
+            schema["enum"] = json!(values);
+        }
+        Ok(schema)
+    }
+}
+
+fn set(schema: &mut Value, key: &str, value: Option<impl serde::Serialize>) {
+    if let Some(value) = value {
+        schema[key] = json!(value);
+    }
+}
+
+/// OpenAPI 3.0 has boolean `exclusiveMinimum` and `exclusiveMaximum`
+fn set_bounds<T: serde::Serialize>(
+    schema: &mut Value,
+    min: Option<T>,
+    max: Option<T>,
+    x_min: Option<T>,
+    x_max: Option<T>,
+) {
+    set(schema, "minimum", min);
+    set(schema, "maximum", max);
+    if let Some(x_min) = x_min {
+        schema["minimum"] = json!(x_min);
+        schema["exclusiveMinimum"] = json!(true);
+    }
+    if let Some(x_max) = x_max {
+        schema["maximum"] = json!(x_max);
+        schema["exclusiveMaximum"] = json!(true);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::core::Core;
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+
+    #[test]
+    fn test_openapi() -> Result<(), String> {
+        Store::reset()?;
+        let user = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx("name", t::string().min(1).max(64))?
+            .named("User")
+            .build()?;
+        let by_id = t::struct_().propx("id", t::integer())?.build()?;
+        let mat =
+            |effect| Lib::register_deno_func(MaterializerDenoFunc::with_code("() => ({})"), effect);
+
+        setup(None)?;
+        Lib::expose(
+            vec![
+                (
+                    "createUser".to_string(),
+                    t::func(user, user, mat(Effect::Create(false))?)?.into(),
+                ),
+                (
+                    "findUser".to_string(),
+                    t::func(by_id, t::optional(user).build()?, mat(Effect::None)?)?.into(),
+                ),
+                (
+                    "updateUser".to_string(),
+                    t::func(user, user, mat(Effect::Update(true))?)?.into(),
+                ),
+                (
+                    "deleteUser".to_string(),
+                    t::func(by_id, user, mat(Effect::Delete(true))?)?.into(),
+                ),
+            ],
+            None,
+        )?;
+        let openapi = Lib::typegraph_to_openapi(Lib::finalize_typegraph()?)?;
+        insta::assert_snapshot!(openapi);
+        Ok(())
+    }
+}
@@ -15,3 +15,232 @@ impl crate::wit::core::Policy {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::runtimes::{DenoMaterializer, MaterializerData};
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::core::Core;
+    use crate::wit::core::{ContextCheck, Policy, PolicySpec, TypePolicy};
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+    use common::typegraph::Typegraph;
+
+    #[test]
+    fn test_context_policy_pattern() -> Result<(), String> {
+        Store::reset()?;
+        Lib::register_context_policy(
+            "user.email".to_string(),
+            ContextCheck::Pattern(r"^[a-z]+@example\.com$".to_string()),
+        )?;
+        assert_eq!(
+            Lib::register_context_policy(
+                "user.role".to_string(),
+                ContextCheck::Pattern("(admin".to_string())
+            ),
+            Err(errors::invalid_pattern("(admin"))
+        );
+
+        crate::host::abi::take_logs();
+        Lib::register_context_policy(
+            "user.name".to_string(),
+            ContextCheck::Pattern("^(?!root$)".to_string()),
+        )?;
+        assert!(crate::host::abi::take_logs()
+            .iter()
+            .any(|l| l.starts_with("warning: cannot validate the pattern")));
+
+        Lib::register_context_policy(
+            "user.id".to_string(),
+            ContextCheck::Pattern(r"^(\d)\1$".to_string()),
+        )?;
+        assert!(crate::host::abi::take_logs()
+            .iter()
+            .any(|l| l.ends_with("backreference is not supported")));
+
+        // escaped or in a class, these are plain characters
+        assert_eq!(crate::js_only_feature(r"\(?=x"), None);
+        assert_eq!(crate::js_only_feature(r"[(?=]\\1"), None);
+        assert_eq!(crate::js_only_feature(r"(?<name>a)"), None);
+        assert_eq!(crate::js_only_feature(r"(?<!a)b"), Some("look-around"));
+        assert_eq!(
+            crate::js_only_feature(r"(?<n>a)\k<n>"),
+            Some("backreference")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_context_policy_present() -> Result<(), String> {
+        Store::reset()?;
+        let (policy_id, name) =
+            Lib::register_context_policy("user.role".to_string(), ContextCheck::Present)?;
+        assert_eq!(name, "__ctx_present_user_role");
+
+        let mat = Store::get_materializer(Store::get_policy(policy_id)?.materializer)?;
+        let MaterializerData::Deno(deno) = mat.data else {
+            panic!("expected a deno materializer");
+        };
+        let DenoMaterializer::Inline(func) = deno.as_ref() else {
+            panic!("expected an inline function");
+        };
+        insta::assert_snapshot!(func.code);
+        Ok(())
+    }
+
+    #[test]
+    fn test_policy_groups() -> Result<(), String> {
+        Store::reset()?;
+        let (admin, _) =
+            Lib::register_context_policy("role".to_string(), ContextCheck::Value("admin".into()))?;
+        let (user, _) = Lib::register_context_policy("user".to_string(), ContextCheck::Present)?;
+        let mat = dummy_mat()?;
+        let func = t::func(t::struct_().build()?, t::integer().build()?, mat)?;
+        let func = Lib::with_policy(TypePolicy {
+            tpe: func.into(),
+            chain: vec![
+                PolicySpec::All(vec![user, admin]),
+                PolicySpec::Any(vec![admin]),
+            ],
+        })?;
+
+        setup(None)?;
+        Lib::expose(vec![("admin_only".to_string(), func)], None)?;
+        let typegraph: Typegraph =
+            serde_json::from_str(&Lib::finalize_typegraph()?).map_err(|e| e.to_string())?;
+        let func_policies =
+            serde_json::to_value(&typegraph.types[1].base().policies).map_err(|e| e.to_string())?;
+        assert_eq!(
+            func_policies,
+            serde_json::json!([{ "all": [0, 1] }, { "any": [1] }])
+        );
+        let names: Vec<_> = typegraph.policies.iter().map(|p| p.name.clone()).collect();
+        assert_eq!(names, vec!["__ctx_present_user", "__ctx_role_admin"]);
+
+        let empty = Lib::with_policy(TypePolicy {
+            tpe: func,
+            chain: vec![PolicySpec::Any(vec![])],
+        })?;
+        setup(None)?;
+        assert_eq!(
+            Lib::expose(vec![("empty".to_string(), empty)], None),
+            Err(errors::empty_policy_group())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_policy_dedup() -> Result<(), String> {
+        Store::reset()?;
+        let mat =
+            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => true"), Effect::None)?;
+        let other =
+            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => false"), Effect::None)?;
+        let policy = |materializer| Policy {
+            name: "allow".to_string(),
+            materializer,
+        };
+
+        let id = Lib::register_policy(policy(mat))?;
+        assert_eq!(Lib::register_policy(policy(mat))?, id);
+        assert_eq!(
+            Lib::register_policy(policy(other)),
+            Err(errors::policy_name_conflict("allow"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_field_policy() -> Result<(), String> {
+        Store::reset()?;
+        let deny = Lib::register_policy(Policy {
+            name: "deny".to_string(),
+            materializer: Lib::register_deno_func(
+                MaterializerDenoFunc::with_code("() => false"),
+                Effect::None,
+            )?,
+        })?;
+        let person = t::struct_()
+            .prop("id", t::integer().build()?)
+            .prop_with_policy("ssn", t::string().build()?, vec![PolicySpec::Simple(deny)])?
+            .named("Person")
+            .build()?;
+        let mat =
+            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => ({})"), Effect::None)?;
+
+        expose_one("person", t::func(t::struct_().build()?, person, mat)?)?;
+        let typegraph = Lib::finalize_typegraph()?;
+        insta::assert_snapshot!(typegraph);
+        Ok(())
+    }
+
+    #[test]
+    fn test_upsert_policy() -> Result<(), String> {
+        use crate::wit::core::PolicyPerEffect;
+        use common::typegraph::PolicyIndicesByEffect;
+
+        Store::reset()?;
+        let policy = |name: &str, code: &str| -> Result<_, String> {
+            Lib::register_policy(Policy {
+                name: name.to_string(),
+                materializer: Lib::register_deno_func(
+                    MaterializerDenoFunc::with_code(code),
+                    Effect::None,
+                )?,
+            })
+        };
+        let read = policy("read", "() => true")?;
+        let upsert = policy("upsert", "(_, { context }) => !!context.admin")?;
+        let mat = Lib::register_deno_func(
+            MaterializerDenoFunc::with_code("({ id }) => ({ id })"),
+            Effect::Upsert(true),
+        )?;
+
+        setup(None)?;
+        let user = t::struct_().propx("id", t::integer())?.build()?;
+        Lib::expose(
+            vec![("saveUser".to_string(), t::func(user, user, mat)?.into())],
+            Some(vec![PolicySpec::PerEffect(PolicyPerEffect {
+                none: Some(read),
+                create: None,
+                update: None,
+                delete: None,
+                upsert: Some(upsert),
+            })]),
+        )?;
+        let tg: serde_json::Value = serde_json::from_str(&Lib::finalize_typegraph()?).unwrap();
+        assert_eq!(
+            tg["types"][1]["policies"],
+            serde_json::json!([{ "none": 0, "upsert": 1 }])
+        );
+        assert_eq!(tg["materializers"][0]["effect"]["effect"], "upsert");
+
+        // `read` is accepted for `none`
+        let indices: PolicyIndicesByEffect =
+            serde_json::from_value(serde_json::json!({ "read": 0, "upsert": 1 })).unwrap();
+        assert_eq!((indices.none, indices.upsert), (Some(0), Some(1)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_describe_policy() -> Result<(), String> {
+        Store::reset()?;
+        let (policy, name) = Lib::register_context_policy(
+            "role".to_string(),
+            ContextCheck::Value("admin".to_string()),
+        )?;
+        let desc = Lib::describe_policy(policy)?;
+        assert_eq!(desc.name, name);
+        assert_eq!(desc.runtime, "deno");
+        assert_eq!(desc.effect, "none");
+
+        assert_eq!(
+            Lib::describe_policy(42).map(|d| d.name),
+            Err(errors::object_not_found("policy", 42))
+        );
+        Ok(())
+    }
+}
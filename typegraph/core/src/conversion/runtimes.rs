@@ -3,20 +3,23 @@
 
 use std::collections::HashMap;
 
-use std::rc::Rc;
+use crate::sync::Rc;
 
-use crate::errors::Result;
+use crate::errors::{self, Result};
+use crate::global_store::Store;
 use crate::runtimes::prisma::{with_prisma_runtime, ConversionContext};
 use crate::runtimes::{
-    DenoMaterializer, Materializer as RawMaterializer, PythonMaterializer, RandomMaterializer,
-    Runtime, TemporalMaterializer, WasiMaterializer,
+    DenoMaterializer, GrpcMaterializer, KafkaMaterializer, Materializer as RawMaterializer,
+    PythonMaterializer, RandomMaterializer, Runtime, TemporalMaterializer, WasiMaterializer,
 };
 use crate::wit::core::RuntimeId;
 use crate::wit::runtimes::{HttpMethod, MaterializerHttpRequest};
 use crate::{typegraph::TypegraphContext, wit::runtimes::Effect as WitEffect};
 use common::typegraph::runtimes::deno::DenoRuntimeData;
 use common::typegraph::runtimes::graphql::GraphQLRuntimeData;
+use common::typegraph::runtimes::grpc::GrpcRuntimeData;
 use common::typegraph::runtimes::http::HTTPRuntimeData;
+use common::typegraph::runtimes::kafka::KafkaRuntimeData;
 use common::typegraph::runtimes::prisma::PrismaRuntimeData;
 use common::typegraph::runtimes::python::PythonRuntimeData;
 use common::typegraph::runtimes::random::RandomRuntimeData;
@@ -37,6 +40,7 @@ fn effect(typ: EffectType, idempotent: bool) -> Effect {
     Effect {
         effect: Some(typ),
         idempotent,
+        idempotency_key: None,
     }
 }
 
@@ -47,10 +51,21 @@ impl From<WitEffect> for Effect {
             WitEffect::Create(idemp) => effect(EffectType::Create, idemp),
             WitEffect::Update(idemp) => effect(EffectType::Update, idemp),
             WitEffect::Delete(idemp) => effect(EffectType::Delete, idemp),
+            WitEffect::Upsert(idemp) => effect(EffectType::Upsert, idemp),
         }
     }
 }
 
+pub fn effect_name(eff: &WitEffect) -> &'static str {
+    match eff {
+        WitEffect::None => "none",
+        WitEffect::Create(_) => "create",
+        WitEffect::Update(_) => "update",
+        WitEffect::Delete(_) => "delete",
+        WitEffect::Upsert(_) => "upsert",
+    }
+}
+
 #[enum_dispatch(MaterializerData)]
 pub trait MaterializerConverter {
     fn convert(
@@ -99,6 +114,9 @@ impl MaterializerConverter for DenoMaterializer {
                     "secrets".to_string(),
                     serde_json::to_value(&inline_fun.secrets).unwrap(),
                 );
+                if let Some(timeout_sec) = inline_fun.timeout_sec {
+                    data.insert("timeout_sec".to_string(), timeout_sec.into());
+                }
                 ("function".to_string(), data)
             }
             Module(module) => {
@@ -281,6 +299,58 @@ impl MaterializerConverter for RandomMaterializer {
     }
 }
 
+impl MaterializerConverter for GrpcMaterializer {
+    fn convert(
+        &self,
+        c: &mut TypegraphContext,
+        runtime_id: RuntimeId,
+        effect: WitEffect,
+    ) -> Result<Materializer> {
+        let runtime = c.register_runtime(runtime_id)?;
+        let GrpcMaterializer::Call(mat) = self;
+
+        let data = serde_json::from_value(json!({
+            "service": mat.service,
+            "method": mat.method,
+        }))
+        .map_err(|e| e.to_string())?;
+
+        let name = "grpc".to_string();
+        Ok(Materializer {
+            name,
+            runtime,
+            effect: effect.into(),
+            data,
+        })
+    }
+}
+
+impl MaterializerConverter for KafkaMaterializer {
+    fn convert(
+        &self,
+        c: &mut TypegraphContext,
+        runtime_id: RuntimeId,
+        effect: WitEffect,
+    ) -> Result<Materializer> {
+        let runtime = c.register_runtime(runtime_id)?;
+        let KafkaMaterializer::Publish(mat) = self;
+
+        let data = serde_json::from_value(json!({
+            "topic": mat.topic,
+            "key_path": mat.key_path,
+        }))
+        .map_err(|e| e.to_string())?;
+
+        let name = "publish".to_string();
+        Ok(Materializer {
+            name,
+            runtime,
+            effect: effect.into(),
+            data,
+        })
+    }
+}
+
 impl MaterializerConverter for WasiMaterializer {
     fn convert(
         &self,
@@ -290,6 +360,8 @@ impl MaterializerConverter for WasiMaterializer {
     ) -> Result<Materializer> {
         let runtime = c.register_runtime(runtime_id)?;
         let WasiMaterializer::Module(mat) = self;
+        // the SDKs give the module as a `file:` url
+        c.add_dep(mat.module.strip_prefix("file:").unwrap_or(&mat.module));
 
         let data = serde_json::from_value(json!({
             "wasm": mat.module,
@@ -357,7 +429,16 @@ pub fn convert_materializer(
     c: &mut TypegraphContext,
     mat: RawMaterializer,
 ) -> Result<Materializer> {
-    mat.data.convert(c, mat.runtime_id, mat.effect)
+    let runtime = Store::get_runtime(mat.runtime_id)?;
+    if !runtime.supports_effect(&mat.effect) {
+        return Err(errors::effect_not_supported(
+            runtime.name(),
+            effect_name(&mat.effect),
+        ));
+    }
+    let mut converted = mat.data.convert(c, mat.runtime_id, mat.effect)?;
+    converted.effect.idempotency_key = mat.idempotency_key;
+    Ok(converted)
 }
 
 type RuntimeInitializer =
@@ -374,7 +455,7 @@ impl From<TGRuntime> for ConvertedRuntime {
     }
 }
 
-pub fn convert_runtime(_c: &mut TypegraphContext, runtime: Runtime) -> Result<ConvertedRuntime> {
+pub fn convert_runtime(c: &mut TypegraphContext, runtime: Runtime) -> Result<ConvertedRuntime> {
     use KnownRuntime as Rt;
 
     match runtime {
@@ -391,6 +472,14 @@ pub fn convert_runtime(_c: &mut TypegraphContext, runtime: Runtime) -> Result<Co
             };
             Ok(TGRuntime::Known(Rt::GraphQL(data)).into())
         }
+        Runtime::Grpc(d) => {
+            c.add_dep(&d.proto_file);
+            let data = GrpcRuntimeData {
+                proto_file: d.proto_file.clone(),
+                endpoint: d.endpoint.clone(),
+            };
+            Ok(TGRuntime::Known(Rt::Grpc(data)).into())
+        }
         Runtime::Http(d) => {
             let data = HTTPRuntimeData {
                 endpoint: d.endpoint.clone(),
@@ -399,6 +488,13 @@ pub fn convert_runtime(_c: &mut TypegraphContext, runtime: Runtime) -> Result<Co
             };
             Ok(TGRuntime::Known(Rt::HTTP(data)).into())
         }
+        Runtime::Kafka(d) => {
+            c.add_secret(&d.broker_secret);
+            let data = KafkaRuntimeData {
+                broker_secret: d.broker_secret.clone(),
+            };
+            Ok(TGRuntime::Known(Rt::Kafka(data)).into())
+        }
         Runtime::Python => {
             Ok(TGRuntime::Known(Rt::PythonWasi(PythonRuntimeData { config: None })).into())
         }
@@ -446,20 +542,30 @@ pub fn convert_runtime(_c: &mut TypegraphContext, runtime: Runtime) -> Result<Co
         Runtime::PrismaMigration => {
             Ok(TGRuntime::Known(Rt::PrismaMigration(PrismaMigrationRuntimeData {})).into())
         }
-        Runtime::Temporal(d) => Ok(TGRuntime::Known(Rt::Temporal(TemporalRuntimeData {
-            name: d.name.clone(),
-            host: d.host.clone(),
-        }))
-        .into()),
+        Runtime::Temporal(d) => {
+            c.add_secret(&d.host_secret);
+            Ok(TGRuntime::Known(Rt::Temporal(TemporalRuntimeData {
+                name: d.name.clone(),
+                host_secret: d.host_secret.clone(),
+            }))
+            .into())
+        }
         Runtime::Typegate => Ok(TGRuntime::Known(Rt::Typegate(TypegateRuntimeData {})).into()),
         Runtime::Typegraph => Ok(TGRuntime::Known(Rt::Typegraph(TypegraphRuntimeData {})).into()),
-        Runtime::S3(d) => Ok(TGRuntime::Known(Rt::S3(S3RuntimeData {
-            host_secret: d.host_secret.clone(),
-            region_secret: d.region_secret.clone(),
-            access_key_secret: d.access_key_secret.clone(),
-            secret_key_secret: d.secret_key_secret.clone(),
-            path_style_secret: d.path_style_secret.clone(),
-        }))
-        .into()),
+        Runtime::S3(d) => {
+            // the path style is optional
+            c.add_secret(&d.host_secret);
+            c.add_secret(&d.region_secret);
+            c.add_secret(&d.access_key_secret);
+            c.add_secret(&d.secret_key_secret);
+            Ok(TGRuntime::Known(Rt::S3(S3RuntimeData {
+                host_secret: d.host_secret.clone(),
+                region_secret: d.region_secret.clone(),
+                access_key_secret: d.access_key_secret.clone(),
+                secret_key_secret: d.secret_key_secret.clone(),
+                path_style_secret: d.path_style_secret.clone(),
+            }))
+            .into())
+        }
     }
 }
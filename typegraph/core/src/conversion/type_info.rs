@@ -0,0 +1,167 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use serde::Serialize;
+
+use crate::errors::Result;
+use crate::types::{Type, TypeFun, TypeId};
+use crate::wit::core::{OptionalKind, TypeInfo};
+
+/// Structured metadata of a type, for the SDKs; proxies, policies and
+/// injections are resolved to the concrete type.
+pub fn type_info(type_id: TypeId) -> Result<TypeInfo> {
+    let attrs = type_id.attrs()?;
+    let typ = attrs.concrete_type.as_type()?;
+
+    let mut constraints = Constraints::default();
+    let children: Vec<u32> = match &typ {
+        Type::Boolean(_) | Type::Json(_) | Type::Scalar(_) => vec![],
+        Type::Integer(t) => {
+            constraints.set("min", t.data.min)?;
+            constraints.set("max", t.data.max)?;
+            constraints.set("exclusiveMinimum", t.data.exclusive_minimum)?;
+            constraints.set("exclusiveMaximum", t.data.exclusive_maximum)?;
+            constraints.set("multipleOf", t.data.multiple_of)?;
+            constraints.set("enum", t.data.enumeration.as_ref())?;
+            vec![]
+        }
+        Type::Float(t) => {
+            constraints.set("min", t.data.min)?;
+            constraints.set("max", t.data.max)?;
+            constraints.set("exclusiveMinimum", t.data.exclusive_minimum)?;
+            constraints.set("exclusiveMaximum", t.data.exclusive_maximum)?;
+            constraints.set("multipleOf", t.data.multiple_of)?;
+            constraints.set("enum", t.data.enumeration.as_ref())?;
+            vec![]
+        }
+        Type::String(t) => {
+            constraints.set("min", t.data.min)?;
+            constraints.set("max", t.data.max)?;
+            constraints.set("format", t.data.format.as_ref())?;
+            constraints.set("pattern", t.data.pattern.as_ref())?;
+            constraints.set("maxSize", t.data.max_size)?;
+            constraints.set_json("enum", &t.data.enumeration);
+            vec![]
+        }
+        Type::File(t) => {
+            constraints.set("min", t.data.min)?;
+            constraints.set("max", t.data.max)?;
+            constraints.set("allow", t.data.allow.as_ref())?;
+            vec![]
+        }
+        Type::Optional(t) => {
+            if let Some(default) = &t.data.default_item {
+                constraints.0.push(("default".to_string(), default.clone()));
+            }
+            constraints.set(
+                "kind",
+                t.data.kind.map(|kind| match kind {
+                    OptionalKind::Absent => "absent",
+                    OptionalKind::Nullable => "nullable",
+                }),
+            )?;
+            vec![t.data.of]
+        }
+        Type::Array(t) => {
+            constraints.set("min", t.data.min)?;
+            constraints.set("max", t.data.max)?;
+            constraints.set("uniqueItems", t.data.unique_items)?;
+            vec![t.data.of]
+        }
+        Type::Map(t) => vec![t.data.key, t.data.value],
+        Type::Union(t) => {
+            constraints.set("discriminator", t.data.discriminator.as_ref())?;
+            t.data.variants.clone()
+        }
+        Type::Either(t) => t.data.variants.clone(),
+        Type::Struct(t) => {
+            constraints.set("min", t.data.min)?;
+            constraints.set("max", t.data.max)?;
+            constraints.set("maxProps", t.data.max_props)?;
+            constraints.set("maxDepth", t.data.max_depth)?;
+            constraints.set_json("enum", &t.data.enumeration);
+            t.data.props.iter().map(|(_, id)| *id).collect()
+        }
+        Type::Func(t) => vec![t.data.inp, t.data.out],
+        Type::Proxy(_) | Type::WithPolicy(_) | Type::WithInjection(_) => unreachable!(),
+    };
+
+    let kind = typ.get_data().variant_name();
+    Ok(TypeInfo {
+        optional: kind == "optional",
+        array: kind == "array",
+        kind,
+        name: attrs.concrete_type.type_name()?,
+        children,
+        constraints: constraints.0,
+    })
+}
+
+/// Active constraints, as json strings
+#[derive(Default)]
+struct Constraints(Vec<(String, String)>);
+
+impl Constraints {
+    fn set(&mut self, key: &str, value: Option<impl Serialize>) -> Result<()> {
+        if let Some(value) = value {
+            let value = serde_json::to_string(&value).map_err(|e| e.to_string())?;
+            self.0.push((key.to_string(), value));
+        }
+        Ok(())
+    }
+
+    /// enumerations of strings and structs are already stored as json strings
+    fn set_json(&mut self, key: &str, values: &Option<Vec<String>>) {
+        if let Some(values) = values {
+            self.0
+                .push((key.to_string(), format!("[{}]", values.join(","))));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::wit::core::Core;
+    use crate::Lib;
+
+    #[test]
+    fn test_get_type_info() -> Result<(), String> {
+        Store::reset()?;
+        let id = t::integer().min(1).build()?;
+        let name = t::string().build()?;
+        let user = t::struct_()
+            .prop("id", id)
+            .prop("name", name)
+            .named("User")
+            .build()?;
+        let users = t::array(user).min(1).max(10).build()?;
+
+        let info = Lib::get_type_info(users.into())?;
+        assert_eq!(info.kind, "array");
+        assert_eq!(info.name, users.type_name()?);
+        assert!(info.array);
+        assert!(!info.optional);
+        assert_eq!(info.children, vec![user.0]);
+        assert_eq!(
+            info.constraints,
+            vec![
+                ("min".to_string(), "1".to_string()),
+                ("max".to_string(), "10".to_string())
+            ]
+        );
+
+        let info = Lib::get_type_info(user.into())?;
+        assert_eq!(info.kind, "struct");
+        assert_eq!(info.name.as_deref(), Some("User"));
+        assert!(!info.array);
+        assert_eq!(info.children, vec![id.0, name.0]);
+        assert!(info.constraints.is_empty());
+
+        let info = Lib::get_type_info(t::optional(id).build()?.into())?;
+        assert!(info.optional);
+        assert_eq!(info.children, vec![id.0]);
+        Ok(())
+    }
+}
@@ -1,13 +1,16 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
+use crate::sync::Rc;
 use common::typegraph::{PolicyIndices, TypeNode, TypeNodeBase};
 use enum_dispatch::enum_dispatch;
 use indexmap::IndexMap;
-use std::rc::Rc;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 
-use crate::errors::Result;
+use crate::errors::{self, Result};
 use crate::typegraph::TypegraphContext;
+use crate::types::{Type, TypeId};
 
 #[enum_dispatch]
 pub trait TypeConversion {
@@ -21,16 +24,107 @@ impl<T: TypeConversion> TypeConversion for Rc<T> {
     }
 }
 
+/// The runtime inherited from the parent; only the root and the namespaces
+/// are converted without one
+pub fn expect_runtime(runtime_id: Option<u32>, type_id: TypeId) -> Result<u32> {
+    match runtime_id {
+        Some(id) => Ok(id),
+        None => Err(errors::missing_runtime_for_type(&type_id.repr()?)),
+    }
+}
+
 #[derive(Default)]
 pub struct TypeNodeBaseBuilder {
     name: String,
     runtime: u32,
     enumeration: Option<Vec<String>>,
+    examples: Option<Vec<String>>,
+    deprecated: Option<String>,
     policies: Vec<PolicyIndices>,
     runtime_config: Option<Vec<(String, String)>>,
     as_id: bool,
 }
 
+/// Fails when a union reachable from the output type of a function has
+/// variants that GraphQL cannot merge; input unions are never merged
+pub fn check_output_unions(output: TypeId) -> Result<()> {
+    let mut visited = HashSet::new();
+    let mut queue = vec![output];
+    while let Some(type_id) = queue.pop() {
+        let type_id = type_id.attrs()?.concrete_type;
+        if !visited.insert(type_id) {
+            continue;
+        }
+        match type_id.as_type()? {
+            Type::Struct(inner) => queue.extend(inner.iter_props().map(|(_, prop)| prop)),
+            Type::Optional(inner) => queue.push(inner.data.of.into()),
+            Type::Array(inner) => queue.push(inner.data.of.into()),
+            Type::Union(inner) => {
+                let variants = inner.flat_variants()?;
+                check_variant_fields(&variants)?;
+                queue.extend(variants);
+            }
+            Type::Either(inner) => {
+                let variants = inner.flat_variants()?;
+                check_variant_fields(&variants)?;
+                queue.extend(variants);
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Fails when a field of the struct variants has scalar types of different
+/// kinds or nullability across the variants, as GraphQL cannot merge them
+fn check_variant_fields(variants: &[TypeId]) -> Result<()> {
+    let mut fields: HashMap<String, (TypeId, ScalarKind)> = HashMap::new();
+    for variant in variants {
+        let Type::Struct(variant) = variant.attrs()?.concrete_type.as_type()? else {
+            continue;
+        };
+        for (name, prop) in variant.iter_props() {
+            let Some(kind) = scalar_kind(prop)? else {
+                continue;
+            };
+            match fields.entry(name.to_string()) {
+                Entry::Vacant(e) => {
+                    e.insert((prop, kind));
+                }
+                Entry::Occupied(e) => {
+                    let (other, other_kind) = *e.get();
+                    if other_kind != kind {
+                        return Err(errors::incompatible_union_field(
+                            name,
+                            &other.repr()?,
+                            &prop.repr()?,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Scalar type name and nullability
+type ScalarKind = (&'static str, bool);
+
+fn scalar_kind(type_id: TypeId) -> Result<Option<ScalarKind>> {
+    let kind = match type_id.attrs()?.concrete_type.as_type()? {
+        Type::Optional(inner) => {
+            return Ok(scalar_kind(inner.data.of.into())?.map(|(kind, _)| (kind, true)))
+        }
+        Type::Boolean(_) => "boolean",
+        Type::Integer(_) => "integer",
+        Type::Float(_) => "float",
+        Type::String(_) => "string",
+        Type::File(_) => "file",
+        _ => return Ok(None),
+    };
+    Ok(Some((kind, false)))
+}
+
 /// takes converted runtime id
 pub fn gen_base(
     name: String,
@@ -57,6 +151,8 @@ impl TypeNodeBaseBuilder {
             config: config.unwrap_or(Default::default()),
             description: None,
             enumeration: self.enumeration,
+            examples: self.examples,
+            deprecated: self.deprecated,
             injection: None,
             policies: self.policies,
             runtime: self.runtime,
@@ -70,8 +166,102 @@ impl TypeNodeBaseBuilder {
         self
     }
 
+    pub fn examples(mut self, examples: Option<Vec<String>>) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    pub fn deprecated(mut self, reason: Option<String>) -> Self {
+        self.deprecated = reason;
+        self
+    }
+
     pub fn id(mut self, b: bool) -> Self {
         self.as_id = b;
         self
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+
+    #[test]
+    fn test_missing_runtime() -> Result<(), String> {
+        Store::reset()?;
+        let tpe = t::integer().named("Orphan").build()?;
+        setup(None)?;
+        let res = crate::typegraph::with_tg_mut(|ctx| ctx.register_type(tpe, None))?;
+        assert_eq!(res, Err(errors::missing_runtime_for_type(&tpe.repr()?)));
+        Store::reset()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_incompatible_union_field() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let int_id = t::integer().build()?;
+        let string_id = t::string().build()?;
+        let by_int = t::struct_().prop("id", int_id).build()?;
+        let by_string = t::struct_().prop("id", string_id).build()?;
+
+        let res = expose_one(
+            "find",
+            t::func(
+                t::struct_().build()?,
+                t::union([by_int, by_string]).build()?,
+                mat,
+            )?,
+        );
+        assert_eq!(
+            res,
+            Err(errors::incompatible_union_field(
+                "id",
+                &int_id.repr()?,
+                &string_id.repr()?
+            ))
+        );
+
+        // GraphQL does not merge fields of different nullability
+        let mat = reset_with_mat()?;
+        let int_id = t::integer().build()?;
+        let optional_int_id = t::optional(t::integer().build()?).build()?;
+        let by_int = t::struct_().prop("id", int_id).build()?;
+        let by_optional_int = t::struct_().prop("id", optional_int_id).build()?;
+        let res = expose_one(
+            "find",
+            t::func(
+                t::struct_().build()?,
+                t::either([by_int, by_optional_int]).build()?,
+                mat,
+            )?,
+        );
+        assert_eq!(
+            res,
+            Err(errors::incompatible_union_field(
+                "id",
+                &int_id.repr()?,
+                &optional_int_id.repr()?
+            ))
+        );
+
+        // input unions are never merged
+        let mat = reset_with_mat()?;
+        let by_int = t::struct_().propx("id", t::integer())?.build()?;
+        let by_string = t::struct_().propx("id", t::string())?.build()?;
+        expose_one(
+            "find",
+            t::func(
+                t::struct_()
+                    .propx("by", t::union([by_int, by_string]))?
+                    .build()?,
+                t::boolean().build()?,
+                mat,
+            )?,
+        )?;
+        Ok(())
+    }
+}
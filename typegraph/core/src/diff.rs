@@ -0,0 +1,507 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use std::collections::HashSet;
+
+use common::typegraph::{TypeNode, Typegraph};
+
+use crate::errors::Result;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    ExportAdded,
+    ExportRemoved,
+    FieldAdded,
+    FieldRemoved,
+    TypeChanged {
+        old: String,
+        new: String,
+    },
+    /// name of the constraint, e.g. `minimum`
+    ConstraintTightened(String),
+    /// name of the constraint, e.g. `minimum`
+    ConstraintLoosened(String),
+    /// json-serialized value
+    EnumValueRemoved(String),
+    /// json-serialized value
+    EnumValueAdded(String),
+    /// title of the variant
+    VariantRemoved(String),
+    /// title of the variant
+    VariantAdded(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// e.g. `createUser.input.name`; the namespaces are included
+    pub path: String,
+    pub kind: ChangeKind,
+    /// whether the existing clients might break
+    pub breaking: bool,
+}
+
+/// Changes of the exports from `old` to `new`.
+/// The changes that narrow the accepted inputs or widen the outputs
+/// are breaking.
+pub fn diff(old: &Typegraph, new: &Typegraph) -> Result<Vec<Change>> {
+    let mut diff = Diff {
+        old,
+        new,
+        changes: vec![],
+        visited: HashSet::new(),
+    };
+    diff.exports(0, 0, &mut vec![])?;
+    Ok(diff.changes)
+}
+
+struct Diff<'a> {
+    old: &'a Typegraph,
+    new: &'a Typegraph,
+    changes: Vec<Change>,
+    /// pairs of (old, new) type indices already compared, with the side
+    visited: HashSet<(u32, u32, bool)>,
+}
+
+fn node(tg: &Typegraph, idx: u32) -> Result<&TypeNode> {
+    tg.types
+        .get(idx as usize)
+        .ok_or_else(|| format!("type index {idx} out of bounds"))
+}
+
+/// Type name and property names, for matching the variants of unions
+fn shape(tg: &Typegraph, idx: u32) -> Result<String> {
+    Ok(match node(tg, idx)? {
+        TypeNode::Object { data, .. } => {
+            let mut keys: Vec<_> = data.properties.keys().map(|k| k.as_str()).collect();
+            keys.sort();
+            format!("object{{{}}}", keys.join(","))
+        }
+        TypeNode::Optional { data, .. } => format!("optional<{}>", shape(tg, data.item)?),
+        TypeNode::Array { data, .. } => format!("array<{}>", shape(tg, data.items)?),
+        other => other.type_name().to_string(),
+    })
+}
+
+/// Constraint changes of a type
+#[derive(Default)]
+struct Constraints {
+    /// the new constraint rejects values that the old one accepted
+    tightened: Vec<&'static str>,
+    /// the new constraint accepts values that the old one rejected
+    loosened: Vec<&'static str>,
+}
+
+impl Constraints {
+    fn min<T: PartialOrd + Copy>(&mut self, name: &'static str, old: Option<T>, new: Option<T>) {
+        if tighter_min(old, new) {
+            self.tightened.push(name);
+        }
+        if tighter_min(new, old) {
+            self.loosened.push(name);
+        }
+    }
+
+    fn max<T: PartialOrd + Copy>(&mut self, name: &'static str, old: Option<T>, new: Option<T>) {
+        if tighter_max(old, new) {
+            self.tightened.push(name);
+        }
+        if tighter_max(new, old) {
+            self.loosened.push(name);
+        }
+    }
+
+    /// For the constraints that cannot be ordered, e.g. patterns: a changed
+    /// value is both tighter and looser
+    fn exact<T: PartialEq>(&mut self, name: &'static str, old: Option<T>, new: Option<T>) {
+        if old == new {
+            return;
+        }
+        if new.is_some() {
+            self.tightened.push(name);
+        }
+        if old.is_some() {
+            self.loosened.push(name);
+        }
+    }
+}
+
+/// Whether the new lower bound rejects values the old one accepted
+fn tighter_min<T: PartialOrd>(old: Option<T>, new: Option<T>) -> bool {
+    match (old, new) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(old), Some(new)) => new > old,
+    }
+}
+
+/// Whether the new upper bound rejects values the old one accepted
+fn tighter_max<T: PartialOrd>(old: Option<T>, new: Option<T>) -> bool {
+    match (old, new) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(old), Some(new)) => new < old,
+    }
+}
+
+impl<'a> Diff<'a> {
+    fn push(&mut self, path: &[String], kind: ChangeKind, breaking: bool) {
+        self.changes.push(Change {
+            path: path.join("."),
+            kind,
+            breaking,
+        });
+    }
+
+    /// Compares the root or namespace objects
+    fn exports(&mut self, old_idx: u32, new_idx: u32, path: &mut Vec<String>) -> Result<()> {
+        let (TypeNode::Object { data: old, .. }, TypeNode::Object { data: new, .. }) =
+            (node(self.old, old_idx)?, node(self.new, new_idx)?)
+        else {
+            return Err("expected an object as root or namespace".to_string());
+        };
+
+        for (name, &old_child) in old.properties.iter() {
+            path.push(name.clone());
+            match new.properties.get(name) {
+                None => self.push(path, ChangeKind::ExportRemoved, true),
+                Some(&new_child) => {
+                    match (node(self.old, old_child)?, node(self.new, new_child)?) {
+                        (TypeNode::Object { .. }, TypeNode::Object { .. }) => {
+                            self.exports(old_child, new_child, path)?
+                        }
+                        _ => self.types(old_child, new_child, path, false)?,
+                    }
+                }
+            }
+            path.pop();
+        }
+        for name in new.properties.keys() {
+            if !old.properties.contains_key(name) {
+                path.push(name.clone());
+                self.push(path, ChangeKind::ExportAdded, false);
+                path.pop();
+            }
+        }
+        Ok(())
+    }
+
+    /// `input` is set for the types given by the clients
+    fn types(
+        &mut self,
+        old_idx: u32,
+        new_idx: u32,
+        path: &mut Vec<String>,
+        input: bool,
+    ) -> Result<()> {
+        if !self.visited.insert((old_idx, new_idx, input)) {
+            return Ok(());
+        }
+        let old = node(self.old, old_idx)?;
+        let new = node(self.new, new_idx)?;
+
+        if let (Some(old_values), Some(new_values)) =
+            (&old.base().enumeration, &new.base().enumeration)
+        {
+            for value in old_values.iter().filter(|v| !new_values.contains(v)) {
+                self.push(path, ChangeKind::EnumValueRemoved(value.clone()), input);
+            }
+            for value in new_values.iter().filter(|v| !old_values.contains(v)) {
+                self.push(path, ChangeKind::EnumValueAdded(value.clone()), !input);
+            }
+        }
+
+        let mut constraints = Constraints::default();
+        match (old, new) {
+            (TypeNode::Optional { data: old, .. }, TypeNode::Optional { data: new, .. }) => {
+                self.types(old.item, new.item, path, input)?;
+            }
+            (TypeNode::Optional { data: old, .. }, _) => {
+                self.push(path, self.type_changed(old_idx, new_idx)?, input);
+                self.types(old.item, new_idx, path, input)?;
+            }
+            (_, TypeNode::Optional { data: new, .. }) => {
+                self.push(path, self.type_changed(old_idx, new_idx)?, !input);
+                self.types(old_idx, new.item, path, input)?;
+            }
+            (TypeNode::Integer { data: old, .. }, TypeNode::Integer { data: new, .. }) => {
+                constraints.min("minimum", old.minimum, new.minimum);
+                constraints.max("maximum", old.maximum, new.maximum);
+                constraints.min(
+                    "exclusiveMinimum",
+                    old.exclusive_minimum,
+                    new.exclusive_minimum,
+                );
+                constraints.max(
+                    "exclusiveMaximum",
+                    old.exclusive_maximum,
+                    new.exclusive_maximum,
+                );
+                constraints.exact("multipleOf", old.multiple_of, new.multiple_of);
+            }
+            (TypeNode::Float { data: old, .. }, TypeNode::Float { data: new, .. }) => {
+                constraints.min("minimum", old.minimum, new.minimum);
+                constraints.max("maximum", old.maximum, new.maximum);
+                constraints.min(
+                    "exclusiveMinimum",
+                    old.exclusive_minimum,
+                    new.exclusive_minimum,
+                );
+                constraints.max(
+                    "exclusiveMaximum",
+                    old.exclusive_maximum,
+                    new.exclusive_maximum,
+                );
+                constraints.exact("multipleOf", old.multiple_of, new.multiple_of);
+            }
+            (TypeNode::String { data: old, .. }, TypeNode::String { data: new, .. }) => {
+                constraints.min("minLength", old.min_length, new.min_length);
+                constraints.max("maxLength", old.max_length, new.max_length);
+                constraints.max("maxSize", old.max_size, new.max_size);
+                constraints.exact("pattern", old.pattern.as_ref(), new.pattern.as_ref());
+                let format = |f| serde_json::to_value(f).ok();
+                constraints.exact(
+                    "format",
+                    old.format.as_ref().and_then(format),
+                    new.format.as_ref().and_then(format),
+                );
+            }
+            (TypeNode::Array { data: old, .. }, TypeNode::Array { data: new, .. }) => {
+                constraints.min("minItems", old.min_items, new.min_items);
+                constraints.max("maxItems", old.max_items, new.max_items);
+                path.push("[]".to_string());
+                self.types(old.items, new.items, path, input)?;
+                path.pop();
+            }
+            (TypeNode::Map { data: old, .. }, TypeNode::Map { data: new, .. }) => {
+                path.push("{}".to_string());
+                self.types(old.value, new.value, path, input)?;
+                path.pop();
+            }
+            (TypeNode::Object { data: old, .. }, TypeNode::Object { data: new, .. }) => {
+                for (name, &old_prop) in old.properties.iter() {
+                    path.push(name.clone());
+                    match new.properties.get(name) {
+                        None => self.push(path, ChangeKind::FieldRemoved, true),
+                        Some(&new_prop) => self.types(old_prop, new_prop, path, input)?,
+                    }
+                    path.pop();
+                }
+                for name in new.properties.keys() {
+                    if !old.properties.contains_key(name) {
+                        path.push(name.clone());
+                        let required = new.required.contains(name);
+                        self.push(path, ChangeKind::FieldAdded, input && required);
+                        path.pop();
+                    }
+                }
+            }
+            (TypeNode::Function { data: old, .. }, TypeNode::Function { data: new, .. }) => {
+                path.push("input".to_string());
+                self.types(old.input, new.input, path, true)?;
+                path.pop();
+                path.push("output".to_string());
+                self.types(old.output, new.output, path, false)?;
+                path.pop();
+            }
+            (TypeNode::Union { data: old, .. }, TypeNode::Union { data: new, .. }) => {
+                self.variants(&old.any_of, &new.any_of, path, input)?;
+            }
+            (TypeNode::Either { data: old, .. }, TypeNode::Either { data: new, .. }) => {
+                self.variants(&old.one_of, &new.one_of, path, input)?;
+            }
+            (old, new) if old.type_name() == new.type_name() => {}
+            _ => self.push(path, self.type_changed(old_idx, new_idx)?, true),
+        }
+
+        for constraint in constraints.tightened {
+            self.push(
+                path,
+                ChangeKind::ConstraintTightened(constraint.to_string()),
+                input,
+            );
+        }
+        for constraint in constraints.loosened {
+            self.push(
+                path,
+                ChangeKind::ConstraintLoosened(constraint.to_string()),
+                !input,
+            );
+        }
+        Ok(())
+    }
+
+    /// The variants are matched by structure, then by title; the unmatched
+    /// ones are reported as added or removed
+    fn variants(
+        &mut self,
+        old: &[u32],
+        new: &[u32],
+        path: &mut Vec<String>,
+        input: bool,
+    ) -> Result<()> {
+        let mut unmatched: Vec<u32> = new.to_vec();
+        let mut pairs = vec![];
+        let mut removed = vec![];
+        for &old_variant in old {
+            let old_shape = shape(self.old, old_variant)?;
+            let mut position = None;
+            for (pos, &v) in unmatched.iter().enumerate() {
+                if shape(self.new, v)? == old_shape {
+                    position = Some(pos);
+                    break;
+                }
+            }
+            if position.is_none() {
+                let title = &node(self.old, old_variant)?.base().title;
+                position = unmatched
+                    .iter()
+                    .position(|&v| node(self.new, v).is_ok_and(|n| &n.base().title == title));
+            }
+            match position {
+                Some(pos) => pairs.push((old_variant, unmatched.remove(pos))),
+                None => removed.push(old_variant),
+            }
+        }
+
+        for old_variant in removed {
+            let title = node(self.old, old_variant)?.base().title.clone();
+            self.push(path, ChangeKind::VariantRemoved(title), input);
+        }
+        for new_variant in unmatched {
+            let title = node(self.new, new_variant)?.base().title.clone();
+            self.push(path, ChangeKind::VariantAdded(title), !input);
+        }
+        for (old_variant, new_variant) in pairs {
+            self.types(old_variant, new_variant, path, input)?;
+        }
+        Ok(())
+    }
+
+    fn type_changed(&self, old_idx: u32, new_idx: u32) -> Result<ChangeKind> {
+        Ok(ChangeKind::TypeChanged {
+            old: node(self.old, old_idx)?.type_name().to_string(),
+            new: node(self.new, new_idx)?.type_name().to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::core::Core;
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+
+    #[test]
+    fn test_diff_typegraphs() -> Result<(), String> {
+        use crate::diff::{Change, ChangeKind};
+
+        let build = |with_name: bool| -> Result<String, String> {
+            Store::reset()?;
+            let mut user = t::struct_();
+            user.propx("id", t::integer())?;
+            if with_name {
+                user.propx("name", t::string())?;
+            }
+            let user = user.named("User").build()?;
+            let mat = Lib::register_deno_func(
+                MaterializerDenoFunc::with_code("() => ({})"),
+                Effect::None,
+            )?;
+            expose_one("getUser", t::func(t::struct_().build()?, user, mat)?)?;
+            Lib::finalize_typegraph()
+        };
+        let old = build(true)?;
+        let new = build(false)?;
+
+        assert_eq!(
+            Lib::diff_typegraphs(&old, &new)?,
+            vec![Change {
+                path: "getUser.output.name".to_string(),
+                kind: ChangeKind::FieldRemoved,
+                breaking: true,
+            }]
+        );
+        assert_eq!(
+            Lib::diff_typegraphs(&new, &old)?,
+            vec![Change {
+                path: "getUser.output.name".to_string(),
+                kind: ChangeKind::FieldAdded,
+                breaking: false,
+            }]
+        );
+        assert_eq!(Lib::diff_typegraphs(&old, &old)?, vec![]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff_output_changes() -> Result<(), String> {
+        use crate::diff::{Change, ChangeKind};
+
+        let build = |v2: bool| -> Result<String, String> {
+            Store::reset()?;
+            let mut statuses = vec!["active".to_string()];
+            if v2 {
+                statuses.push("banned".to_string());
+            }
+            let status = t::string().enum_(statuses).build()?;
+            let name = t::string().max(if v2 { 64 } else { 32 }).build()?;
+            let mut variants = vec![
+                t::struct_().propx("ok", t::boolean())?.build()?,
+                t::struct_().propx("error", t::string())?.build()?,
+            ];
+            if v2 {
+                variants.reverse();
+            }
+            let result = t::union(variants).build()?;
+            let user = t::struct_()
+                .prop("status", status)
+                .prop("name", name)
+                .prop("result", result)
+                .build()?;
+            let mat = Lib::register_deno_func(
+                MaterializerDenoFunc::with_code("() => ({})"),
+                Effect::None,
+            )?;
+            expose_one("getUser", t::func(t::struct_().build()?, user, mat)?)?;
+            Lib::finalize_typegraph()
+        };
+        let old = build(false)?;
+        let new = build(true)?;
+
+        // the variants are matched by structure, not by position
+        assert_eq!(
+            Lib::diff_typegraphs(&old, &new)?,
+            vec![
+                Change {
+                    path: "getUser.output.status".to_string(),
+                    kind: ChangeKind::EnumValueAdded("\"banned\"".to_string()),
+                    breaking: true,
+                },
+                Change {
+                    path: "getUser.output.name".to_string(),
+                    kind: ChangeKind::ConstraintLoosened("maxLength".to_string()),
+                    breaking: true,
+                },
+            ]
+        );
+        assert_eq!(
+            Lib::diff_typegraphs(&new, &old)?,
+            vec![
+                Change {
+                    path: "getUser.output.status".to_string(),
+                    kind: ChangeKind::EnumValueRemoved("\"banned\"".to_string()),
+                    breaking: false,
+                },
+                Change {
+                    path: "getUser.output.name".to_string(),
+                    kind: ChangeKind::ConstraintTightened("maxLength".to_string()),
+                    breaking: false,
+                },
+            ]
+        );
+        Ok(())
+    }
+}
@@ -2,17 +2,64 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use crate::wit::core::Error as TgError;
+use std::panic::Location;
 
 pub type Result<T, E = TgError> = std::result::Result<T, E>;
 
+pub fn empty_policy_group() -> TgError {
+    "a policy group must contain at least one policy".to_string()
+}
+
+pub fn invalid_pattern(pattern: &str) -> TgError {
+    format!("invalid pattern {pattern:?}")
+}
+
+pub fn at_location(error: TgError, location: &Location) -> TgError {
+    format!("{error}\n  at {location}")
+}
+
+pub fn multiple(errors: Vec<TgError>) -> TgError {
+    let count = errors.len();
+    let list = errors
+        .into_iter()
+        .map(|e| format!("  - {e}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("{count} errors:\n{list}")
+}
+
 pub fn invalid_max_value() -> TgError {
     "min must be less than or equal to max".to_string()
 }
 
+pub fn max_size_without_binary_format() -> TgError {
+    "max_size requires the binary format".to_string()
+}
+
+pub fn conflicting_bounds(side: &str) -> TgError {
+    format!("inclusive and exclusive {side} cannot be set together")
+}
+
+pub fn invalid_example(example: &str, reason: &str) -> TgError {
+    format!("invalid example {example}: {reason}")
+}
+
 pub fn duplicate_key(name: &str) -> TgError {
     format!("duplicate key '{name}' in properties")
 }
 
+pub fn too_many_props(count: usize, max: u32) -> TgError {
+    format!("struct has {count} properties, exceeding the limit of {max}")
+}
+
+pub fn invalid_max_depth() -> TgError {
+    "max depth must be greater than 0".to_string()
+}
+
+pub fn unknown_field(name: &str) -> TgError {
+    format!("unknown field '{name}'")
+}
+
 pub fn invalid_prop_key(name: &str) -> TgError {
     format!(
         "'{name}' is not a valid property key: allowed characters are ascii letters and underscores",
@@ -27,10 +74,46 @@ pub fn invalid_input_type(got: &str) -> TgError {
     format!("expected a Struct as input type but got {got}")
 }
 
+pub fn func_in_input(path: &str) -> TgError {
+    format!("function types are not allowed in input types, found at {path:?}")
+}
+
+pub fn invalid_timeout() -> TgError {
+    "timeout must be greater than 0".to_string()
+}
+
+pub fn invalid_rate_weight() -> TgError {
+    "rate weight must be greater than 0".to_string()
+}
+
+pub fn func_as_output(path: &str) -> TgError {
+    if path.is_empty() {
+        "the output type must not be a function".to_string()
+    } else {
+        format!("function types are not allowed in output types, found at {path:?}")
+    }
+}
+
 pub fn invalid_type(expected: &str, got: &str) -> TgError {
     format!("expected {expected} but got {got}")
 }
 
+pub fn invalid_map_key(got: &str) -> TgError {
+    format!("map keys must be strings, got {got}")
+}
+
+pub fn duplicate_union_variant(repr: &str) -> TgError {
+    format!("duplicate variant {repr}")
+}
+
+pub fn missing_discriminator_field(variant: &str) -> TgError {
+    format!("union variant {variant} must be a struct with the discriminator as a string field")
+}
+
+pub fn invalid_default_value(repr: &str) -> TgError {
+    format!("default value does not match the item type of {repr}")
+}
+
 pub fn nested_typegraph_context(active: &str) -> TgError {
     format!("cannot init typegraph: typegraph '{active}' is still active")
 }
@@ -43,18 +126,42 @@ pub fn invalid_export_type(name: &str, got: &str) -> TgError {
     format!("expected a Func to be exposed, got {got} under the name '{name}'")
 }
 
+pub fn endpoint_read_failed(path: &str, cause: &str) -> TgError {
+    format!("could not read endpoint file {path:?}: {cause}")
+}
+
+pub fn endpoint_parse_failed(path: &str, cause: &str) -> TgError {
+    format!("could not parse endpoint file {path:?}: {cause}")
+}
+
+pub fn invalid_typegraph_name(name: &str) -> TgError {
+    format!("invalid typegraph name {name:?}: allowed characters are ascii letters, digits, underscores and dashes")
+}
+
 pub fn invalid_export_name(name: &str) -> TgError {
     format!("invalid export name '{name}': allowed characters are ascii letters and underscores")
 }
 
+pub fn reserved_export_name(name: &str) -> TgError {
+    format!("export name '{name}' is reserved: names starting with '__' are used by GraphQL introspection")
+}
+
 pub fn duplicate_export_name(name: &str) -> TgError {
     format!("duplicate export name '{name}'")
 }
 
+pub fn namespace_title_conflict(title: &str) -> TgError {
+    format!("cannot create the namespace object '{title}': a type with the same title exists")
+}
+
 pub fn unregistered_type_name(name: &str) -> TgError {
     format!("type name '{name}' has not been registered")
 }
 
+pub fn proxy_cycle(name: &str) -> TgError {
+    format!("could not resolve proxy '{name}': cycle detected")
+}
+
 pub fn object_not_found(kind: &str, id: u32) -> TgError {
     format!("{kind} #{id} not found")
 }
@@ -84,8 +191,85 @@ pub fn unknown_predefined_function(name: &str, runtime: &str) -> TgError {
     format!("unknown predefined function {name} for runtime {runtime}")
 }
 
-pub fn duplicate_policy_name(name: &str) -> TgError {
-    format!("duplicate policy name '{name}'")
+pub fn policy_name_conflict(name: &str) -> TgError {
+    format!("policy name '{name}' is already used by a policy with a different materializer")
+}
+
+pub fn empty_typegraph(name: &str) -> TgError {
+    format!("typegraph '{name}' does not expose anything")
+}
+
+pub fn missing_runtime_for_type(type_repr: &str) -> TgError {
+    format!("no runtime to convert the type {type_repr} into")
+}
+
+pub fn unnamed_scalar() -> TgError {
+    "custom scalars must be named".to_string()
+}
+
+pub fn invalid_expiry_secs(bucket: &str) -> TgError {
+    format!("expiry for presigned urls of bucket {bucket:?} must be greater than 0")
+}
+
+pub fn empty_wasi_func_name(module: &str) -> TgError {
+    format!("missing the name of the function exported by the wasm module {module:?}")
+}
+
+pub fn invalid_runtime_type(expected: &str, actual: &str) -> TgError {
+    format!("expected a {expected} runtime, got {actual}")
+}
+
+pub fn incomplete_grpc_method(service: &str, method: &str) -> TgError {
+    format!("invalid grpc method {service:?}/{method:?}: the service and the method are required")
+}
+
+pub fn empty_kafka_topic() -> TgError {
+    "the topic of a kafka publish materializer must not be empty".to_string()
+}
+
+pub fn idempotency_on_read(path: &str) -> TgError {
+    format!("idempotency key {path:?} is only allowed on mutating materializers")
+}
+
+pub fn invalid_idempotency_key(path: &str) -> TgError {
+    format!("invalid idempotency key {path:?}: expected a dotted path like 'input.requestId'")
+}
+
+pub fn effect_not_supported(runtime: &str, effect: &str) -> TgError {
+    format!("the {runtime} runtime does not support the {effect:?} effect")
+}
+
+pub fn incompatible_union_field(field: &str, repr_a: &str, repr_b: &str) -> TgError {
+    format!("field {field:?} has incompatible types across the variants: {repr_a} and {repr_b}")
+}
+
+pub fn enum_value_out_of_range(value: &str, reason: &str) -> TgError {
+    format!("enumerated value {value} is out of range: {reason}")
+}
+
+pub fn default_not_in_enum(value: &str) -> TgError {
+    format!("default value {value} is not one of the enumerated values")
+}
+
+pub fn not_internable(type_repr: &str) -> TgError {
+    format!("cannot intern {type_repr}: only unnamed scalar types can be interned")
+}
+
+pub fn unresolved_lazy_type(name: &str) -> TgError {
+    format!("lazy type '{name}' has not been resolved yet, see `t::resolve_lazy`")
+}
+
+pub fn stale_store_state() -> TgError {
+    "cannot restore a store state dropped by the restore of an enclosing state".to_string()
+}
+
+#[cfg(feature = "multithread")]
+pub fn store_in_use() -> TgError {
+    "the store is in use by a typegraph of another thread".to_string()
+}
+
+pub fn type_frozen(name: &str) -> TgError {
+    format!("type '{name}' is frozen and cannot be modified")
 }
 
 pub fn base_required(name: &str) -> TgError {
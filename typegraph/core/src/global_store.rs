@@ -3,15 +3,25 @@
 
 use crate::errors::{self, Result};
 use crate::runtimes::{DenoMaterializer, Materializer, MaterializerDenoModule, Runtime};
+use crate::sync::{Rc, ThreadSafe};
 use crate::types::{Struct, Type, TypeFun, TypeId, WrapperTypeData};
 use crate::wit::core::{Policy as CorePolicy, PolicyId, RuntimeId};
 use crate::wit::runtimes::{Effect, MaterializerDenoPredefined, MaterializerId};
 use indexmap::IndexMap;
-use std::rc::Rc;
-use std::{cell::RefCell, collections::HashMap};
+use std::collections::HashMap;
 
 pub type Policy = Rc<CorePolicy>;
 
+/// Definition of a lazy type, see [`crate::t::lazy`]
+pub trait LazyTypeBuild: Fn() -> Result<TypeId> + ThreadSafe {}
+
+impl<F: Fn() -> Result<TypeId> + ThreadSafe> LazyTypeBuild for F {}
+
+/// Deferred type definition, see [`crate::t::lazy`]
+struct LazyType {
+    build: Rc<dyn LazyTypeBuild>,
+}
+
 /// As all the store entries are append only, we can set a restore point
 /// to reset it to a previous state.
 /// This is useful to remove the entities that were added in a typegraph scope
@@ -21,23 +31,57 @@ pub type Policy = Rc<CorePolicy>;
 ///
 /// With this feature, we can reuse a type name in a typegraph definition module,
 /// within different typegraph contexts.
-#[derive(Default, Debug)]
+///
+/// The saved states form a stack: restoring a state also drops the states
+/// saved after it, which can no longer be restored.
+/// A state that was not restored with [`Store::restore`] is restored when
+/// dropped, so that early returns do not leak entities in the global scope.
+///
+/// With the `multithread` feature, the saved states truncate the store shared
+/// by all the threads: only the thread that saved them can save more until
+/// they are all restored.
+#[derive(Debug)]
+#[must_use]
 pub struct SavedState {
+    /// position in the stack of the saved states
+    depth: usize,
     types: usize,
     type_names: usize,
+    lazy_types: usize,
     runtimes: usize,
     materializers: usize,
     policies: usize,
+    restored: bool,
+}
+
+impl Drop for SavedState {
+    fn drop(&mut self) {
+        if !self.restored {
+            // must neither block nor panic, even while unwinding
+            if let Some(Err(e)) = try_with_store_mut(|s| s.restore_state(self)) {
+                crate::log!("warning: dropped saved store state: {e}");
+            }
+        }
+    }
 }
 
 #[derive(Default)]
 pub struct Store {
-    pub types: Vec<Type>,
+    /// `None` for the ids reserved by a registration in progress
+    types: Vec<Option<Type>>,
     pub type_by_names: IndexMap<String, TypeId>,
+    interned_types: HashMap<String, TypeId>,
+    /// Lazy types by proxy name, pending until the name is registered
+    lazy_types: IndexMap<String, LazyType>,
 
     pub runtimes: Vec<Runtime>,
     pub materializers: Vec<Materializer>,
     pub policies: Vec<Policy>,
+    /// number of saved states not restored yet
+    save_depth: usize,
+    /// thread that saved the states not restored yet
+    #[cfg(feature = "multithread")]
+    save_owner: Option<std::thread::ThreadId>,
 
     deno_runtime: RuntimeId,
     predefined_deno_functions: HashMap<String, MaterializerId>,
@@ -68,63 +112,241 @@ impl Store {
 
 const PREDEFINED_DENO_FUNCTIONS: &[&str] = &["identity", "true"];
 
+#[cfg(not(feature = "multithread"))]
 thread_local! {
-    pub static STORE: RefCell<Store> = RefCell::new(Store::new());
+    pub static STORE: std::cell::RefCell<Store> = std::cell::RefCell::new(Store::new());
 }
 
+#[cfg(not(feature = "multithread"))]
 fn with_store<T, F: FnOnce(&Store) -> T>(f: F) -> T {
     STORE.with(|s| f(&s.borrow()))
 }
 
+#[cfg(not(feature = "multithread"))]
 fn with_store_mut<T, F: FnOnce(&mut Store) -> T>(f: F) -> T {
     STORE.with(|s| f(&mut s.borrow_mut()))
 }
 
-#[cfg(test)]
-impl Store {
-    pub fn reset() {
-        let _ = crate::typegraph::finalize();
-        with_store_mut(|s| *s = Store::new());
+/// Same as `with_store_mut`, returns `None` if the store is already borrowed,
+/// or was already destroyed at thread exit
+#[cfg(not(feature = "multithread"))]
+fn try_with_store_mut<T, F: FnOnce(&mut Store) -> T>(f: F) -> Option<T> {
+    STORE
+        .try_with(|s| s.try_borrow_mut().ok().map(|mut s| f(&mut s)))
+        .ok()
+        .flatten()
+}
+
+/// Store shared by all the threads; the typegraph contexts remain thread local
+#[cfg(feature = "multithread")]
+pub static STORE: once_cell::sync::Lazy<std::sync::RwLock<Store>> =
+    once_cell::sync::Lazy::new(|| std::sync::RwLock::new(Store::new()));
+
+#[cfg(feature = "multithread")]
+fn with_store<T, F: FnOnce(&Store) -> T>(f: F) -> T {
+    f(&STORE.read().unwrap())
+}
+
+#[cfg(feature = "multithread")]
+fn with_store_mut<T, F: FnOnce(&mut Store) -> T>(f: F) -> T {
+    f(&mut STORE.write().unwrap())
+}
+
+/// Same as `with_store_mut`, returns `None` if the lock is poisoned, or still
+/// held after a few attempts, e.g. by the current thread
+#[cfg(feature = "multithread")]
+fn try_with_store_mut<T, F: FnOnce(&mut Store) -> T>(f: F) -> Option<T> {
+    for _ in 0..100 {
+        match STORE.try_write() {
+            Ok(mut s) => return Some(f(&mut s)),
+            Err(std::sync::TryLockError::WouldBlock) => std::thread::yield_now(),
+            Err(std::sync::TryLockError::Poisoned(_)) => return None,
+        }
     }
+    None
 }
 
 impl Store {
-    pub fn save() -> SavedState {
-        with_store(|s| SavedState {
-            types: s.types.len(),
-            type_names: s.type_by_names.len(),
-            runtimes: s.runtimes.len(),
-            materializers: s.materializers.len(),
-            policies: s.policies.len(),
+    /// Fails with the `multithread` feature while another thread has saved
+    /// states, see [`SavedState`]
+    pub fn reset() -> Result<()> {
+        crate::typegraph::reset();
+        with_store_mut(|s| {
+            s.check_save_owner()?;
+            *s = Store::new();
+            Ok(())
         })
     }
 
-    pub fn restore(saved_state: SavedState) {
+    /// Fails with the `multithread` feature while another thread has saved
+    /// states, see [`SavedState`]
+    pub fn save() -> Result<SavedState> {
         with_store_mut(|s| {
-            s.types.truncate(saved_state.types);
-            s.type_by_names.truncate(saved_state.type_names);
-            s.runtimes.truncate(saved_state.runtimes);
-            s.materializers.truncate(saved_state.materializers);
-            s.policies.truncate(saved_state.policies);
+            s.check_save_owner()?;
+            #[cfg(feature = "multithread")]
+            {
+                s.save_owner = Some(std::thread::current().id());
+            }
+            s.save_depth += 1;
+            Ok(SavedState {
+                depth: s.save_depth - 1,
+                types: s.types.len(),
+                type_names: s.type_by_names.len(),
+                lazy_types: s.lazy_types.len(),
+                runtimes: s.runtimes.len(),
+                materializers: s.materializers.len(),
+                policies: s.policies.len(),
+                restored: false,
+            })
         })
     }
 
+    #[cfg(feature = "multithread")]
+    fn check_save_owner(&self) -> Result<()> {
+        match self.save_owner {
+            Some(owner) if owner != std::thread::current().id() => Err(errors::store_in_use()),
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(not(feature = "multithread"))]
+    fn check_save_owner(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Fails if the state was dropped by the restore of an enclosing state
+    pub fn restore(mut saved_state: SavedState) -> Result<()> {
+        saved_state.restored = true;
+        with_store_mut(|s| s.restore_state(&saved_state))
+    }
+
+    fn restore_state(&mut self, saved_state: &SavedState) -> Result<()> {
+        if saved_state.depth >= self.save_depth {
+            return Err(errors::stale_store_state());
+        }
+        self.save_depth = saved_state.depth;
+        #[cfg(feature = "multithread")]
+        if self.save_depth == 0 {
+            self.save_owner = None;
+        }
+        self.types.truncate(saved_state.types);
+        self.interned_types
+            .retain(|_, id| (id.0 as usize) < saved_state.types);
+        self.type_by_names.truncate(saved_state.type_names);
+        self.lazy_types.truncate(saved_state.lazy_types);
+        self.runtimes.truncate(saved_state.runtimes);
+        self.materializers.truncate(saved_state.materializers);
+        self.policies.truncate(saved_state.policies);
+        Ok(())
+    }
+
+    pub fn get_types() -> Vec<Type> {
+        with_store(|s| s.types.iter().flatten().cloned().collect())
+    }
+
     pub fn get_type_by_name(name: &str) -> Option<TypeId> {
         with_store(|s| s.type_by_names.get(name).copied())
     }
 
+    pub fn resolve_type_name(name: &str) -> Result<TypeId> {
+        with_store(|s| match s.type_by_names.get(name) {
+            Some(id) => Ok(*id),
+            None if s.lazy_types.contains_key(name) => Err(errors::unresolved_lazy_type(name)),
+            None => Err(errors::unregistered_type_name(name)),
+        })
+    }
+
+    /// Registers a lazy type and returns the name of the proxy to resolve it,
+    /// see [`Store::resolve_lazy_types`]
+    pub fn register_lazy_type(build: impl LazyTypeBuild + 'static) -> String {
+        with_store_mut(|s| {
+            let name = format!("__lazy_{}", s.lazy_types.len());
+            let lazy = LazyType {
+                build: Rc::new(build),
+            };
+            s.lazy_types.insert(name.clone(), lazy);
+            name
+        })
+    }
+
+    /// Builds the pending lazy types and registers the results under the name
+    /// of their proxy
+    pub fn resolve_lazy_types() -> Result<()> {
+        // building a lazy type may register other ones
+        while let Some((name, build)) = with_store(|s| {
+            s.lazy_types
+                .iter()
+                .find(|(name, _)| !s.type_by_names.contains_key(*name))
+                .map(|(name, lazy)| (name.clone(), lazy.build.clone()))
+        }) {
+            // the store must not be borrowed while building
+            let type_id = build()?;
+            Self::register_type_name(name, type_id)?;
+        }
+        Ok(())
+    }
+
+    /// The id is reserved before calling `build`, which can then access the
+    /// store.
     pub fn register_type(build: impl FnOnce(TypeId) -> Type) -> Result<TypeId> {
-        // this works since the store is thread local
-        let id = with_store(|s| s.types.len()) as u32;
-        let typ = build(id.into());
+        let id = with_store_mut(|s| s.reserve_type_id());
+        let typ = build(id);
+        with_store_mut(|s| s.set_type(typ))
+    }
+
+    /// Registers several types under a single store borrow, with
+    /// [`Store::add_type`]; `f` must only access the store through the given
+    /// reference.
+    /// The registration is atomic: the types added are dropped if `f` fails.
+    pub fn register_types<T>(f: impl FnOnce(&mut Store) -> Result<T>) -> Result<T> {
+        with_store_mut(|s| {
+            let (types, type_names, lazy_types) =
+                (s.types.len(), s.type_by_names.len(), s.lazy_types.len());
+            let res = f(s);
+            if res.is_err() {
+                s.types.truncate(types);
+                s.interned_types.retain(|_, id| (id.0 as usize) < types);
+                s.type_by_names.truncate(type_names);
+                s.lazy_types.truncate(lazy_types);
+            }
+            res
+        })
+    }
+
+    /// `build` must not access the store, see [`Store::register_types`]
+    pub fn add_type(&mut self, build: impl FnOnce(TypeId) -> Type) -> Result<TypeId> {
+        let id = self.reserve_type_id();
+        self.set_type(build(id))
+    }
+
+    fn reserve_type_id(&mut self) -> TypeId {
+        self.types.push(None);
+        (self.types.len() as u32 - 1).into()
+    }
+
+    /// Fills the slot reserved for the type; the slot stays empty on error
+    fn set_type(&mut self, typ: Type) -> Result<TypeId> {
+        let id = typ.get_id();
         if let Some(name) = typ.get_base().and_then(|b| b.name.clone()) {
-            Self::register_type_name(name, id.into())?;
+            if self.type_by_names.contains_key(&name) {
+                return Err(format!("type with name {:?} already exists", name));
+            }
+            self.type_by_names.insert(name, id);
         }
-        with_store_mut(move |s| -> Result<()> {
-            s.types.push(typ);
-            Ok(())
-        })?;
-        Ok(id.into())
+        self.types[id.0 as usize] = Some(typ);
+        Ok(id)
+    }
+
+    /// Returns the id of the interned type structurally identical to the
+    /// given one, interning the latter if there is none.
+    /// Only the unnamed scalar types can be interned.
+    pub fn intern_type(type_id: TypeId) -> Result<TypeId> {
+        let Some(key) = type_id.as_type()?.intern_key() else {
+            return Err(errors::not_internable(&type_id.repr()?));
+        };
+        Ok(with_store_mut(|s| {
+            *s.interned_types.entry(key).or_insert(type_id)
+        }))
     }
 
     pub fn register_type_name(name: impl Into<String>, id: TypeId) -> Result<()> {
@@ -221,16 +443,18 @@ impl Store {
         })
     }
 
+    /// Re-registering an identical policy returns the existing id
     pub fn register_policy(policy: Policy) -> Result<PolicyId> {
-        with_store_mut(|s| {
-            let id = s.policies.len() as u32;
-            if s.policies.iter().any(|p| p.name == policy.name) {
-                Err(errors::duplicate_policy_name(&policy.name))
-            } else {
-                s.policies.push(policy);
-                Ok(id)
-            }
-        })
+        with_store_mut(
+            |s| match s.policies.iter().position(|p| p.name == policy.name) {
+                Some(idx) if s.policies[idx].materializer == policy.materializer => Ok(idx as u32),
+                Some(_) => Err(errors::policy_name_conflict(&policy.name)),
+                None => {
+                    s.policies.push(policy);
+                    Ok(s.policies.len() as u32 - 1)
+                }
+            },
+        )
     }
 
     pub fn get_policy(id: PolicyId) -> Result<Policy> {
@@ -251,6 +475,7 @@ impl Store {
             let runtime_id = Store::get_deno_runtime();
             let mat = Store::register_materializer(Materializer {
                 runtime_id,
+                idempotency_key: None,
                 effect: Effect::None,
                 data: Rc::new(DenoMaterializer::Predefined(MaterializerDenoPredefined {
                     name: name.clone(),
@@ -271,6 +496,7 @@ impl Store {
             let runtime_id = Store::get_deno_runtime();
             let mat = Store::register_materializer(Materializer {
                 runtime_id,
+                idempotency_key: None,
                 effect: Effect::None, // N/A
                 data: Rc::new(DenoMaterializer::Module(MaterializerDenoModule {
                     file: file.clone(),
@@ -289,6 +515,7 @@ impl TypeId {
             s.types
                 .get(self.0 as usize)
                 .cloned()
+                .flatten()
                 .ok_or_else(|| errors::object_not_found("type", self.0))
         })
     }
@@ -334,3 +561,278 @@ impl TypeId {
         Ok(id)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::types::{Type, TypeId};
+    use crate::wit::core::Core;
+    use crate::Lib;
+
+    #[test]
+    fn test_reset() -> Result<(), String> {
+        let build = || -> Result<(), String> {
+            let mat = dummy_mat()?;
+            let inp = t::struct_()
+                .propx("id", t::integer())?
+                .named("User")
+                .build()?;
+            expose_one("one", t::func(inp, t::string().build()?, mat)?)
+        };
+
+        // no active typegraph
+        Lib::reset()?;
+        build()?;
+        // the typegraph is dropped without being finalized
+        Lib::reset()?;
+        build()?;
+        let typegraph = Lib::finalize_typegraph_struct()?;
+        assert!(typegraph.types.iter().any(|t| t.base().title == "User"));
+        Lib::reset()
+    }
+
+    #[test]
+    fn test_nested_store_save() -> Result<(), String> {
+        Store::reset()?;
+        t::integer().min(1).build()?;
+
+        let outer = Store::save()?;
+        t::integer().min(2).build()?;
+        let inner = Store::save()?;
+        t::integer().min(3).build()?;
+        assert_eq!(Store::get_types().len(), 3);
+        Store::restore(inner)?;
+        assert_eq!(Store::get_types().len(), 2);
+        Store::restore(outer)?;
+        assert_eq!(Store::get_types().len(), 1);
+
+        // restoring the outer state drops the inner one
+        let outer = Store::save()?;
+        t::integer().min(4).build()?;
+        let inner = Store::save()?;
+        Store::restore(outer)?;
+        t::integer().min(5).build()?;
+        t::integer().min(6).build()?;
+        assert_eq!(Store::restore(inner), Err(errors::stale_store_state()));
+        assert_eq!(Store::get_types().len(), 3);
+
+        // a state is restored when dropped
+        {
+            let _saved = Store::save()?;
+            t::integer().min(7).build()?;
+        }
+        assert_eq!(Store::get_types().len(), 3);
+
+        // so is the state saved by a dropped typegraph context
+        setup(None)?;
+        t::integer().min(8).build()?;
+        crate::typegraph::reset();
+        assert_eq!(Store::get_types().len(), 3);
+
+        // a state dropped while the store is borrowed stays pending
+        let outer = Store::save()?;
+        let inner = Store::save()?;
+        t::integer().min(9).build()?;
+        super::with_store(|_| drop(inner));
+        assert_eq!(Store::get_types().len(), 4);
+        Store::restore(outer)?;
+        assert_eq!(Store::get_types().len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stale_state_dropped() -> Result<(), String> {
+        Store::reset()?;
+        crate::host::abi::take_logs();
+        let outer = Store::save()?;
+        let inner = Store::save()?;
+        Store::restore(outer)?;
+        drop(inner);
+        assert_eq!(
+            crate::host::abi::take_logs(),
+            vec![format!(
+                "warning: dropped saved store state: {}",
+                errors::stale_store_state()
+            )]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_struct_batch() -> Result<(), String> {
+        let field_count = 50;
+        let serialize = |build: &dyn Fn() -> Result<TypeId, String>| -> Result<String, String> {
+            Store::reset()?;
+            let s = build()?;
+            let mat = dummy_mat()?;
+            expose_one("one", t::func(s, s, mat)?)?;
+            Lib::finalize_typegraph()
+        };
+
+        let per_field = serialize(&|| {
+            let mut builder = t::struct_();
+            for i in 0..field_count {
+                let ty = match i % 3 {
+                    0 => t::integer().min(0).build()?,
+                    1 => t::string().build()?,
+                    _ => t::boolean().build()?,
+                };
+                builder.prop(format!("field_{i}"), ty);
+            }
+            builder.build()
+        })?;
+
+        let batch = serialize(&|| {
+            Lib::build_struct_batch(
+                (0..field_count)
+                    .map(|i| {
+                        let spec = match i % 3 {
+                            0 => t::integer().min(0).into(),
+                            1 => t::string().into(),
+                            _ => t::boolean().into(),
+                        };
+                        (format!("field_{i}"), spec)
+                    })
+                    .collect(),
+            )
+        })?;
+
+        assert_eq!(batch, per_field);
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_type_store_access() -> Result<(), String> {
+        Store::reset()?;
+        let int = t::integer().min(1).build()?;
+        let copy = Store::register_type(|id| {
+            // the store is not borrowed while building
+            let Type::Integer(inner) = int.as_type().unwrap() else {
+                unreachable!()
+            };
+            Type::Integer(
+                crate::types::Integer::new(id, inner.base.clone(), inner.data.clone()).into(),
+            )
+        })?;
+        assert_ne!(copy, int);
+        assert_eq!(copy.repr()?, format!("integer(#{}, min=1)", copy.0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_types_atomic() -> Result<(), String> {
+        Store::reset()?;
+        let count = Store::get_types().len();
+        let (mut a, mut b) = (t::integer(), t::string());
+        a.named("A");
+        b.named("A");
+        let res = Lib::build_struct_batch(vec![
+            ("a".to_string(), a.into()),
+            ("b".to_string(), b.into()),
+        ]);
+        assert_eq!(res, Err("type with name \"A\" already exists".to_string()));
+        assert_eq!(Store::get_types().len(), count);
+        assert_eq!(Store::get_type_by_name("A"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_types_rollback() -> Result<(), String> {
+        Store::reset()?;
+        let int = t::integer().min(1).build()?;
+        let typ = int.as_type()?;
+        let key = typ.intern_key().unwrap();
+        let Type::Integer(inner) = typ else {
+            unreachable!()
+        };
+        let res: Result<(), String> = Store::register_types(|s| {
+            let copy = s.add_type(|id| {
+                Type::Integer(
+                    crate::types::Integer::new(id, inner.base.clone(), inner.data.clone()).into(),
+                )
+            })?;
+            s.interned_types.insert(key, copy);
+            s.lazy_types.insert(
+                "__lazy_0".to_string(),
+                super::LazyType {
+                    build: crate::sync::Rc::new(|| t::integer().build()),
+                },
+            );
+            Err("failed".to_string())
+        });
+        assert_eq!(res, Err("failed".to_string()));
+        assert_eq!(Store::intern_type(int)?, int);
+        assert_eq!(
+            Store::resolve_type_name("__lazy_0"),
+            Err(errors::unregistered_type_name("__lazy_0"))
+        );
+        Ok(())
+    }
+}
+
+// The other tests rely on a fresh store in each test thread, so only this one
+// is meant to run with the `multithread` feature.
+#[cfg(all(test, feature = "multithread"))]
+mod tests {
+    use super::Store;
+    use crate::errors::{self, Result};
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::types::TypeId;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    // the tests share the store
+    static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_shared_store() -> Result<()> {
+        let _lock = STORE_LOCK.lock().unwrap();
+        Store::reset()?;
+        let spawn = |prefix: &'static str| {
+            std::thread::spawn(move || -> Result<Vec<TypeId>> {
+                (0..50)
+                    .map(|i| t::integer().named(format!("{prefix}{i}")).build())
+                    .collect()
+            })
+        };
+        let (a, b) = (spawn("A"), spawn("B"));
+        let (a, b) = (a.join().unwrap()?, b.join().unwrap()?);
+
+        let ids: HashSet<_> = a.iter().chain(b.iter()).collect();
+        assert_eq!(ids.len(), 100);
+        for (prefix, ids) in [("A", a), ("B", b)] {
+            for (i, id) in ids.into_iter().enumerate() {
+                assert_eq!(Store::get_type_by_name(&format!("{prefix}{i}")), Some(id));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_saved_state_owner() -> Result<()> {
+        let _lock = STORE_LOCK.lock().unwrap();
+        Store::reset()?;
+        t::integer().named("Global").build()?;
+        let saved = Store::save()?;
+        t::integer().named("Local").build()?;
+
+        // another thread cannot drop the types of the pending state
+        let (save, reset) = std::thread::spawn(|| (Store::save().map(|_| ()), Store::reset()))
+            .join()
+            .unwrap();
+        assert_eq!(save, Err(errors::store_in_use()));
+        assert_eq!(reset, Err(errors::store_in_use()));
+        assert!(Store::get_type_by_name("Local").is_some());
+
+        Store::restore(saved)?;
+        assert_eq!(Store::get_type_by_name("Local"), None);
+        std::thread::spawn(|| Store::save().map(|_| ()))
+            .join()
+            .unwrap()?;
+        assert!(Store::get_type_by_name("Global").is_some());
+        Ok(())
+    }
+}
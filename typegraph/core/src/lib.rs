@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: MPL-2.0
 
 mod conversion;
+mod diff;
 mod errors;
 mod global_store;
 mod runtimes;
+mod sync;
 mod t;
 mod typedef;
 mod typegraph;
@@ -15,21 +17,22 @@ mod validation;
 #[cfg(test)]
 mod test_utils;
 
-use std::collections::HashSet;
-
 use errors::Result;
 use global_store::Store;
 use indoc::formatdoc;
 use regex::Regex;
+use t::{TypeBuilder, TypeBuilderSpec};
 use types::{
-    Array, Boolean, Either, File, Float, Func, Integer, Optional, Proxy, StringT, Struct, Type,
-    TypeBoolean, TypeId, Union, WithInjection, WithPolicy,
+    Array, Boolean, Either, File, Float, Func, Integer, Json, Map, Optional, Proxy, Scalar,
+    StringT, Struct, Type, TypeBoolean, TypeFun, TypeId, TypeJson, Union, WithInjection,
+    WithPolicy, WrapperTypeData,
 };
-use validation::validate_name;
+use validation::types::find_nested_func;
 use wit::core::{
-    ContextCheck, Policy, PolicyId, PolicySpec, TypeArray, TypeBase, TypeEither, TypeFile,
-    TypeFloat, TypeFunc, TypeId as CoreTypeId, TypeInteger, TypeOptional, TypePolicy, TypeProxy,
-    TypeString, TypeStruct, TypeUnion, TypeWithInjection, TypegraphInitParams,
+    ContextCheck, Policy, PolicyDescription, PolicyId, PolicySpec, RuntimeInfo, TypeArray,
+    TypeBase, TypeEither, TypeFile, TypeFloat, TypeFunc, TypeId as CoreTypeId, TypeInfo,
+    TypeInteger, TypeMap, TypeOptional, TypePolicy, TypeProxy, TypeScalar, TypeString, TypeStruct,
+    TypeUnion, TypeWithInjection, TypegraphInitParams,
 };
 use wit::runtimes::{MaterializerDenoFunc, Runtimes};
 
@@ -54,15 +57,78 @@ pub mod host {
 #[cfg(not(feature = "wasm"))]
 pub mod host {
     pub mod abi {
+        #[cfg(test)]
+        thread_local! {
+            static LOGS: std::cell::RefCell<Vec<String>> = Default::default();
+            static GLOB_CALLS: std::cell::Cell<usize> = Default::default();
+            static FILES: std::cell::RefCell<Vec<(String, Result<String, String>)>> = Default::default();
+        }
+
+        /// messages logged so far on the current thread
+        #[cfg(test)]
+        pub fn take_logs() -> Vec<String> {
+            LOGS.with(|logs| std::mem::take(&mut *logs.borrow_mut()))
+        }
+
+        /// number of `glob` calls so far on the current thread
+        #[cfg(test)]
+        pub fn take_glob_calls() -> usize {
+            GLOB_CALLS.with(|calls| calls.take())
+        }
+
         pub fn log(message: &str) {
+            #[cfg(test)]
+            LOGS.with(|logs| logs.borrow_mut().push(message.to_string()));
             println!("{}", message);
         }
+
+        /// files (path, content or read error) returned by `glob` and `read_file` on the current thread
+        #[cfg(test)]
+        pub fn set_files(files: Vec<(&str, Result<&str, &str>)>) {
+            let files = files
+                .into_iter()
+                .map(|(path, res)| {
+                    (
+                        path.to_string(),
+                        res.map(|s| s.to_string()).map_err(|e| e.to_string()),
+                    )
+                })
+                .collect();
+            FILES.with(|f| *f.borrow_mut() = files);
+        }
+
+        #[cfg(not(test))]
         pub fn glob(_pattern: &str, _exts: &[String]) -> Result<Vec<String>, String> {
             Ok(vec![])
         }
+        #[cfg(test)]
+        pub fn glob(_pattern: &str, exts: &[String]) -> Result<Vec<String>, String> {
+            GLOB_CALLS.with(|calls| calls.set(calls.get() + 1));
+            Ok(FILES.with(|files| {
+                files
+                    .borrow()
+                    .iter()
+                    .map(|(path, _)| path)
+                    .filter(|path| exts.iter().any(|ext| path.ends_with(&format!(".{ext}"))))
+                    .cloned()
+                    .collect()
+            }))
+        }
+        #[cfg(not(test))]
         pub fn read_file(path: &str) -> Result<String, String> {
             Ok(path.to_string())
         }
+        #[cfg(test)]
+        pub fn read_file(path: &str) -> Result<String, String> {
+            FILES.with(|files| {
+                files
+                    .borrow()
+                    .iter()
+                    .find(|(p, _)| p == path)
+                    .map(|(_, res)| res.clone())
+                    .unwrap_or_else(|| Ok(path.to_string()))
+            })
+        }
         pub fn write_file(_path: &str, _data: &str) -> Result<(), String> {
             Ok(())
         }
@@ -90,59 +156,58 @@ impl wit::core::Core for Lib {
         typegraph::finalize()
     }
 
+    fn typegraph_to_graphql_sdl(typegraph: String) -> Result<String> {
+        conversion::graphql::to_sdl(&parse_typegraph(&typegraph)?)
+    }
+
+    fn typegraph_to_openapi(typegraph: String) -> Result<String> {
+        conversion::openapi::to_openapi(&parse_typegraph(&typegraph)?)
+    }
+
+    fn reset() -> Result<()> {
+        Store::reset()
+    }
+
+    fn typegraph_version() -> String {
+        typegraph::TYPEGRAPH_VERSION.to_string()
+    }
+
+    fn runtime_info() -> RuntimeInfo {
+        RuntimeInfo {
+            typegraph_version: typegraph::TYPEGRAPH_VERSION.to_string(),
+            core_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
     fn proxyb(data: TypeProxy) -> Result<CoreTypeId> {
         Ok(Store::register_type(|id| Type::Proxy(Proxy { id, data }.into()))?.into())
     }
 
     fn integerb(data: TypeInteger, base: TypeBase) -> Result<CoreTypeId> {
-        if let (Some(min), Some(max)) = (data.min, data.max) {
-            if min >= max {
-                return Err(errors::invalid_max_value());
-            }
-        }
-        if let (Some(min), Some(max)) = (data.exclusive_minimum, data.exclusive_maximum) {
-            if min >= max {
-                return Err(errors::invalid_max_value());
-            }
-        }
-        Ok(Store::register_type(|id| Type::Integer(Integer { id, base, data }.into()))?.into())
+        data.validate(&base)?;
+        Ok(Store::register_type(|id| Type::Integer(Integer::new(id, base, data).into()))?.into())
     }
 
     fn floatb(data: TypeFloat, base: TypeBase) -> Result<CoreTypeId> {
-        if let (Some(min), Some(max)) = (data.min, data.max) {
-            if min >= max {
-                return Err(errors::invalid_max_value());
-            }
-        }
-        if let (Some(min), Some(max)) = (data.exclusive_minimum, data.exclusive_maximum) {
-            if min >= max {
-                return Err(errors::invalid_max_value());
-            }
-        }
-        Ok(Store::register_type(|id| Type::Float(Float { id, base, data }.into()))?.into())
+        data.validate(&base)?;
+        Ok(Store::register_type(|id| Type::Float(Float::new(id, base, data).into()))?.into())
     }
 
     fn booleanb(base: TypeBase) -> Result<CoreTypeId> {
-        Ok(Store::register_type(|id| {
-            Type::Boolean(
-                Boolean {
-                    id,
-                    base,
-                    data: TypeBoolean,
-                }
+        TypeBoolean.validate(&base)?;
+        Ok(
+            Store::register_type(|id| Type::Boolean(Boolean::new(id, base, TypeBoolean).into()))?
                 .into(),
-            )
-        })?
-        .into())
+        )
+    }
+
+    fn jsonb(base: TypeBase) -> Result<CoreTypeId> {
+        Ok(Store::register_type(|id| Type::Json(Json::new(id, base, TypeJson).into()))?.into())
     }
 
     fn stringb(data: TypeString, base: TypeBase) -> Result<CoreTypeId> {
-        if let (Some(min), Some(max)) = (data.min, data.max) {
-            if min >= max {
-                return Err(errors::invalid_max_value());
-            }
-        }
-        Ok(Store::register_type(|id| Type::String(StringT { id, base, data }.into()))?.into())
+        data.validate(&base)?;
+        Ok(Store::register_type(|id| Type::String(StringT::new(id, base, data).into()))?.into())
     }
 
     fn fileb(data: TypeFile, base: TypeBase) -> Result<CoreTypeId> {
@@ -156,7 +221,7 @@ impl wit::core::Core for Lib {
                 name: Some(format!("_{}_file", id.0)),
                 ..base
             };
-            Type::File(File { id, base, data }.into())
+            Type::File(File::new(id, base, data).into())
         })?
         .into())
     }
@@ -179,12 +244,13 @@ impl wit::core::Core for Lib {
                 },
                 None => base,
             };
-            Type::Array(Array { id, base, data }.into())
+            Type::Array(Array::new(id, base, data).into())
         })?
         .into())
     }
 
     fn optionalb(data: TypeOptional, base: TypeBase) -> Result<CoreTypeId> {
+        data.validate()?;
         let inner_name = match base.name {
             Some(_) => None,
             None => TypeId(data.of).type_name()?,
@@ -197,43 +263,64 @@ impl wit::core::Core for Lib {
                 },
                 None => base,
             };
-            Type::Optional(Optional { id, base, data }.into())
+            Type::Optional(Optional::new(id, base, data).into())
         })?
         .into())
     }
 
+    fn scalarb(data: TypeScalar, base: TypeBase) -> Result<CoreTypeId> {
+        data.validate(&base)?;
+        Ok(Store::register_type(|id| Type::Scalar(Scalar::new(id, base, data).into()))?.into())
+    }
+
+    fn mapb(data: TypeMap, base: TypeBase) -> Result<CoreTypeId> {
+        data.validate()?;
+        Ok(Store::register_type(|id| Type::Map(Map::new(id, base, data).into()))?.into())
+    }
+
     fn unionb(data: TypeUnion, base: TypeBase) -> Result<CoreTypeId> {
-        Ok(Store::register_type(|id| Type::Union(Union { id, base, data }.into()))?.into())
+        data.validate()?;
+        Ok(Store::register_type(|id| Type::Union(Union::new(id, base, data).into()))?.into())
     }
 
     fn eitherb(data: TypeEither, base: TypeBase) -> Result<CoreTypeId> {
-        Ok(Store::register_type(|id| Type::Either(Either { id, base, data }.into()))?.into())
+        data.validate()?;
+        Ok(Store::register_type(|id| Type::Either(Either::new(id, base, data).into()))?.into())
     }
 
     fn structb(data: TypeStruct, base: TypeBase) -> Result<CoreTypeId> {
-        let mut prop_names = HashSet::new();
-        for (name, _) in data.props.iter() {
-            if !validate_name(name) {
-                return Err(errors::invalid_prop_key(name));
-            }
-            if prop_names.contains(name) {
-                return Err(errors::duplicate_key(name));
-            }
-            prop_names.insert(name.clone());
-        }
+        data.validate()?;
+        Ok(Store::register_type(|id| Type::Struct(Struct::new(id, base, data).into()))?.into())
+    }
 
-        Ok(Store::register_type(|id| Type::Struct(Struct { id, base, data }.into()))?.into())
+    fn structb_checked(data: TypeStruct, base: TypeBase) -> Result<CoreTypeId> {
+        data.validate_all()?;
+        Ok(Store::register_type(|id| Type::Struct(Struct::new(id, base, data).into()))?.into())
     }
 
-    fn funcb(data: TypeFunc) -> Result<CoreTypeId> {
-        let wrapper_type = TypeId(data.inp);
+    fn funcb(mut data: TypeFunc, base: TypeBase) -> Result<CoreTypeId> {
+        if data.rate_weight == Some(0) {
+            return Err(errors::invalid_rate_weight());
+        }
+        let mut wrapper_type = TypeId(data.inp);
         let attrs = wrapper_type.attrs()?;
         let concrete_type = attrs.concrete_type.as_type()?;
         if !matches!(concrete_type, Type::Struct(_)) {
-            return Err(errors::invalid_input_type(&wrapper_type.repr()?));
+            if !data.auto_wrap_input {
+                return Err(errors::invalid_input_type(&wrapper_type.repr()?));
+            }
+            let repr = wrapper_type.repr()?;
+            wrapper_type = t::struct_().prop("_arg", wrapper_type).build()?;
+            log!("note: the input {repr} of the function is wrapped in a struct as `_arg`");
+            data.inp = wrapper_type.into();
+        }
+        if let Some(path) = find_nested_func(wrapper_type, false)? {
+            return Err(errors::func_in_input(&path.join(".")));
         }
-        let base = TypeBase::default();
-        Ok(Store::register_type(|id| Type::Func(Func { id, base, data }.into()))?.into())
+        if let Some(path) = find_nested_func(data.out.into(), true)? {
+            return Err(errors::func_as_output(&path.join(".")));
+        }
+        Ok(Store::register_type(|id| Type::Func(Func::new(id, base, data).into()))?.into())
     }
 
     fn with_injection(data: TypeWithInjection) -> Result<CoreTypeId> {
@@ -255,6 +342,7 @@ impl wit::core::Core for Lib {
         let name = match &check {
             ContextCheck::Value(v) => format!("__ctx_{}_{}", key, v),
             ContextCheck::Pattern(p) => format!("__ctx_p_{}_{}", key, p),
+            ContextCheck::Present => format!("__ctx_present_{}", key),
         };
         let name = Regex::new("[^a-zA-Z0-9_]")
             .unwrap()
@@ -266,11 +354,22 @@ impl wit::core::Core for Lib {
                 format!("value === {}", serde_json::to_string(&val).unwrap())
             }
             ContextCheck::Pattern(pattern) => {
+                // JS-specific features are left to the JS engine
+                if let Some(feature) = js_only_feature(&pattern) {
+                    log!(
+                        "warning: cannot validate the pattern {:?}: {} is not supported",
+                        pattern,
+                        feature
+                    );
+                } else if Regex::new(&pattern).is_err() {
+                    return Err(errors::invalid_pattern(&pattern));
+                }
                 format!(
                     "new RegExp({}).test(value)",
                     serde_json::to_string(&pattern).unwrap()
                 )
             }
+            ContextCheck::Present => "value !== undefined && value !== null".to_string(),
         };
 
         let key = serde_json::to_string(&key).unwrap();
@@ -290,6 +389,7 @@ impl wit::core::Core for Lib {
             MaterializerDenoFunc {
                 code,
                 secrets: vec![],
+                timeout_sec: None,
             },
             wit::runtimes::Effect::None,
         )?;
@@ -301,6 +401,16 @@ impl wit::core::Core for Lib {
         .map(|id| (id, name))
     }
 
+    fn describe_policy(id: PolicyId) -> Result<PolicyDescription> {
+        let policy = Store::get_policy(id)?;
+        let mat = Store::get_materializer(policy.materializer)?;
+        Ok(PolicyDescription {
+            name: policy.name.clone(),
+            runtime: Store::get_runtime(mat.runtime_id)?.name().to_string(),
+            effect: conversion::runtimes::effect_name(&mat.effect).to_string(),
+        })
+    }
+
     fn rename_type(type_id: CoreTypeId, new_name: String) -> Result<CoreTypeId, String> {
         let typ = TypeId(type_id).as_type()?;
         match typ {
@@ -314,12 +424,15 @@ impl wit::core::Core for Lib {
                 injection: inner.data.injection.clone(),
             }),
             Type::Boolean(inner) => Ok(inner.rename(new_name)?.into()),
+            Type::Json(inner) => Ok(inner.rename(new_name)?.into()),
             Type::Integer(inner) => Ok(inner.rename(new_name)?.into()),
             Type::Float(inner) => Ok(inner.rename(new_name)?.into()),
             Type::String(inner) => Ok(inner.rename(new_name)?.into()),
             Type::File(inner) => Ok(inner.rename(new_name)?.into()),
             Type::Optional(inner) => Ok(inner.rename(new_name)?.into()),
             Type::Array(inner) => Ok(inner.rename(new_name)?.into()),
+            Type::Map(inner) => Ok(inner.rename(new_name)?.into()),
+            Type::Scalar(inner) => Ok(inner.rename(new_name)?.into()),
             Type::Union(inner) => Ok(inner.rename(new_name)?.into()),
             Type::Either(inner) => Ok(inner.rename(new_name)?.into()),
             Type::Struct(inner) => Ok(inner.rename(new_name)?.into()),
@@ -327,10 +440,65 @@ impl wit::core::Core for Lib {
         }
     }
 
+    fn duplicate_type(type_id: CoreTypeId) -> Result<CoreTypeId, String> {
+        let typ = TypeId(type_id).as_type()?;
+        match typ {
+            Type::Proxy(_) => Err("cannot duplicate proxy".to_string()),
+            Type::WithPolicy(inner) => Self::with_policy(TypePolicy {
+                tpe: Self::duplicate_type(inner.data.tpe)?,
+                chain: inner.data.chain.clone(),
+            }),
+            Type::WithInjection(inner) => Self::with_injection(TypeWithInjection {
+                tpe: Self::duplicate_type(inner.data.tpe)?,
+                injection: inner.data.injection.clone(),
+            }),
+            Type::Boolean(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            Type::Json(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            Type::Integer(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            Type::Float(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            Type::String(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            Type::File(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            Type::Optional(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            Type::Array(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            Type::Map(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            // scalars are referenced by name
+            Type::Scalar(_) => Err(errors::unnamed_scalar()),
+            Type::Union(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            Type::Either(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            Type::Struct(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+            Type::Func(inner) => Ok(inner.modified(|base, _| base.name = None)?.into()),
+        }
+    }
+
     fn get_type_repr(type_id: CoreTypeId) -> Result<String> {
         TypeId(type_id).repr()
     }
 
+    fn get_type_info(type_id: CoreTypeId) -> Result<TypeInfo> {
+        conversion::type_info::type_info(type_id.into())
+    }
+
+    fn convert_type(type_id: CoreTypeId) -> Result<String> {
+        let nodes = typegraph::convert_type(type_id.into())?;
+        serde_json::to_string(&nodes).map_err(|e| e.to_string())
+    }
+
+    fn list_types() -> Result<Vec<(CoreTypeId, String)>> {
+        Store::get_types()
+            .into_iter()
+            .map(|typ| {
+                let id = typ.get_id();
+                let repr = match typ {
+                    Type::Proxy(inner) if inner.data.resolve().is_none() => {
+                        format!("proxy({})", inner.data.name)
+                    }
+                    _ => id.repr()?,
+                };
+                Ok((id.into(), repr))
+            })
+            .collect()
+    }
+
     fn expose(
         fns: Vec<(String, CoreTypeId)>,
         default_policy: Option<Vec<PolicySpec>>,
@@ -338,8 +506,160 @@ impl wit::core::Core for Lib {
         typegraph::expose(
             fns.into_iter().map(|(k, ty)| (k, ty.into())).collect(),
             default_policy,
+            false,
+        )
+    }
+
+    fn expose_reserved(
+        fns: Vec<(String, CoreTypeId)>,
+        default_policy: Option<Vec<PolicySpec>>,
+    ) -> Result<(), String> {
+        typegraph::expose(
+            fns.into_iter().map(|(k, ty)| (k, ty.into())).collect(),
+            default_policy,
+            true,
+        )
+    }
+
+    fn expose_aliased(
+        fns: Vec<(String, String, CoreTypeId)>,
+        default_policy: Option<Vec<PolicySpec>>,
+    ) -> Result<(), String> {
+        typegraph::expose_aliased(
+            fns.into_iter()
+                .map(|(k, alias, ty)| (k, alias, ty.into()))
+                .collect(),
+            default_policy,
+            false,
         )
     }
+
+    fn expose_namespace(
+        path: Vec<String>,
+        fns: Vec<(String, CoreTypeId)>,
+        default_policy: Option<Vec<PolicySpec>>,
+    ) -> Result<(), String> {
+        let fns = fns
+            .into_iter()
+            .map(|(k, ty)| (k.clone(), k, ty.into()))
+            .collect();
+        typegraph::expose_at(&path, fns, default_policy, false, false)
+    }
+
+    fn expose_merged(
+        fns: Vec<(String, CoreTypeId)>,
+        default_policy: Option<Vec<PolicySpec>>,
+    ) -> Result<(), String> {
+        let fns = fns
+            .into_iter()
+            .map(|(k, ty)| (k.clone(), k, ty.into()))
+            .collect();
+        typegraph::expose_at(&[], fns, default_policy, false, true)
+    }
+}
+
+impl Lib {
+    /// Same as `finalize_typegraph`, without the serialization, for in-process hosts
+    #[allow(dead_code)]
+    pub fn finalize_typegraph_struct() -> Result<common::typegraph::Typegraph> {
+        typegraph::finalize_struct()
+    }
+
+    /// Same as `finalize_typegraph`, with an indented output that is easier to diff
+    #[allow(dead_code)]
+    pub fn finalize_typegraph_pretty() -> Result<String> {
+        let tg = typegraph::finalize_struct()?;
+        serde_json::to_string_pretty(&tg).map_err(|e| e.to_string())
+    }
+
+    /// Standalone JSON Schema (draft 2020-12) of the type
+    #[allow(dead_code)]
+    pub fn type_to_json_schema(type_id: TypeId) -> Result<String> {
+        conversion::json_schema::to_json_schema(type_id)
+    }
+
+    /// Changes between two serialized typegraphs, for breaking change detection
+    #[allow(dead_code)]
+    pub fn diff_typegraphs(old_json: &str, new_json: &str) -> Result<Vec<diff::Change>> {
+        diff::diff(&parse_typegraph(old_json)?, &parse_typegraph(new_json)?)
+    }
+
+    /// Builds a struct of scalar props, registering all the types at once
+    #[allow(dead_code)]
+    pub fn build_struct_batch(props: Vec<(String, TypeBuilderSpec)>) -> Result<TypeId> {
+        for (_, spec) in props.iter() {
+            match spec {
+                TypeBuilderSpec::Boolean(base) => TypeBoolean.validate(base)?,
+                TypeBuilderSpec::Integer(data, base) => data.validate(base)?,
+                TypeBuilderSpec::Float(data, base) => data.validate(base)?,
+                TypeBuilderSpec::String(data, base) => data.validate(base)?,
+            }
+        }
+        let data = TypeStruct {
+            props: props.iter().map(|(name, _)| (name.clone(), 0)).collect(),
+            ..Default::default()
+        };
+        data.validate()?;
+
+        Store::register_types(move |s| {
+            let mut data = data;
+            for (i, (_, spec)) in props.into_iter().enumerate() {
+                let id = match spec {
+                    TypeBuilderSpec::Boolean(base) => {
+                        s.add_type(|id| Type::Boolean(Boolean::new(id, base, TypeBoolean).into()))?
+                    }
+                    TypeBuilderSpec::Integer(data, base) => {
+                        s.add_type(|id| Type::Integer(Integer::new(id, base, data).into()))?
+                    }
+                    TypeBuilderSpec::Float(data, base) => {
+                        s.add_type(|id| Type::Float(Float::new(id, base, data).into()))?
+                    }
+                    TypeBuilderSpec::String(data, base) => {
+                        s.add_type(|id| Type::String(StringT::new(id, base, data).into()))?
+                    }
+                };
+                data.props[i].1 = id.into();
+            }
+            let base = TypeBase::default();
+            s.add_type(|id| Type::Struct(Struct::new(id, base, data).into()))
+        })
+    }
+}
+
+fn parse_typegraph(json: &str) -> Result<common::typegraph::Typegraph> {
+    serde_json::from_str(json).map_err(|e| e.to_string())
+}
+
+/// Look-around and backreferences are valid in JS but not for the `regex` crate
+fn js_only_feature(pattern: &str) -> Option<&'static str> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut in_class = false;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => {
+                let named = chars.get(i + 1) == Some(&'k') && chars.get(i + 2) == Some(&'<');
+                if !in_class && (named || matches!(chars.get(i + 1), Some('1'..='9'))) {
+                    return Some("backreference");
+                }
+                i += 1;
+            }
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '(' if !in_class => {
+                let group: String = chars[i + 1..].iter().take(3).collect();
+                if ["?=", "?!", "?<=", "?<!"]
+                    .iter()
+                    .any(|p| group.starts_with(p))
+                {
+                    return Some("look-around");
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
 }
 
 #[macro_export]
@@ -353,35 +673,14 @@ macro_rules! log {
 mod tests {
     use crate::errors;
     use crate::global_store::Store;
-    use crate::t::{self, TypeBuilder};
-    use crate::test_utils::setup;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::types::{Type, TypeId};
     use crate::wit::core::Core;
-    use crate::wit::core::Cors;
+    use crate::wit::core::{Policy, PolicySpec, TypePolicy};
     use crate::wit::runtimes::{Effect, MaterializerDenoFunc, Runtimes};
     use crate::Lib;
-    use crate::TypegraphInitParams;
-
-    impl Default for TypegraphInitParams {
-        fn default() -> Self {
-            Self {
-                name: "".to_string(),
-                dynamic: None,
-                folder: None,
-                path: ".".to_string(),
-                prefix: None,
-                cors: Cors {
-                    allow_origin: vec![],
-                    allow_headers: vec![],
-                    expose_headers: vec![],
-                    allow_methods: vec![],
-                    allow_credentials: false,
-                    max_age_sec: None,
-                },
-                auths: vec![],
-                rate: None,
-            }
-        }
-    }
+    use common::typegraph::TypeNode;
 
     #[test]
     fn test_integer_invalid_max() {
@@ -423,8 +722,7 @@ mod tests {
 
     #[test]
     fn test_invalid_input_type() -> Result<(), String> {
-        let mat =
-            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => 12"), Effect::None)?;
+        let mat = dummy_mat()?;
         let inp = t::integer().build()?;
         let res = t::func(inp, t::integer().build()?, mat);
 
@@ -434,7 +732,7 @@ mod tests {
 
     #[test]
     fn test_nested_typegraph_context() -> Result<(), String> {
-        Store::reset();
+        Store::reset()?;
         setup(Some("test-1"))?;
         assert_eq!(
             crate::test_utils::setup(Some("test-2")),
@@ -446,7 +744,7 @@ mod tests {
 
     #[test]
     fn test_no_active_context() -> Result<(), String> {
-        Store::reset();
+        Store::reset()?;
         assert_eq!(
             Lib::expose(vec![], None),
             Err(errors::expected_typegraph_context())
@@ -462,10 +760,9 @@ mod tests {
 
     #[test]
     fn test_expose_invalid_type() -> Result<(), String> {
-        Store::reset();
-        setup(None)?;
+        Store::reset()?;
         let tpe = t::integer().build()?;
-        let res = Lib::expose(vec![("one".to_string(), tpe.into())], None);
+        let res = expose_one("one", tpe);
 
         assert_eq!(res, Err(errors::invalid_export_type("one", &tpe.repr()?,)));
 
@@ -474,19 +771,11 @@ mod tests {
 
     #[test]
     fn test_expose_invalid_name() -> Result<(), String> {
-        setup(None)?;
-
-        let mat = Lib::register_deno_func(
-            MaterializerDenoFunc::with_code("() => 12"),
-            Effect::default(),
-        )?;
+        let mat = dummy_mat()?;
 
-        let res = Lib::expose(
-            vec![(
-                "".to_string(),
-                t::func(t::struct_().build()?, t::integer().build()?, mat)?.into(),
-            )],
-            None,
+        let res = expose_one(
+            "",
+            t::func(t::struct_().build()?, t::integer().build()?, mat)?,
         );
         assert_eq!(res, Err(errors::invalid_export_name("")));
 
@@ -506,8 +795,7 @@ mod tests {
     fn test_expose_duplicate() -> Result<(), String> {
         setup(None)?;
 
-        let mat =
-            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => 12"), Effect::None)?;
+        let mat = dummy_mat()?;
 
         let res = Lib::expose(
             vec![
@@ -529,7 +817,7 @@ mod tests {
 
     #[test]
     fn test_successful_serialization() -> Result<(), String> {
-        Store::reset();
+        Store::reset()?;
         let a = t::integer().build()?;
         let b = t::integer().min(12).max(44).build()?;
         // -- optional(array(float))
@@ -544,12 +832,238 @@ mod tests {
             .prop("three", c)
             .build()?;
 
-        setup(None)?;
-        let mat =
-            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => 12"), Effect::None)?;
-        Lib::expose(vec![("one".to_string(), t::func(s, b, mat)?.into())], None)?;
+        let mat = dummy_mat()?;
+        expose_one("one", t::func(s, b, mat)?)?;
         let typegraph = Lib::finalize_typegraph()?;
         insta::assert_snapshot!(typegraph);
         Ok(())
     }
+
+    #[test]
+    fn test_list_types() -> Result<(), String> {
+        Store::reset()?;
+        let int = t::integer().build()?;
+        let s = t::struct_().prop("a", int).named("A").build()?;
+        let b = t::proxy("B").build()?;
+        let a = t::proxy("A").build()?;
+
+        assert_eq!(
+            Lib::list_types()?,
+            vec![
+                (int.into(), int.repr()?),
+                (s.into(), s.repr()?),
+                (b.into(), "proxy(B)".to_string()),
+                (a.into(), a.repr()?),
+            ]
+        );
+        assert_eq!(s.repr()?, "struct(#1, [a] => #0)");
+        assert_eq!(a.repr()?, "struct(#3, proxy_name='A')");
+        Ok(())
+    }
+
+    #[test]
+    fn test_graphql_names() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let int = t::integer().build()?;
+        let res = t::struct_().prop("1st", int).build();
+        assert_eq!(res, Err(errors::invalid_prop_key("1st")));
+        let res = t::struct_().prop("prénom", int).build();
+        assert_eq!(res, Err(errors::invalid_prop_key("prénom")));
+        let inp = t::struct_().prop("_private", int).build()?;
+
+        let res = expose_one("1st", t::func(inp, int, mat)?);
+        assert_eq!(res, Err(errors::invalid_export_name("1st")));
+        Lib::expose(
+            vec![("_first".to_string(), t::func(inp, int, mat)?.into())],
+            None,
+        )
+    }
+
+    #[test]
+    fn test_reserved_export_name() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let inp = t::struct_().build()?;
+        let out = t::integer().build()?;
+        let res = expose_one("__secret", t::func(inp, out, mat)?);
+        assert_eq!(res, Err(errors::reserved_export_name("__secret")));
+        Lib::expose_reserved(
+            vec![("__secret".to_string(), t::func(inp, out, mat)?.into())],
+            None,
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_alias() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let inp = t::struct_().propx("id", t::integer())?.build()?;
+        let out = t::string().build()?;
+        setup(None)?;
+        Lib::expose_aliased(
+            vec![(
+                "getUserById".to_string(),
+                "user".to_string(),
+                t::func(inp, out, mat)?.into(),
+            )],
+            None,
+        )?;
+        let res = Lib::expose_aliased(
+            vec![(
+                "findUser".to_string(),
+                "user".to_string(),
+                t::func(inp, out, mat)?.into(),
+            )],
+            None,
+        );
+        assert_eq!(res, Err(errors::duplicate_export_name("user")));
+
+        let typegraph = Lib::finalize_typegraph_struct()?;
+        let TypeNode::Object { data, .. } = &typegraph.types[0] else {
+            panic!("expected the root to be an object");
+        };
+        assert_eq!(data.properties.keys().collect::<Vec<_>>(), vec!["user"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_namespace_required() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let inp = t::struct_().build()?;
+        let out = t::integer().build()?;
+        setup(None)?;
+        let path = vec!["admin".to_string(), "users".to_string()];
+        Lib::expose_namespace(
+            path.clone(),
+            vec![("count".to_string(), t::func(inp, out, mat)?.into())],
+            None,
+        )?;
+        Lib::expose_namespace(
+            path,
+            vec![("list".to_string(), t::func(inp, out, mat)?.into())],
+            None,
+        )?;
+
+        let typegraph = Lib::finalize_typegraph_struct()?;
+        let object = |idx: u32| match &typegraph.types[idx as usize] {
+            TypeNode::Object { data, .. } => data.clone(),
+            _ => panic!("expected an object"),
+        };
+        let root = object(0);
+        assert_eq!(root.required, vec!["admin"]);
+        let admin = object(root.properties["admin"]);
+        assert_eq!(admin.required, vec!["users"]);
+        let users = object(admin.properties["users"]);
+        assert_eq!(users.required, vec!["count", "list"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_type() -> Result<(), String> {
+        Store::reset()?;
+        let name = t::string().min(1).max(10).named("Name").build()?;
+        let copy: TypeId = Lib::duplicate_type(name.into())?.into();
+        assert_ne!(copy, name);
+        assert_eq!(copy.type_name()?, None);
+        assert_eq!(copy.repr()?, format!("string(#{}, min=1, max=10)", copy.0));
+
+        let Type::String(inner) = copy.as_type()? else {
+            panic!("expected a string");
+        };
+        let longer = inner.modified(|_, data| data.max = Some(20))?;
+        assert_eq!(
+            longer.repr()?,
+            format!("string(#{}, min=1, max=20)", longer.0)
+        );
+        assert_eq!(name.repr()?, format!("string(#{}, min=1, max=10)", name.0));
+
+        let user = t::struct_().prop("name", name).named("User").build()?;
+        let user_copy: TypeId = Lib::duplicate_type(user.into())?.into();
+        assert_eq!(
+            user_copy.as_struct()?.iter_props().collect::<Vec<_>>(),
+            vec![("name", name)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_default_policy() -> Result<(), String> {
+        use crate::wit::core::PolicyId;
+        use common::typegraph::PolicyIndices;
+
+        Store::reset()?;
+        let policy = |name: &str| -> Result<PolicyId, String> {
+            Lib::register_policy(Policy {
+                name: name.to_string(),
+                materializer: Lib::register_deno_func(
+                    MaterializerDenoFunc::with_code("() => true"),
+                    Effect::None,
+                )?,
+            })
+        };
+        let explicit = policy("explicit")?;
+        let default = policy("default")?;
+        let mat = dummy_mat()?;
+        let f = t::func(t::struct_().build()?, t::integer().build()?, mat)?;
+        let f = Lib::with_policy(TypePolicy {
+            tpe: f.into(),
+            chain: vec![PolicySpec::Simple(explicit)],
+        })?;
+
+        setup(None)?;
+        Lib::expose_merged(
+            vec![("f".to_string(), f)],
+            Some(vec![PolicySpec::Simple(default)]),
+        )?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        let TypeNode::Object { data, .. } = &tg.types[0] else {
+            panic!("root is not an object");
+        };
+        let names = tg.types[data.properties["f"] as usize]
+            .base()
+            .policies
+            .iter()
+            .map(|p| match p {
+                PolicyIndices::Policy(idx) => tg.policies[*idx as usize].name.as_str(),
+                _ => panic!("unexpected policy"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["explicit", "default"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_type() -> Result<(), String> {
+        Store::reset()?;
+        let user = t::struct_()
+            .propx("id", t::integer())?
+            .propx("tags", t::array(t::string().build()?))?
+            .named("User")
+            .build()?;
+
+        // no active typegraph
+        let nodes: serde_json::Value =
+            serde_json::from_str(&Lib::convert_type(user.into())?).unwrap();
+        assert_eq!(nodes[0]["title"], "User");
+        assert_eq!(
+            nodes[0]["properties"],
+            serde_json::json!({ "id": 1, "tags": 2 })
+        );
+        assert_eq!(nodes[2]["type"], "array");
+        assert_eq!(nodes.as_array().unwrap().len(), 4);
+
+        // the active typegraph is left unchanged
+        setup(None)?;
+        Lib::convert_type(user.into())?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        assert_eq!(tg.types.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_typegraph_version() {
+        assert_eq!(Lib::typegraph_version(), "0.0.2");
+        let info = Lib::runtime_info();
+        assert_eq!(info.typegraph_version, "0.0.2");
+        assert_eq!(info.core_version, env!("CARGO_PKG_VERSION"));
+    }
 }
@@ -1,10 +1,10 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
-use std::rc::Rc;
+use crate::sync::Rc;
 
 use crate::conversion::runtimes::MaterializerConverter;
-use crate::errors::Result;
+use crate::errors::{self, Result};
 use crate::global_store::Store;
 use crate::typegraph::TypegraphContext;
 use crate::wit::aws::{
@@ -52,6 +52,7 @@ impl Materializer {
     fn s3(runtime_id: RuntimeId, data: S3Materializer, effect: WitEffect) -> Self {
         Self {
             runtime_id,
+            idempotency_key: None,
             data: Rc::new(data).into(),
             effect,
         }
@@ -120,12 +121,20 @@ impl MaterializerConverter for S3Materializer {
     }
 }
 
+fn validate_expiry(bucket: &str, expiry_secs: Option<u32>) -> Result<()> {
+    match expiry_secs {
+        Some(0) => Err(errors::invalid_expiry_secs(bucket)),
+        _ => Ok(()),
+    }
+}
+
 impl Aws for crate::Lib {
     fn register_s3_runtime(data: S3RuntimeData) -> Result<RuntimeId> {
         Ok(Store::register_runtime(Runtime::S3(data.into())))
     }
 
     fn s3_presign_get(runtime: RuntimeId, params: S3PresignGetParams) -> Result<MaterializerId> {
+        validate_expiry(&params.bucket, params.expiry_secs)?;
         let mat = Materializer::s3(
             runtime,
             S3Materializer::PresignGet(params.into()),
@@ -135,6 +144,7 @@ impl Aws for crate::Lib {
     }
 
     fn s3_presign_put(runtime: RuntimeId, params: S3PresignPutParams) -> Result<MaterializerId> {
+        validate_expiry(&params.bucket, params.expiry_secs)?;
         let mat = Materializer::s3(
             runtime,
             S3Materializer::PresignPut(params.into()),
@@ -170,3 +180,59 @@ impl Aws for crate::Lib {
         Ok(Store::register_materializer(mat))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::Lib;
+
+    #[test]
+    fn test_s3_presign_put() -> Result<(), String> {
+        use crate::wit::aws::{Aws, S3PresignPutParams, S3RuntimeData};
+
+        Store::reset()?;
+        let s3 = Lib::register_s3_runtime(S3RuntimeData {
+            host_secret: "HOST".to_string(),
+            region_secret: "REGION".to_string(),
+            access_key_secret: "ACCESS_KEY".to_string(),
+            secret_key_secret: "SECRET_KEY".to_string(),
+            path_style_secret: "PATH_STYLE".to_string(),
+        })?;
+        let presign_put = |expiry_secs| {
+            Lib::s3_presign_put(
+                s3,
+                S3PresignPutParams {
+                    bucket: "avatars".to_string(),
+                    expiry_secs,
+                    content_type: Some("image/png".to_string()),
+                },
+            )
+        };
+        assert_eq!(
+            presign_put(Some(0)),
+            Err(errors::invalid_expiry_secs("avatars"))
+        );
+        let mat = presign_put(Some(300))?;
+
+        expose_one(
+            "signAvatarUpload",
+            t::func(
+                t::struct_().propx("path", t::string())?.build()?,
+                t::string().build()?,
+                mat,
+            )?,
+        )?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        assert_eq!(tg.materializers[0].name, "presign_put");
+        assert_eq!(tg.materializers[0].data["bucket"], "avatars");
+        assert_eq!(tg.materializers[0].data["expiry_secs"], 300);
+        assert_eq!(
+            tg.meta.secrets,
+            vec!["HOST", "REGION", "ACCESS_KEY", "SECRET_KEY"]
+        );
+        Ok(())
+    }
+}
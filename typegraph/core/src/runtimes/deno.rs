@@ -28,3 +28,83 @@ pub enum DenoMaterializer {
     Module(MaterializerDenoModule),
     Import(MaterializerDenoImport),
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::core::Core;
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+
+    #[test]
+    fn test_deno_func_timeout() -> Result<(), String> {
+        Store::reset()?;
+        assert_eq!(
+            Lib::register_deno_func(
+                MaterializerDenoFunc::with_code("() => 12").timeout(0),
+                Effect::None
+            ),
+            Err(errors::invalid_timeout())
+        );
+
+        let mat = Lib::register_deno_func(
+            MaterializerDenoFunc::with_code("() => 12").timeout(30),
+            Effect::None,
+        )?;
+        let func = t::func(t::struct_().build()?, t::integer().build()?, mat)?;
+        expose_one("slow", func)?;
+        let typegraph: serde_json::Value =
+            serde_json::from_str(&Lib::finalize_typegraph()?).map_err(|e| e.to_string())?;
+        assert_eq!(
+            typegraph["materializers"][0]["data"]["timeout_sec"],
+            serde_json::json!(30)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_idempotency_key() -> Result<(), String> {
+        Store::reset()?;
+        let create = Lib::register_deno_func(
+            MaterializerDenoFunc::with_code("({ input }) => input"),
+            Effect::Create(false),
+        )?;
+        let read =
+            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => []"), Effect::None)?;
+
+        assert_eq!(
+            Lib::with_idempotency_key(read, "input.requestId".to_string()),
+            Err(errors::idempotency_on_read("input.requestId"))
+        );
+        assert_eq!(
+            Lib::with_idempotency_key(create, "input..requestId".to_string()),
+            Err(errors::invalid_idempotency_key("input..requestId"))
+        );
+        let create_once = Lib::with_idempotency_key(create, "input.requestId".to_string())?;
+
+        let inp = t::struct_()
+            .prop(
+                "input",
+                t::struct_()
+                    .propx("requestId", t::string())?
+                    .propx("amount", t::integer())?
+                    .build()?,
+            )
+            .build()?;
+        expose_one(
+            "createPayment",
+            t::func(inp, t::boolean().build()?, create_once)?,
+        )?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        assert_eq!(
+            tg.materializers[0].effect.idempotency_key.as_deref(),
+            Some("input.requestId")
+        );
+        // the original materializer is left unchanged
+        assert!(Store::get_materializer(create)?.idempotency_key.is_none());
+        Ok(())
+    }
+}
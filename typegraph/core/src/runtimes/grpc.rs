@@ -0,0 +1,73 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::wit::runtimes as wit;
+
+#[derive(Debug)]
+pub enum GrpcMaterializer {
+    Call(wit::MaterializerGrpc),
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+
+    #[test]
+    fn test_grpc_call() -> Result<(), String> {
+        use crate::wit::runtimes::{
+            BaseMaterializer, GrpcRuntimeData, MaterializerGrpc, RuntimeId,
+        };
+
+        Store::reset()?;
+        let grpc = Lib::register_grpc_runtime(GrpcRuntimeData {
+            proto_file: "proto/helloworld.proto".to_string(),
+            endpoint: "tcp://localhost:4770".to_string(),
+        })?;
+        let grpc_call = |runtime: RuntimeId, service: &str, method: &str| {
+            Lib::grpc_call(
+                BaseMaterializer {
+                    runtime,
+                    effect: Effect::None,
+                },
+                MaterializerGrpc {
+                    service: service.to_string(),
+                    method: method.to_string(),
+                },
+            )
+        };
+        assert_eq!(
+            grpc_call(grpc, "helloworld.Greeter", ""),
+            Err(errors::incomplete_grpc_method("helloworld.Greeter", ""))
+        );
+        assert_eq!(
+            grpc_call(Lib::get_deno_runtime(), "helloworld.Greeter", "SayHello"),
+            Err(errors::invalid_runtime_type("grpc", "deno"))
+        );
+        let say_hello = grpc_call(grpc, "helloworld.Greeter", "SayHello")?;
+
+        expose_one(
+            "greet",
+            t::func(
+                t::struct_().propx("name", t::string())?.build()?,
+                t::struct_().propx("message", t::string())?.build()?,
+                say_hello,
+            )?,
+        )?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        assert_eq!(
+            tg.deps,
+            vec![std::path::PathBuf::from("proto/helloworld.proto")]
+        );
+        let converted = serde_json::json!({
+            "materializers": tg.materializers,
+            "runtimes": tg.runtimes,
+        });
+        insta::assert_snapshot!(serde_json::to_string_pretty(&converted).unwrap());
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use crate::wit::runtimes as wit;
+
+#[derive(Debug)]
+pub enum KafkaMaterializer {
+    Publish(wit::MaterializerKafkaPublish),
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+
+    #[test]
+    fn test_kafka_publish() -> Result<(), String> {
+        use crate::wit::runtimes::{KafkaRuntimeData, MaterializerKafkaPublish};
+
+        Store::reset()?;
+        let kafka = Lib::register_kafka_runtime(KafkaRuntimeData {
+            broker_secret: "KAFKA_BROKER".to_string(),
+        })?;
+        let publish = |runtime, topic: &str| {
+            Lib::kafka_publish(
+                runtime,
+                MaterializerKafkaPublish {
+                    topic: topic.to_string(),
+                    key_path: Some(vec!["user".to_string(), "id".to_string()]),
+                },
+            )
+        };
+        assert_eq!(publish(kafka, ""), Err(errors::empty_kafka_topic()));
+        assert_eq!(
+            publish(Lib::get_deno_runtime(), "user.created"),
+            Err(errors::invalid_runtime_type("kafka", "deno"))
+        );
+        let user_created = publish(kafka, "user.created")?;
+
+        let user = t::struct_()
+            .propx("id", t::string())?
+            .propx("email", t::string())?
+            .build()?;
+        expose_one(
+            "userCreated",
+            t::func(
+                t::struct_().prop("user", user).build()?,
+                t::boolean().build()?,
+                user_created,
+            )?,
+        )?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        let mat = &tg.materializers[0];
+        assert_eq!(mat.name, "publish");
+        assert_eq!(mat.data["topic"], "user.created");
+        assert_eq!(mat.data["key_path"], serde_json::json!(["user", "id"]));
+        assert_eq!(tg.meta.secrets, vec!["KAFKA_BROKER"]);
+        Ok(())
+    }
+}
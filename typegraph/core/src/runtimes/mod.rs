@@ -4,6 +4,8 @@
 pub mod aws;
 pub mod deno;
 pub mod graphql;
+pub mod grpc;
+pub mod kafka;
 pub mod prisma;
 pub mod python;
 pub mod random;
@@ -12,9 +14,10 @@ pub mod typegate;
 pub mod typegraph;
 pub mod wasi;
 
-use std::rc::Rc;
+use crate::sync::Rc;
 
 use crate::conversion::runtimes::MaterializerConverter;
+use crate::errors;
 use crate::global_store::Store;
 use crate::runtimes::prisma::migration::{
     prisma_apply, prisma_create, prisma_deploy, prisma_diff, prisma_reset,
@@ -26,9 +29,10 @@ use crate::validation::types::validate_value;
 use crate::wit::aws::S3RuntimeData;
 use crate::wit::core::{FuncParams, MaterializerId, RuntimeId, TypeId as CoreTypeId};
 use crate::wit::runtimes::{
-    self as wit, BaseMaterializer, Error as TgError, GraphqlRuntimeData, HttpRuntimeData,
-    MaterializerHttpRequest, PrismaLinkData, PrismaMigrationOperation, PrismaRuntimeData,
-    RandomRuntimeData, TemporalOperationData, TemporalRuntimeData,
+    self as wit, BaseMaterializer, Error as TgError, GraphqlRuntimeData, GrpcRuntimeData,
+    HttpRuntimeData, KafkaRuntimeData, MaterializerHttpRequest, PrismaLinkData,
+    PrismaMigrationOperation, PrismaRuntimeData, RandomRuntimeData, TemporalOperationData,
+    TemporalRuntimeData,
 };
 use crate::{typegraph::TypegraphContext, wit::runtimes::Effect as WitEffect};
 use enum_dispatch::enum_dispatch;
@@ -36,6 +40,8 @@ use enum_dispatch::enum_dispatch;
 use self::aws::S3Materializer;
 pub use self::deno::{DenoMaterializer, MaterializerDenoImport, MaterializerDenoModule};
 pub use self::graphql::GraphqlMaterializer;
+pub use self::grpc::GrpcMaterializer;
+pub use self::kafka::KafkaMaterializer;
 use self::prisma::relationship::prisma_link;
 use self::prisma::type_generation::replace_variables_to_indices;
 use self::prisma::{PrismaMaterializer, PrismaRuntimeContext};
@@ -52,7 +58,9 @@ type Result<T, E = TgError> = std::result::Result<T, E>;
 pub enum Runtime {
     Deno,
     Graphql(Rc<GraphqlRuntimeData>),
+    Grpc(Rc<GrpcRuntimeData>),
     Http(Rc<HttpRuntimeData>),
+    Kafka(Rc<KafkaRuntimeData>),
     Python,
     Random(Rc<RandomRuntimeData>),
     WasmEdge,
@@ -64,10 +72,45 @@ pub enum Runtime {
     S3(Rc<S3RuntimeData>),
 }
 
+impl Runtime {
+    /// Name of the runtime in the typegraph
+    pub fn name(&self) -> &'static str {
+        match self {
+            Runtime::Deno => "deno",
+            Runtime::Graphql(_) => "graphql",
+            Runtime::Grpc(_) => "grpc",
+            Runtime::Http(_) => "http",
+            Runtime::Kafka(_) => "kafka",
+            Runtime::Python => "python_wasi",
+            Runtime::Random(_) => "random",
+            Runtime::WasmEdge => "wasmedge",
+            Runtime::Prisma(..) => "prisma",
+            Runtime::PrismaMigration => "prisma_migration",
+            Runtime::Temporal(_) => "temporal",
+            Runtime::Typegate => "typegate",
+            Runtime::Typegraph => "typegraph",
+            Runtime::S3(_) => "s3",
+        }
+    }
+
+    /// Whether the materializers of the runtime may have the effect
+    pub fn supports_effect(&self, effect: &wit::Effect) -> bool {
+        match self {
+            // generated values have no side effect
+            Runtime::Random(_) => matches!(effect, wit::Effect::None),
+            // only appends messages to a topic
+            Runtime::Kafka(_) => matches!(effect, wit::Effect::Create(_)),
+            _ => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Materializer {
     pub runtime_id: RuntimeId,
     pub effect: wit::Effect,
+    /// dotted path of the key identifying the retries of a mutation
+    pub idempotency_key: Option<String>,
     pub data: MaterializerData,
 }
 
@@ -75,6 +118,7 @@ impl Materializer {
     fn deno(data: DenoMaterializer, effect: wit::Effect) -> Self {
         Self {
             runtime_id: Store::get_deno_runtime(),
+            idempotency_key: None,
             effect,
             data: Rc::new(data).into(),
         }
@@ -83,6 +127,16 @@ impl Materializer {
     fn graphql(runtime_id: RuntimeId, data: GraphqlMaterializer, effect: wit::Effect) -> Self {
         Self {
             runtime_id,
+            idempotency_key: None,
+            effect,
+            data: Rc::new(data).into(),
+        }
+    }
+
+    fn grpc(runtime_id: RuntimeId, data: GrpcMaterializer, effect: wit::Effect) -> Self {
+        Self {
+            runtime_id,
+            idempotency_key: None,
             effect,
             data: Rc::new(data).into(),
         }
@@ -91,6 +145,16 @@ impl Materializer {
     fn http(runtime_id: RuntimeId, data: MaterializerHttpRequest, effect: wit::Effect) -> Self {
         Self {
             runtime_id,
+            idempotency_key: None,
+            effect,
+            data: Rc::new(data).into(),
+        }
+    }
+
+    fn kafka(runtime_id: RuntimeId, data: KafkaMaterializer, effect: wit::Effect) -> Self {
+        Self {
+            runtime_id,
+            idempotency_key: None,
             effect,
             data: Rc::new(data).into(),
         }
@@ -99,6 +163,7 @@ impl Materializer {
     fn python(runtime_id: RuntimeId, data: PythonMaterializer, effect: wit::Effect) -> Self {
         Self {
             runtime_id,
+            idempotency_key: None,
             effect,
             data: Rc::new(data).into(),
         }
@@ -107,6 +172,7 @@ impl Materializer {
     fn random(runtime_id: RuntimeId, data: RandomMaterializer, effect: wit::Effect) -> Self {
         Self {
             runtime_id,
+            idempotency_key: None,
             effect,
             data: Rc::new(data).into(),
         }
@@ -115,6 +181,7 @@ impl Materializer {
     fn wasi(runtime_id: RuntimeId, data: WasiMaterializer, effect: wit::Effect) -> Self {
         Self {
             runtime_id,
+            idempotency_key: None,
             effect,
             data: Rc::new(data).into(),
         }
@@ -123,6 +190,7 @@ impl Materializer {
     fn prisma(runtime_id: RuntimeId, data: PrismaMaterializer, effect: wit::Effect) -> Self {
         Self {
             runtime_id,
+            idempotency_key: None,
             effect,
             data: Rc::new(data).into(),
         }
@@ -135,6 +203,7 @@ impl Materializer {
     ) -> Self {
         Self {
             runtime_id,
+            idempotency_key: None,
             effect,
             data: data.into(),
         }
@@ -143,6 +212,7 @@ impl Materializer {
     fn temporal(runtime_id: RuntimeId, data: TemporalMaterializer, effect: wit::Effect) -> Self {
         Self {
             runtime_id,
+            idempotency_key: None,
             effect,
             data: Rc::new(data).into(),
         }
@@ -151,6 +221,7 @@ impl Materializer {
     fn typegate(runtime_id: RuntimeId, data: TypegateOperation, effect: wit::Effect) -> Self {
         Self {
             runtime_id,
+            idempotency_key: None,
             effect,
             data: data.into(),
         }
@@ -159,6 +230,7 @@ impl Materializer {
     fn typegraph(runtime_id: RuntimeId, data: TypegraphOperation, effect: wit::Effect) -> Self {
         Self {
             runtime_id,
+            idempotency_key: None,
             effect,
             data: data.into(),
         }
@@ -170,7 +242,9 @@ impl Materializer {
 pub enum MaterializerData {
     Deno(Rc<DenoMaterializer>),
     GraphQL(Rc<GraphqlMaterializer>),
+    Grpc(Rc<GrpcMaterializer>),
     Http(Rc<MaterializerHttpRequest>),
+    Kafka(Rc<KafkaMaterializer>),
     Python(Rc<PythonMaterializer>),
     Random(Rc<RandomMaterializer>),
     WasmEdge(Rc<WasiMaterializer>),
@@ -213,11 +287,26 @@ impl wit::Runtimes for crate::Lib {
         Store::get_deno_runtime()
     }
 
+    fn with_idempotency_key(mat: wit::MaterializerId, path: String) -> Result<wit::MaterializerId> {
+        let mut mat = Store::get_materializer(mat)?;
+        if matches!(mat.effect, WitEffect::None) {
+            return Err(errors::idempotency_on_read(&path));
+        }
+        if path.split('.').any(|chunk| chunk.is_empty()) {
+            return Err(errors::invalid_idempotency_key(&path));
+        }
+        mat.idempotency_key = Some(path);
+        Ok(Store::register_materializer(mat))
+    }
+
     fn register_deno_func(
         data: wit::MaterializerDenoFunc,
         effect: wit::Effect,
     ) -> Result<wit::MaterializerId> {
         // TODO: check code is valid function?
+        if data.timeout_sec == Some(0) {
+            return Err(errors::invalid_timeout());
+        }
         let mat = Materializer::deno(DenoMaterializer::Inline(data), effect);
         Ok(Store::register_materializer(mat))
     }
@@ -354,10 +443,55 @@ impl wit::Runtimes for crate::Lib {
         base: wit::BaseMaterializer,
         data: wit::MaterializerWasi,
     ) -> Result<wit::MaterializerId, wit::Error> {
+        if data.func_name.is_empty() {
+            return Err(errors::empty_wasi_func_name(&data.module));
+        }
         let mat = Materializer::wasi(base.runtime, WasiMaterializer::Module(data), base.effect);
         Ok(Store::register_materializer(mat))
     }
 
+    fn register_grpc_runtime(data: wit::GrpcRuntimeData) -> Result<wit::RuntimeId, wit::Error> {
+        Ok(Store::register_runtime(Runtime::Grpc(data.into())))
+    }
+
+    fn grpc_call(
+        base: wit::BaseMaterializer,
+        data: wit::MaterializerGrpc,
+    ) -> Result<wit::MaterializerId, wit::Error> {
+        let runtime = Store::get_runtime(base.runtime)?;
+        if !matches!(runtime, Runtime::Grpc(_)) {
+            return Err(errors::invalid_runtime_type("grpc", runtime.name()));
+        }
+        if data.service.is_empty() || data.method.is_empty() {
+            return Err(errors::incomplete_grpc_method(&data.service, &data.method));
+        }
+        let mat = Materializer::grpc(base.runtime, GrpcMaterializer::Call(data), base.effect);
+        Ok(Store::register_materializer(mat))
+    }
+
+    fn register_kafka_runtime(data: wit::KafkaRuntimeData) -> Result<wit::RuntimeId, wit::Error> {
+        Ok(Store::register_runtime(Runtime::Kafka(data.into())))
+    }
+
+    fn kafka_publish(
+        runtime: wit::RuntimeId,
+        data: wit::MaterializerKafkaPublish,
+    ) -> Result<wit::MaterializerId, wit::Error> {
+        let kafka = Store::get_runtime(runtime)?;
+        if !matches!(kafka, Runtime::Kafka(_)) {
+            return Err(errors::invalid_runtime_type("kafka", kafka.name()));
+        }
+        if data.topic.is_empty() {
+            return Err(errors::empty_kafka_topic());
+        }
+        let mat = Materializer::kafka(
+            runtime,
+            KafkaMaterializer::Publish(data),
+            WitEffect::Create(false),
+        );
+        Ok(Store::register_materializer(mat))
+    }
+
     fn register_prisma_runtime(data: wit::PrismaRuntimeData) -> Result<wit::RuntimeId, wit::Error> {
         Ok(Store::register_runtime(Runtime::Prisma(
             data.into(),
@@ -596,3 +730,77 @@ impl wit::Runtimes for crate::Lib {
         )))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+    use common::typegraph::TypeNode;
+
+    #[test]
+    fn test_runtime_override() -> Result<(), String> {
+        use common::typegraph::runtimes::{KnownRuntime, TGRuntime};
+
+        Store::reset()?;
+        let python = Lib::register_python_runtime()?;
+        let pinned = t::struct_()
+            .propx("a", t::integer())?
+            .named("Pinned")
+            .runtime(python)
+            .build()?;
+        let out = t::struct_().prop("pinned", pinned).build()?;
+        let mat =
+            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => ({})"), Effect::None)?;
+
+        expose_one("f", t::func(t::struct_().build()?, out, mat)?)?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        let pinned = tg
+            .types
+            .iter()
+            .find(|t| t.base().title == "Pinned")
+            .unwrap();
+        let idx = pinned.base().runtime;
+        assert!(matches!(
+            tg.runtimes[idx as usize],
+            TGRuntime::Known(KnownRuntime::PythonWasi(_))
+        ));
+        assert_ne!(idx, tg.types[0].base().runtime);
+        // the props inherit the pinned runtime
+        let TypeNode::Object { data, .. } = pinned else {
+            panic!("expected an object");
+        };
+        assert_eq!(tg.types[data.properties["a"] as usize].base().runtime, idx);
+        Ok(())
+    }
+
+    #[test]
+    fn test_effect_not_supported() -> Result<(), String> {
+        use crate::wit::runtimes::{BaseMaterializer, MaterializerRandom, RandomRuntimeData};
+
+        Store::reset()?;
+        let random = Lib::register_random_runtime(RandomRuntimeData {
+            seed: Some(1),
+            reset: None,
+        })?;
+        let mat = Lib::create_random_mat(
+            BaseMaterializer {
+                runtime: random,
+                effect: Effect::Delete(true),
+            },
+            MaterializerRandom { runtime: random },
+        )?;
+
+        assert_eq!(
+            expose_one(
+                "randomUser",
+                t::func(t::struct_().build()?, t::string().build()?, mat)?
+            ),
+            Err(errors::effect_not_supported("random", "delete"))
+        );
+        Ok(())
+    }
+}
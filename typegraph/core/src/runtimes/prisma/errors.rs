@@ -40,6 +40,41 @@ pub fn conflicting_attributes(
     .join(" ")
 }
 
+pub fn empty_db_name(model: &str, field: Option<&str>) -> Error {
+    match field {
+        Some(field) => format!("Empty 'db_name' on {model}::{field}"),
+        None => format!("Empty 'db_name' on model {model}"),
+    }
+}
+
+pub fn empty_db_type(model: &str, field: &str) -> Error {
+    format!("Empty 'db_type' on {model}::{field}")
+}
+
+pub fn model_without_id(model: &str) -> Error {
+    format!("Model {model} must have at least one id or unique field")
+}
+
+pub fn conflicting_id_definition(model: &str) -> Error {
+    format!("Model {model} cannot have both an id field and a composite id")
+}
+
+pub fn invalid_composite_id(model: &str, field: &str, reason: &str) -> Error {
+    format!("Invalid composite id on model {model}: field {field:?} {reason}")
+}
+
+pub fn unknown_index_field(name: &str) -> Error {
+    format!("Unknown field {name:?} in index")
+}
+
+pub fn inline_link_conflict(name: &str) -> Error {
+    format!("Type {name:?} already exists with a different structure")
+}
+
+pub fn unknown_target_field(model: &str, field: &str) -> Error {
+    format!("Target field {field:?} not found on model {model}")
+}
+
 pub fn no_relationship_target(model: &str, field: &str, target_model: &str) -> Error {
     format!(r#"Relationship target field not found for "{model}::{field}" on {target_model:?}."#)
 }
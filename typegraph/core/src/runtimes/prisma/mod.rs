@@ -16,6 +16,7 @@ use indexmap::IndexMap;
 use crate::conversion::runtimes::MaterializerConverter;
 use crate::errors::Result;
 use crate::global_store::Store;
+use crate::sync::RefCell;
 use crate::typegraph::TypegraphContext;
 use crate::wit::runtimes::{self as wit, RuntimeId};
 
@@ -24,7 +25,7 @@ use self::type_generation::TypeGenContext;
 
 use super::Runtime;
 
-pub struct PrismaRuntimeContext(std::cell::RefCell<Option<TypeGenContext>>);
+pub struct PrismaRuntimeContext(RefCell<Option<TypeGenContext>>);
 
 impl Debug for PrismaRuntimeContext {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -35,7 +36,7 @@ impl Debug for PrismaRuntimeContext {
 
 impl Default for PrismaRuntimeContext {
     fn default() -> Self {
-        Self(std::cell::RefCell::new(Some(TypeGenContext::default())))
+        Self(RefCell::new(Some(TypeGenContext::default())))
     }
 }
 
@@ -97,6 +97,17 @@ impl Candidate {
     }
 
     fn into_pair(self, registry: &RelationshipRegistry) -> Result<CandidatePair> {
+        if let Some(target_field) = &self.target_field {
+            let found = self
+                .model_type
+                .as_struct()?
+                .iter_props()
+                .any(|(k, _)| k == target_field);
+            if !found {
+                return Err(errors::unknown_target_field(&self.model_name, target_field));
+            }
+        }
+
         let alternatives = self.get_alternatives(registry)?;
         match alternatives.len() {
             0 => Err(errors::no_relationship_target(
@@ -178,6 +189,24 @@ impl Candidate {
     }
 }
 
+/// How the side holding the foreign key was chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SideHeuristic {
+    ExplicitFkey,
+    UniqueAttribute,
+    Cardinality,
+}
+
+impl std::fmt::Display for SideHeuristic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ExplicitFkey => write!(f, "explicit fkey attribute"),
+            Self::UniqueAttribute => write!(f, "unique attribute"),
+            Self::Cardinality => write!(f, "cardinality"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct CandidatePair(pub Candidate, pub Candidate);
 
@@ -201,28 +230,31 @@ impl CandidatePair {
         }
     }
 
-    pub fn ordered(self) -> Result<Self> {
+    /// Orders the pair so that the second model holds the foreign key;
+    /// also returns the heuristic that chose the side.
+    pub fn ordered(self) -> Result<(Self, SideHeuristic)> {
+        use SideHeuristic as H;
         let CandidatePair(first, second) = self;
         // right will be the model that has the foreign key
         use Cardinality as C;
         match (first.cardinality, second.cardinality) {
             (C::One, C::One) | (C::Optional, C::Optional) => {
                 match (first.fkey, second.fkey) {
-                    (Some(true), Some(false)) => Ok(Self(first, second)),
-                    (Some(false), Some(true)) => Ok(Self(second, first)),
+                    (Some(true), Some(false)) => Ok((Self(first, second), H::ExplicitFkey)),
+                    (Some(false), Some(true)) => Ok((Self(second, first), H::ExplicitFkey)),
                     (Some(true), Some(true)) => {
                         Err(errors::conflicting_attributes("fkey", &first.model_name, &second.field_name, &second.model_name, &first.field_name))
                     }
                     (Some(false), Some(false)) => Err(errors::conflicting_attributes("fkey", &first.model_name, &second.field_name, &second.model_name, &first.field_name)),
-                    (Some(true), None) => Ok(Self(first, second)),
-                    (Some(false), None) => Ok(Self(second, first)),
-                    (None, Some(true)) => Ok(Self(second, first)),
-                    (None, Some(false)) => Ok(Self(first, second)),
+                    (Some(true), None) => Ok((Self(first, second), H::ExplicitFkey)),
+                    (Some(false), None) => Ok((Self(second, first), H::ExplicitFkey)),
+                    (None, Some(true)) => Ok((Self(second, first), H::ExplicitFkey)),
+                    (None, Some(false)) => Ok((Self(first, second), H::ExplicitFkey)),
                     (None, None) => {
                         // choose by unique attribute
                         match (first.unique, second.unique) {
-                            (true, false) => Ok(Self(first, second)),
-                            (false, true) => Ok(Self(second, first)),
+                            (true, false) => Ok((Self(first, second), H::UniqueAttribute)),
+                            (false, true) => Ok((Self(second, first), H::UniqueAttribute)),
                             (true, true) => Err(errors::conflicting_attributes("unique", &first.model_name, &second.field_name, &second.model_name, &first.field_name)),
                             (false, false) => Err(errors::ambiguous_side(&first.model_name, &second.field_name, &second.model_name, &first.field_name)),
                         }
@@ -232,12 +264,12 @@ impl CandidatePair {
 
             (C::One, C::Optional) | (C::One, C::Many) | (C::Optional, C::Many) => {
                 // TODO check unique/fkey
-                Ok(Self(first, second))
+                Ok((Self(first, second), H::Cardinality))
             }
 
             (C::Optional, C::One) | (C::Many, C::One) | (C::Many, C::Optional) => {
                 // TODO check unique/fkey
-                Ok(Self(second, first))
+                Ok((Self(second, first), H::Cardinality))
             }
             (C::Many, C::Many) => {
                 Err(format!(
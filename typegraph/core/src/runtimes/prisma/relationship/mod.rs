@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use crate::errors::Result;
+use crate::global_store::Store;
+use crate::runtimes::prisma::errors;
+use crate::runtimes::prisma::type_utils::{anonymous_structure_key, structure_key};
 use crate::t;
 use crate::t::TypeBuilder;
 use crate::types::TypeId;
+use sha2::{Digest, Sha256};
 
 mod discovery;
 pub mod registry;
@@ -151,7 +155,7 @@ impl PrismaLink {
             proxy.set("rel_name", rel_name);
         }
         if let Some(fkey) = self.fkey {
-            proxy.set("fkey", format!("{fkey}"));
+            proxy.set("fkey", serde_json::to_string(&fkey).unwrap());
         }
         if let Some(target_field) = self.target_field.clone() {
             proxy.set("target_field", target_field);
@@ -180,6 +184,31 @@ pub fn prisma_link(type_id: TypeId) -> Result<PrismaLink> {
     Ok(prisma_linkn(name))
 }
 
+/// Same as `prisma_link`, but an unnamed target gets a name generated from
+/// its structure.
+#[allow(dead_code)]
+pub fn prisma_link_inline(type_id: TypeId) -> Result<PrismaLink> {
+    if type_id.type_name()?.is_some() {
+        return prisma_link(type_id);
+    }
+    let key = structure_key(type_id)?;
+    let mut sha256 = Sha256::new();
+    sha256.update(&key);
+    let name = format!("Inline{}", &format!("{:x}", sha256.finalize())[..12]);
+    match Store::get_type_by_name(&name) {
+        // reused only if it has the same structure
+        Some(existing) => {
+            if anonymous_structure_key(existing)? != key {
+                return Err(errors::inline_link_conflict(&name));
+            }
+        }
+        None => {
+            type_id.as_struct()?.rename(name.clone())?;
+        }
+    }
+    Ok(prisma_linkn(name))
+}
+
 pub fn prisma_linkn(name: impl Into<String>) -> PrismaLink {
     PrismaLink {
         type_name: name.into(),
@@ -191,13 +220,15 @@ use registry::RelationshipRegistry;
 
 #[cfg(test)]
 mod test {
-    use super::{prisma_linkn, prisma_linkx};
+    use super::{prisma_link_inline, prisma_linkn, prisma_linkx};
     use crate::errors::Result;
     use crate::global_store::Store;
     use crate::runtimes::prisma::errors;
     use crate::runtimes::prisma::relationship::registry::RelationshipRegistry;
+    use crate::runtimes::prisma::type_utils::as_relationship_target;
     use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
     use crate::test_utils::*;
+    use crate::types::{Type, TypeId};
 
     #[test]
     fn test_implicit_relationships() -> Result<(), String> {
@@ -213,7 +244,7 @@ mod test {
 
     #[test]
     fn test_explicit_relationship_name() -> Result<(), String> {
-        Store::reset();
+        Store::reset()?;
         let user = t::struct_()
             .propx("id", t::integer().as_id(true))?
             .propx("name", t::string())?
@@ -239,7 +270,7 @@ mod test {
 
     #[test]
     fn test_fkey_attribute() -> Result<(), String> {
-        Store::reset();
+        Store::reset()?;
         let user = t::struct_()
             .propx("id", t::integer().as_id(true))?
             .propx(
@@ -264,14 +295,122 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_fkey_diagnostics() -> Result<(), String> {
+        Store::reset()?;
+        let user = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx(
+                "profile",
+                prisma_linkx(t::optionalx(t::proxy("Profile"))?)?.fkey(true),
+            )?
+            .named("User")
+            .build()?;
+
+        let profile = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx("user", t::optionalx(t::proxy("User"))?)?
+            .named("Profile")
+            .build()?;
+
+        let mut reg = RelationshipRegistry::with_diagnostics();
+        reg.manage(user)?;
+        reg.manage(profile)?;
+
+        assert_eq!(
+            reg.diagnostics(),
+            &["__rel_User_Profile_1: User::profile holds the foreign key (explicit fkey attribute)"]
+        );
+
+        let mut reg = RelationshipRegistry::default();
+        reg.manage(user)?;
+        assert!(reg.diagnostics().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_target_field() -> Result<(), String> {
+        Store::reset()?;
+        let user = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx(
+                "profile",
+                prisma_linkx(t::optionalx(t::proxy("Profile"))?)?.field("nonexistent"),
+            )?
+            .named("User")
+            .build()?;
+
+        t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx("user", t::optionalx(t::proxy("User"))?)?
+            .named("Profile")
+            .build()?;
+
+        let mut reg = RelationshipRegistry::default();
+        assert_eq!(
+            reg.manage(user),
+            Err(errors::unknown_target_field("Profile", "nonexistent"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inline_link() -> Result<(), String> {
+        Store::reset()?;
+        let profile = || -> Result<_> {
+            t::struct_()
+                .propx("id", t::integer().as_id(true))?
+                .propx("bio", t::string())?
+                .propx("user", t::proxy("User"))?
+                .build()
+        };
+
+        let first = prisma_link_inline(profile()?)?;
+        let name = first.type_name.clone();
+        assert!(name.starts_with("Inline"));
+        // same structure, same name
+        assert_eq!(prisma_link_inline(profile()?)?.type_name, name);
+        // the constraints are part of the structure
+        let bounded = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx("bio", t::string().max(140))?
+            .propx("user", t::proxy("User"))?
+            .build()?;
+        assert_ne!(prisma_link_inline(bounded)?.type_name, name);
+
+        let user = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx("profile", t::optionalx(first)?)?
+            .named("User")
+            .build()?;
+
+        let mut reg = RelationshipRegistry::default();
+        reg.manage(user)?;
+        let rel = reg.find_relationship_on(user, "profile").unwrap();
+        assert_eq!(rel.left.model_name, "User");
+        assert_eq!(rel.right.model_name, name);
+
+        // an unrelated type already has the generated name
+        Store::reset()?;
+        t::string().named(name.clone()).build()?;
+        assert_eq!(
+            prisma_link_inline(profile()?).map(|l| l.type_name),
+            Err(errors::inline_link_conflict(&name))
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_unique_attribute() -> Result<(), String> {
-        Store::reset();
+        Store::reset()?;
         let user = t::struct_()
             .propx("id", t::integer().as_id(true))?
             .propx(
                 "profile",
-                t::optionalx(t::proxy("Profile"))?.config("unique", "true"),
+                t::optionalx(t::proxy("Profile"))?.config("unique", true),
             )?
             .named("User")
             .build()?;
@@ -291,9 +430,250 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_unique_config_value() -> Result<(), String> {
+        use crate::runtimes::prisma::type_utils::RuntimeConfig;
+
+        Store::reset()?;
+        let typ = t::integer().config("unique", true).build()?.as_type()?;
+        let config = RuntimeConfig::try_from(&typ)?;
+        assert_eq!(
+            config.get::<serde_json::Value>("unique")?,
+            Some(serde_json::Value::Bool(true))
+        );
+        assert!(config.flag("unique")?);
+
+        let typ = t::integer().config("unique", "true").build()?.as_type()?;
+        let config = RuntimeConfig::try_from(&typ)?;
+        assert_eq!(
+            config.get::<serde_json::Value>("unique")?,
+            Some(serde_json::Value::String("true".to_string()))
+        );
+        assert!(config.flag("unique").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_db_name() -> Result<(), String> {
+        Store::reset()?;
+        let user = t::struct_()
+            .propx("id", t::integer().as_id(true).db_name("user_id"))?
+            .propx("name", t::string())?
+            .named("User")
+            .db_name("app_users")
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        reg.manage(user)?;
+
+        let user = t::struct_()
+            .propx("id", t::integer().as_id(true).db_name(""))?
+            .named("Post")
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        assert_eq!(
+            reg.manage(user),
+            Err(errors::empty_db_name("Post", Some("id")))
+        );
+
+        let user = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .named("Profile")
+            .db_name("")
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        assert_eq!(
+            reg.manage(user),
+            Err(errors::empty_db_name("Profile", None))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_db_type() -> Result<(), String> {
+        Store::reset()?;
+        let product = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx("name", t::string().db_type("VarChar(255)"))?
+            .propx("price", t::float().db_type("Decimal(10, 2)"))?
+            .named("Product")
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        reg.manage(product)?;
+
+        let item = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx("price", t::float().db_type(""))?
+            .named("Item")
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        assert_eq!(
+            reg.manage(item),
+            Err(errors::empty_db_type("Item", "price"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scalar_list() -> Result<(), String> {
+        Store::reset()?;
+        let article = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx("tags", t::arrayx(t::string())?)?
+            .named("Article")
+            .build()?;
+
+        let mut reg = RelationshipRegistry::default();
+        reg.manage(article)?;
+        assert!(reg.relationships.is_empty());
+        assert!(!reg.has(article, "tags"));
+
+        // a scalar list column: not a relationship target
+        let tags = article.as_struct()?.data.get_prop("tags").unwrap();
+        let Type::Array(list) = tags.as_type()? else {
+            panic!("expected an array");
+        };
+        assert!(matches!(TypeId(list.data.of).as_type()?, Type::String(_)));
+        assert!(as_relationship_target(tags, None)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_model_without_id() -> Result<(), String> {
+        Store::reset()?;
+        let log = t::struct_()
+            .propx("message", t::string())?
+            .propx("level", t::integer())?
+            .named("Log")
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        assert_eq!(reg.manage(log), Err(errors::model_without_id("Log")));
+
+        let entry = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx("message", t::string())?
+            .named("Entry")
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        reg.manage(entry)?;
+
+        t::string().named("Email").build()?;
+        let account = t::struct_()
+            .prop("email", t::proxy("Email").set("unique", "true").build()?)
+            .propx("name", t::string())?
+            .named("Account")
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        reg.manage(account)?;
+        assert_eq!(reg.models[&account].id_fields, vec!["email"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_composite_id() -> Result<(), String> {
+        Store::reset()?;
+        let member = t::struct_()
+            .propx("tenant", t::string())?
+            .propx("id", t::integer())?
+            .propx("name", t::string())?
+            .named("Member")
+            .composite_id(vec!["tenant", "id"])
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        reg.manage(member)?;
+        assert_eq!(reg.models[&member].id_fields, vec!["tenant", "id"]);
+
+        let account = t::struct_()
+            .propx("tenant", t::string())?
+            .propx("id", t::integer().as_id(true))?
+            .named("Account")
+            .composite_id(vec!["tenant", "id"])
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        assert_eq!(
+            reg.manage(account),
+            Err(errors::conflicting_id_definition("Account"))
+        );
+
+        let group = t::struct_()
+            .propx("tenant", t::optionalx(t::string())?)?
+            .propx("id", t::integer())?
+            .named("Group")
+            .composite_id(vec!["tenant", "id", "missing"])
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        assert_eq!(
+            reg.manage(group),
+            Err(errors::invalid_composite_id(
+                "Group",
+                "tenant",
+                "must not be optional"
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_indexes() -> Result<(), String> {
+        Store::reset()?;
+        let user = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx("email", t::string())?
+            .propx("first_name", t::string())?
+            .propx("last_name", t::string())?
+            .named("User")
+            .index(vec!["email"])
+            .unique_index(vec!["first_name", "last_name"])
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        reg.manage(user)?;
+        insta::assert_debug_snapshot!("indexes", user.as_struct()?.base.runtime_config.clone());
+
+        let post = t::struct_()
+            .propx("id", t::integer().as_id(true))?
+            .propx("title", t::string())?
+            .named("Post")
+            .index(vec!["title"])
+            .index(vec!["slug"])
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        assert_eq!(reg.manage(post), Err(errors::unknown_index_field("slug")));
+
+        // a unique index identifies the records
+        let person = t::struct_()
+            .propx("first_name", t::string())?
+            .propx("last_name", t::string())?
+            .named("Person")
+            .unique_index(vec!["first_name", "last_name"])
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        reg.manage(person)?;
+        assert_eq!(
+            reg.models[&person].id_fields,
+            vec!["first_name", "last_name"]
+        );
+
+        // the id field is found through proxies
+        t::integer().as_id(true).named("CommentId").build()?;
+        let comment = t::struct_()
+            .prop("id", t::proxy("CommentId").build()?)
+            .named("Comment")
+            .build()?;
+        let mut reg = RelationshipRegistry::default();
+        reg.manage(comment)?;
+        assert_eq!(reg.models[&comment].id_fields, vec!["id"]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_self_relationship() -> Result<(), String> {
-        Store::reset();
+        Store::reset()?;
         let node = t::struct_()
             .propx("id", t::string().as_id(true))?
             .propx("children", t::arrayx(t::proxy("Node"))?)?
@@ -311,7 +691,7 @@ mod test {
 
     #[test]
     fn test_ambiguous_side() -> Result<(), String> {
-        Store::reset();
+        Store::reset()?;
         let user = t::struct_()
             .propx("id", t::integer().as_id(true))?
             .propx("profile", t::proxy("Profile"))?
@@ -336,7 +716,7 @@ mod test {
             Err(errors::ambiguous_side("User", "profile", "Profile", "user"))
         );
 
-        Store::reset();
+        Store::reset()?;
         let user = t::struct_()
             .propx("id", t::integer().as_id(true))?
             .propx("profile", t::optionalx(t::proxy("Profile"))?)?
@@ -366,7 +746,7 @@ mod test {
 
     #[test]
     fn test_conflicting_attributes() -> Result<(), String> {
-        Store::reset();
+        Store::reset()?;
         let user = t::struct_()
             .propx("id", t::integer().as_id(true))?
             .propx("profile", prisma_linkn("Profile").fkey(true))?
@@ -395,7 +775,7 @@ mod test {
             ))
         );
 
-        Store::reset();
+        Store::reset()?;
         let user = t::struct_()
             .propx("id", t::integer().as_id(true))?
             .propx("profile", prisma_linkn("Profile").fkey(false))?
@@ -429,7 +809,7 @@ mod test {
 
     #[test]
     fn test_missing_target() -> Result<(), String> {
-        Store::reset();
+        Store::reset()?;
         let user = t::struct_()
             .propx("id", t::integer().as_id(true))?
             .propx("profile", prisma_linkn("Profile").fkey(true))?
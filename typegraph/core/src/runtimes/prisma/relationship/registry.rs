@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use crate::errors::Result;
-use crate::runtimes::prisma::type_utils::get_id_field;
+use crate::runtimes::prisma::type_utils::{
+    check_db_config, check_indexes, check_unique_identifier, get_id_fields,
+};
+use crate::sync::{Rc, RefCell};
 use crate::types::TypeId;
 #[cfg(test)]
 use indexmap::IndexMap as HashMap;
@@ -14,9 +17,6 @@ use std::collections::HashMap;
 #[cfg(not(test))]
 use std::collections::HashSet;
 
-use std::cell::RefCell;
-use std::rc::Rc;
-
 use super::{
     discovery::{scan_model, Candidate, CandidatePair},
     Relationship, RelationshipModel,
@@ -27,7 +27,7 @@ pub struct RegisteredModel {
     // property => relationship_name
     pub relationships: IndexMap<String, String>,
     pub name: String,
-    pub id_field: String, // TODO support multiple id fields
+    pub id_fields: Vec<String>,
 }
 
 #[derive(Default, Debug)]
@@ -38,9 +38,24 @@ pub struct RelationshipRegistry {
     pub relationships: HashMap<String, Rc<Relationship>>,
     complete_registrations: HashSet<TypeId>,
     counter: RefCell<usize>,
+    diagnostics: Option<Vec<String>>,
 }
 
 impl RelationshipRegistry {
+    /// Registry recording which heuristic chose the foreign key side of each relationship
+    #[allow(dead_code)]
+    pub fn with_diagnostics() -> Self {
+        Self {
+            diagnostics: Some(vec![]),
+            ..Default::default()
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn diagnostics(&self) -> &[String] {
+        self.diagnostics.as_deref().unwrap_or_default()
+    }
+
     fn is_registered(&self, candidate: &Candidate) -> bool {
         let entry = self.models.get(&candidate.source_model);
         match entry {
@@ -59,7 +74,7 @@ impl RelationshipRegistry {
             Entry::Vacant(e) => e.insert(RegisteredModel {
                 relationships: IndexMap::new(),
                 name: candidate.model_name.clone(),
-                id_field: get_id_field(candidate.model_type)?,
+                id_fields: get_id_fields(candidate.model_type)?,
             }),
             Entry::Occupied(e) => e.into_mut(),
         };
@@ -85,11 +100,18 @@ impl RelationshipRegistry {
         }
 
         let id = self.next_id();
-        let pair = pair.ordered()?;
+        let (pair, heuristic) = pair.ordered()?;
 
         let rel_name = pair.rel_name(id)?;
         let CandidatePair(left, right) = pair;
 
+        if let Some(diagnostics) = self.diagnostics.as_mut() {
+            diagnostics.push(format!(
+                "{rel_name}: {}::{} holds the foreign key ({heuristic})",
+                right.model_name, left.field_name
+            ));
+        }
+
         self.add_relationship_field(&left, right.field_name.clone(), rel_name.clone())?;
         self.add_relationship_field(&right, left.field_name.clone(), rel_name.clone())?;
 
@@ -127,6 +149,9 @@ impl RelationshipRegistry {
                 let model = model_id.as_struct()?;
 
                 if let Entry::Vacant(e) = self.models.entry(model_id) {
+                    check_db_config(model_id)?;
+                    check_unique_identifier(model_id)?;
+                    check_indexes(model_id)?;
                     e.insert(RegisteredModel {
                         relationships: IndexMap::new(),
                         name: model
@@ -134,7 +159,7 @@ impl RelationshipRegistry {
                             .name
                             .clone()
                             .ok_or_else(|| "prisma model requires a name".to_string())?,
-                        id_field: get_id_field(model_id)?,
+                        id_fields: get_id_fields(model_id)?,
                     });
                 }
 
@@ -9,7 +9,15 @@ use crate::{
 
 use super::{TypeGen, TypeGenContext};
 
-pub struct Count;
+pub struct Count {
+    model_id: TypeId,
+}
+
+impl Count {
+    pub fn new(model_id: TypeId) -> Self {
+        Self { model_id }
+    }
+}
 
 impl TypeGen for Count {
     fn generate(&self, _context: &mut TypeGenContext) -> Result<TypeId> {
@@ -17,6 +25,7 @@ impl TypeGen for Count {
     }
 
     fn name(&self) -> String {
-        "_Count".to_string()
+        let model_name = self.model_id.type_name().unwrap().unwrap();
+        format!("_{model_name}_Count")
     }
 }
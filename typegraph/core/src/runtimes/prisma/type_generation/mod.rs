@@ -8,9 +8,7 @@
 //! Type generation should always be done through the `TypeGenContext` to enable
 //! the cache. Do not call `TypeGen::generate` directly.
 
-use std::cell::RefCell;
 use std::collections::HashMap;
-use std::rc::{Rc, Weak};
 
 use regex::Regex;
 
@@ -27,6 +25,7 @@ use self::with_nested_count::WithNestedCount;
 use super::relationship::registry::RelationshipRegistry;
 use crate::errors::Result;
 use crate::runtimes::prisma::relationship::Cardinality;
+use crate::sync::{Rc, RefCell, Weak};
 use crate::t::{self, TypeBuilder};
 use crate::typegraph::with_tg_mut;
 use crate::types::{TypeFun, TypeId};
@@ -99,6 +98,18 @@ pub fn replace_variables_to_indices(query: String, input_id: TypeId) -> Result<F
 }
 
 impl TypeGenContext {
+    /// The create input and the output types of a model: relations are
+    /// nested `create`/`connect` objects on the input and nested models on
+    /// the output.
+    pub fn model_types(&mut self, model_id: TypeId) -> Result<OperationTypes> {
+        self.registry.manage(model_id)?;
+
+        Ok(OperationTypes {
+            input: self.generate(&InputType::for_create(model_id))?,
+            output: self.generate(&OutType::new(model_id))?,
+        })
+    }
+
     pub fn find_unique(&mut self, model_id: TypeId) -> Result<OperationTypes> {
         self.registry.manage(model_id)?;
 
@@ -179,13 +190,11 @@ impl TypeGenContext {
     }
 
     pub fn create_one(&mut self, model_id: TypeId) -> Result<OperationTypes> {
-        self.registry.manage(model_id)?;
+        let model = self.model_types(model_id)?;
 
         Ok(OperationTypes {
-            input: t::struct_()
-                .prop("data", self.generate(&InputType::for_create(model_id))?)
-                .build()?,
-            output: self.generate(&OutType::new(model_id))?,
+            input: t::struct_().prop("data", model.input).build()?,
+            output: model.output,
         })
     }
 
@@ -424,6 +433,23 @@ mod test {
         }};
     }
 
+    #[test]
+    fn test_count_per_model() -> Result<()> {
+        setup(None)?;
+        let mut context = TypeGenContext::default();
+        let (user, post) = models::simple_relationship()?;
+        context.registry.manage(user)?;
+
+        let user_count = context.generate(&count::Count::new(user))?;
+        let post_count = context.generate(&count::Count::new(post))?;
+        assert_ne!(user_count, post_count);
+        assert_eq!(user_count.type_name()?.as_deref(), Some("_User_Count"));
+        assert_eq!(post_count.type_name()?.as_deref(), Some("_Post_Count"));
+        assert_eq!(context.generate(&count::Count::new(user))?, user_count);
+        Ok(())
+    }
+
+    test_op!(model_types);
     test_op!(find_unique);
     test_op!(find_many);
     test_op!(find_first, output_only);
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use crate::errors::Result;
+use crate::runtimes::prisma::errors;
+use crate::runtimes::prisma::type_utils::RuntimeConfig;
 use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
 use crate::types::TypeFun;
 use crate::types::TypeId;
@@ -31,13 +33,13 @@ impl TypeGen for QueryUniqueWhereExpr {
                 .get_base()
                 .ok_or_else(|| "expected a concrete type".to_string())?
                 .as_id;
-            let is_unique = type_id.as_type()?.get_base().map_or(false, |base| {
-                base.runtime_config
-                    .iter()
-                    .flatten()
-                    .find_map(|(k, v)| (k == "unique").then(|| v.clone()))
-                    .map_or(false, |v| v == "true")
-            });
+            let is_unique = RuntimeConfig::new(
+                type_id
+                    .as_type()?
+                    .get_base()
+                    .and_then(|base| base.runtime_config.as_ref()),
+            )
+            .flag("unique")?;
 
             if attrs.concrete_type.is_func()? || (!is_id && !is_unique) {
                 continue;
@@ -46,11 +48,73 @@ impl TypeGen for QueryUniqueWhereExpr {
             builder.propx(key, t::optional(inner))?;
         }
 
+        // compound unique keys, in the `a_b: { a, b }` form of Prisma
+        let config = RuntimeConfig::new(model.base.runtime_config.as_ref());
+        let compound_keys = config
+            .get::<Vec<String>>("composite_id")?
+            .into_iter()
+            .chain(
+                config
+                    .get::<Vec<Vec<String>>>("unique_indexes")?
+                    .unwrap_or_default(),
+            )
+            .filter(|fields| fields.len() > 1);
+        for fields in compound_keys {
+            let key = fields.join("_");
+            let mut compound = t::struct_();
+            for field in fields.iter() {
+                let type_id = model
+                    .data
+                    .get_prop(field)
+                    .ok_or_else(|| errors::unknown_index_field(field))?;
+                compound.prop(field, type_id.attrs()?.concrete_type);
+            }
+            let compound = compound
+                .named(format!("{}_{}_CompoundUniqueInput", self.model_name(), key))
+                .build()?;
+            builder.propx(key, t::optional(compound))?;
+        }
+
         builder.named(self.name()).build()
     }
 
     fn name(&self) -> String {
-        let name = self.model_id.type_name().unwrap().unwrap();
-        format!("QueryUnique{}WhereInput", name)
+        format!("QueryUnique{}WhereInput", self.model_name())
+    }
+}
+
+impl QueryUniqueWhereExpr {
+    fn model_name(&self) -> String {
+        self.model_id.type_name().unwrap().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use super::*;
+    use crate::test_utils::*;
+
+    #[test]
+    fn test_compound_unique_where() -> Result<()> {
+        setup(None)?;
+
+        let member = t::struct_()
+            .propx("tenant", t::string())?
+            .propx("id", t::integer())?
+            .propx("email", t::string().config("unique", true))?
+            .propx("first_name", t::string())?
+            .propx("last_name", t::string())?
+            .named("Member")
+            .composite_id(vec!["tenant", "id"])
+            .unique_index(vec!["first_name", "last_name"])
+            .build()?;
+        let mut context = TypeGenContext::default();
+        context.registry.manage(member)?;
+
+        let ty = context.generate(&QueryUniqueWhereExpr::new(member))?;
+        insta::assert_snapshot!("query_unique_where_expr Member", tree::print(ty));
+
+        Ok(())
     }
 }
@@ -66,7 +66,7 @@ impl TypeGen for WithNestedCount {
         if !countable.is_empty() {
             let mut count = t::struct_();
             for prop in countable.into_iter() {
-                count.prop(prop, context.generate(&Count)?);
+                count.prop(prop, context.generate(&Count::new(self.model_id))?);
             }
             builder.propx("_count", count)?;
         }
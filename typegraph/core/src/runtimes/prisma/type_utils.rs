@@ -4,28 +4,29 @@
 use std::borrow::Cow;
 
 use crate::errors::Result;
+use crate::runtimes::prisma::errors;
 use crate::types::Type;
 use crate::types::TypeAttributes;
 use crate::types::TypeFun;
 use crate::types::TypeId;
+use crate::wit::core::{TypeArray, TypeBase};
 
 use super::relationship::Cardinality;
 
 impl TypeAttributes {
     pub fn is_unique_ref(&self) -> Result<bool> {
         let typ = self.concrete_type.as_type()?;
-        let base = typ.get_base().unwrap();
-        Ok(base
-            .runtime_config
-            .iter()
-            .flatten()
-            .find_map(|(k, v)| (k == "unique").then(|| v == "true"))
-            .unwrap_or(false)
-            || self
-                .proxy_data
-                .iter()
-                .find_map(|(k, v)| (k == "unique").then(|| v == "true"))
-                .unwrap_or(false))
+        if RuntimeConfig::try_from(&typ)?.flag("unique")? {
+            return Ok(true);
+        }
+        self.proxy_data
+            .get("unique")
+            .map(|v| {
+                serde_json::from_str::<bool>(v)
+                    .map_err(|_| format!("invalid 'unique' field: expected bool, got {}", v))
+            })
+            .transpose()
+            .map(|unique| unique.unwrap_or(false))
     }
 }
 
@@ -56,12 +57,216 @@ pub fn as_relationship_target(
     }
 }
 
-pub fn get_id_field(model_id: TypeId) -> Result<String> {
+/// Signature of a type built from its structure only (not from the type ids),
+/// including the constraints and the runtime config; named types are
+/// identified by their name
+pub fn structure_key(type_id: TypeId) -> Result<String> {
+    structure_key_of(type_id, true)
+}
+
+/// Same as `structure_key`, but the name of the type itself is ignored
+pub fn anonymous_structure_key(type_id: TypeId) -> Result<String> {
+    structure_key_of(type_id, false)
+}
+
+fn structure_key_of(type_id: TypeId, by_name: bool) -> Result<String> {
+    let typ = type_id.as_type()?;
+    // optional and array types get a generated name that includes the type id
+    match &typ {
+        Type::Proxy(p) => return Ok(format!("&{}{:?}", p.data.name, p.data.extras)),
+        Type::WithPolicy(p) => {
+            return Ok(format!(
+                "policy({}){:?}",
+                structure_key(p.data.tpe.into())?,
+                p.data.chain
+            ))
+        }
+        Type::WithInjection(i) => {
+            return Ok(format!(
+                "injection({}){}",
+                structure_key(i.data.tpe.into())?,
+                i.data.injection
+            ))
+        }
+        Type::Optional(inner) => {
+            let mut data = inner.data.clone();
+            data.of = 0;
+            return Ok(format!(
+                "optional({}){}{:?}",
+                structure_key(inner.data.of.into())?,
+                base_key(&inner.base),
+                data
+            ));
+        }
+        Type::Array(inner) => {
+            let data = TypeArray {
+                of: 0,
+                ..inner.data
+            };
+            return Ok(format!(
+                "array({}){}{:?}",
+                structure_key(inner.data.of.into())?,
+                base_key(&inner.base),
+                data
+            ));
+        }
+        _ => {}
+    }
+    if let Some(name) = typ.get_base().and_then(|b| b.name.as_ref()) {
+        if by_name {
+            return Ok(name.clone());
+        }
+    }
+    let variants_key = |variants: &[u32]| -> Result<String> {
+        let keys = variants
+            .iter()
+            .map(|v| structure_key((*v).into()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(keys.join(" | "))
+    };
+    Ok(match typ {
+        Type::Struct(inner) => {
+            let props = inner
+                .iter_props()
+                .map(|(k, ty)| Ok(format!("{k}: {}", structure_key(ty)?)))
+                .collect::<Result<Vec<_>>>()?;
+            let mut data = inner.data.clone();
+            data.props = vec![];
+            format!(
+                "struct{{{}}}{}{:?}",
+                props.join(", "),
+                base_key(&inner.base),
+                data
+            )
+        }
+        Type::Union(inner) => {
+            let mut data = inner.data.clone();
+            data.variants = vec![];
+            let variants = variants_key(&inner.data.variants)?;
+            format!("union({variants}){}{:?}", base_key(&inner.base), data)
+        }
+        Type::Either(inner) => {
+            let mut data = inner.data.clone();
+            data.variants = vec![];
+            let variants = variants_key(&inner.data.variants)?;
+            format!("either({variants}){}{:?}", base_key(&inner.base), data)
+        }
+        typ => {
+            let mut params = vec![];
+            let data = typ.get_data();
+            data.get_display_params_into(&mut params);
+            format!(
+                "{}({}){}",
+                data.variant_name(),
+                params.join(", "),
+                typ.get_base().map(base_key).unwrap_or_default()
+            )
+        }
+    })
+}
+
+/// The type base, without the name
+fn base_key(base: &TypeBase) -> String {
+    format!(
+        "{:?}",
+        TypeBase {
+            name: None,
+            ..base.clone()
+        }
+    )
+}
+
+/// All the fields of the `indexes` and `unique_indexes` struct configs must exist
+pub fn check_indexes(model_id: TypeId) -> Result<()> {
+    let model = model_id.as_struct()?;
+    let config = RuntimeConfig::new(model.base.runtime_config.as_ref());
+    for key in ["indexes", "unique_indexes"] {
+        for fields in config.get::<Vec<Vec<String>>>(key)?.unwrap_or_default() {
+            for field in fields {
+                if !model.iter_props().any(|(k, _)| k == field) {
+                    return Err(errors::unknown_index_field(&field));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A model must have at least one id or unique field, or a composite id or
+/// unique index
+pub fn check_unique_identifier(model_id: TypeId) -> Result<()> {
+    let model = model_id.as_struct()?;
+    let config = RuntimeConfig::new(model.base.runtime_config.as_ref());
+    if config.get::<Vec<String>>("composite_id")?.is_some()
+        || !config
+            .get::<Vec<Vec<String>>>("unique_indexes")?
+            .unwrap_or_default()
+            .is_empty()
+    {
+        return Ok(());
+    }
+    for (_, type_id) in model.iter_props() {
+        // through proxies: the `unique` flag can also be set on the proxy
+        let attrs = type_id.attrs()?;
+        let as_id = attrs.concrete_type.as_type()?.get_base().map(|b| b.as_id) == Some(true);
+        if as_id || attrs.is_unique_ref()? {
+            return Ok(());
+        }
+    }
+    Err(errors::model_without_id(
+        model.base.name.as_deref().unwrap_or_default(),
+    ))
+}
+
+/// Fields of the primary key: either the single `as_id` field or the
+/// fields of the `composite_id` struct config; without any, the first unique
+/// field or unique index
+pub fn get_id_fields(model_id: TypeId) -> Result<Vec<String>> {
+    let model = model_id.as_struct()?;
+    let model_name = model.base.name.as_deref().unwrap_or_default();
+    let config = RuntimeConfig::new(model.base.runtime_config.as_ref());
+
+    let Some(fields) = config.get::<Vec<String>>("composite_id")? else {
+        if let Some(field) = get_id_field(model_id)? {
+            return Ok(vec![field]);
+        }
+        return config
+            .get::<Vec<Vec<String>>>("unique_indexes")?
+            .and_then(|indexes| indexes.into_iter().next())
+            .ok_or_else(|| "no id field found".to_string());
+    };
+
+    for (_, type_id) in model.iter_props() {
+        let concrete_type = type_id.attrs()?.concrete_type;
+        if concrete_type.as_type()?.get_base().map(|b| b.as_id) == Some(true) {
+            return Err(errors::conflicting_id_definition(model_name));
+        }
+    }
+    if fields.is_empty() {
+        return Err("composite id must have at least one field".to_string());
+    }
+    for field in fields.iter() {
+        let type_id = model
+            .iter_props()
+            .find_map(|(k, ty)| (k == field).then_some(ty))
+            .ok_or_else(|| errors::invalid_composite_id(model_name, field, "does not exist"))?;
+        if let Type::Optional(_) = type_id.attrs()?.concrete_type.as_type()? {
+            return Err(errors::invalid_composite_id(
+                model_name,
+                field,
+                "must not be optional",
+            ));
+        }
+    }
+    Ok(fields)
+}
+
+fn get_id_field(model_id: TypeId) -> Result<Option<String>> {
     let matches = model_id
         .as_struct()?
         .iter_props()
         .map(|(k, ty)| -> Result<Option<String>> {
-            match ty.as_type()? {
+            match ty.attrs()?.concrete_type.as_type()? {
                 Type::Integer(i) => Ok(i.base.as_id.then_some(k.to_string())),
                 Type::String(i) => Ok(i.base.as_id.then_some(k.to_string())),
                 typ => match typ.get_base() {
@@ -84,12 +289,54 @@ pub fn get_id_field(model_id: TypeId) -> Result<String> {
         .flatten()
         .collect::<Vec<_>>();
     match matches.len() {
-        0 => Err("no id field found".to_string()),
-        1 => Ok(matches.into_iter().next().unwrap()),
+        0 => first_unique_field(model_id),
+        1 => Ok(matches.into_iter().next()),
         _ => Err("multiple id fields not supported".to_string()),
     }
 }
 
+/// Without an id field, the first unique integer or string field identifies
+/// the records
+fn first_unique_field(model_id: TypeId) -> Result<Option<String>> {
+    for (k, ty) in model_id.as_struct()?.iter_props() {
+        let attrs = ty.attrs()?;
+        let scalar = matches!(
+            attrs.concrete_type.as_type()?,
+            Type::Integer(_) | Type::String(_)
+        );
+        if scalar && attrs.is_unique_ref()? {
+            return Ok(Some(k.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// The table/column names set with the `db_name` config and the native
+/// column types set with the `db_type` config must not be empty
+pub fn check_db_config(model_id: TypeId) -> Result<()> {
+    let model = model_id.as_struct()?;
+    let model_name = model.base.name.clone().unwrap_or_default();
+    let check = |field: Option<&str>, config: Option<&Vec<(String, String)>>| -> Result<()> {
+        match RuntimeConfig::new(config).get::<String>("db_name")? {
+            Some(name) if name.is_empty() => Err(errors::empty_db_name(&model_name, field)),
+            _ => Ok(()),
+        }
+    };
+
+    check(None, model.base.runtime_config.as_ref())?;
+    for (name, type_id) in model.iter_props() {
+        let typ = type_id.as_type()?;
+        let config = typ.get_base().and_then(|b| b.runtime_config.as_ref());
+        check(Some(name), config)?;
+        if let Some(db_type) = RuntimeConfig::new(config).get::<String>("db_type")? {
+            if db_type.is_empty() {
+                return Err(errors::empty_db_type(&model_name, name));
+            }
+        }
+    }
+    Ok(())
+}
+
 pub struct RuntimeConfig<'a>(Cow<'a, [(String, String)]>);
 
 impl<'a> RuntimeConfig<'a> {
@@ -112,6 +359,11 @@ impl<'a> RuntimeConfig<'a> {
             .transpose()
             .map_err(|e| format!("invalid config value for {}: {}", key, e))
     }
+
+    /// Boolean config value, `false` when not set
+    pub fn flag(&self, key: &str) -> Result<bool> {
+        Ok(self.get(key)?.unwrap_or(false))
+    }
 }
 
 impl<'a> TryFrom<&'a Type> for RuntimeConfig<'a> {
@@ -87,3 +87,42 @@ pub fn temporal_operation(runtime: RuntimeId, data: TemporalOperationData) -> Re
         mat: mat_id,
     })
 }
+
+#[cfg(test)]
+mod test {
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::core::Core;
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+
+    #[test]
+    fn test_temporal_start_workflow() -> Result<(), String> {
+        use crate::wit::runtimes::{
+            TemporalOperationData, TemporalOperationType, TemporalRuntimeData,
+        };
+
+        Store::reset()?;
+        let temporal = Lib::register_temporal_runtime(TemporalRuntimeData {
+            name: "workflows".to_string(),
+            host_secret: "TEMPORAL_HOST".to_string(),
+        })?;
+        let start = Lib::generate_temporal_operation(
+            temporal,
+            TemporalOperationData {
+                mat_arg: Some("SignupWorkflow".to_string()),
+                func_arg: Some(t::struct_().propx("email", t::string())?.build()?.into()),
+                operation: TemporalOperationType::StartWorkflow,
+            },
+        )?;
+
+        expose_one(
+            "signup",
+            t::func(start.inp.into(), start.out.into(), start.mat)?,
+        )?;
+        let typegraph = Lib::finalize_typegraph()?;
+        insta::assert_snapshot!(typegraph);
+        Ok(())
+    }
+}
@@ -7,3 +7,51 @@ use crate::wit::runtimes as wit;
 pub enum WasiMaterializer {
     Module(wit::MaterializerWasi),
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+
+    #[test]
+    fn test_wasi_module() -> Result<(), String> {
+        use crate::wit::runtimes::{BaseMaterializer, MaterializerWasi};
+
+        Store::reset()?;
+        let wasmedge = Lib::register_wasmedge_runtime()?;
+        let wasi_mat = |func_name: &str| {
+            Lib::from_wasi_module(
+                BaseMaterializer {
+                    runtime: wasmedge,
+                    effect: Effect::None,
+                },
+                MaterializerWasi {
+                    func_name: func_name.to_string(),
+                    module: "file:wasm/math.wasm".to_string(),
+                },
+            )
+        };
+        assert_eq!(
+            wasi_mat(""),
+            Err(errors::empty_wasi_func_name("file:wasm/math.wasm"))
+        );
+        let add = wasi_mat("add")?;
+
+        let inp = t::struct_()
+            .propx("a", t::integer())?
+            .propx("b", t::integer())?
+            .build()?;
+        expose_one("add", t::func(inp, t::integer().build()?, add)?)?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        assert_eq!(tg.deps, vec![std::path::PathBuf::from("wasm/math.wasm")]);
+        let mat = &tg.materializers[0];
+        assert_eq!(mat.name, "wasi");
+        assert_eq!(mat.data["wasm"], "file:wasm/math.wasm");
+        assert_eq!(mat.data["func"], "add");
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+//! Shared ownership and interior mutability for the store entries.
+//!
+//! With the `multithread` feature, the store is shared by all the threads:
+//! `Rc` is then `Arc` and `RefCell` is backed by a mutex, with the same API.
+
+#[cfg(not(feature = "multithread"))]
+pub use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+#[cfg(feature = "multithread")]
+pub use std::sync::{Arc as Rc, Weak};
+
+#[cfg(feature = "multithread")]
+#[derive(Debug, Default)]
+pub struct RefCell<T>(std::sync::Mutex<T>);
+
+#[cfg(feature = "multithread")]
+impl<T> RefCell<T> {
+    pub fn new(value: T) -> Self {
+        Self(std::sync::Mutex::new(value))
+    }
+
+    /// Unlike `std::cell::RefCell::borrow`, this is exclusive
+    pub fn borrow(&self) -> std::sync::MutexGuard<'_, T> {
+        self.0.lock().unwrap()
+    }
+
+    pub fn borrow_mut(&self) -> std::sync::MutexGuard<'_, T> {
+        self.0.lock().unwrap()
+    }
+}
+
+/// Bound of the closures stored in the store: `Send + Sync` with the
+/// `multithread` feature
+#[cfg(feature = "multithread")]
+pub trait ThreadSafe: Send + Sync {}
+
+#[cfg(feature = "multithread")]
+impl<T: Send + Sync + ?Sized> ThreadSafe for T {}
+
+/// Bound of the closures stored in the store: `Send + Sync` with the
+/// `multithread` feature
+#[cfg(not(feature = "multithread"))]
+pub trait ThreadSafe {}
+
+#[cfg(not(feature = "multithread"))]
+impl<T: ?Sized> ThreadSafe for T {}
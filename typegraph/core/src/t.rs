@@ -1,18 +1,55 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::errors::Result;
-use crate::types::TypeId;
+use crate::errors::{self, Result};
+use crate::global_store::{LazyTypeBuild, Store};
+use crate::sync::Rc;
+use crate::types::{Struct, Type, TypeFun, TypeId};
 use crate::wit::core::{
-    Core, TypeArray, TypeBase, TypeEither, TypeFloat, TypeFunc, TypeInteger, TypeOptional,
-    TypeProxy, TypeString, TypeStruct, TypeUnion,
+    Core, OptionalKind, PolicySpec, RuntimeId, TypeArray, TypeBase, TypeEither, TypeFloat,
+    TypeFunc, TypeInteger, TypeMap, TypeOptional, TypePolicy, TypeProxy, TypeScalar, TypeString,
+    TypeStruct, TypeUnion,
 };
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::panic::Location;
 
 pub trait TypeBuilder {
     fn build(&self) -> Result<TypeId>;
 
-    fn optional(&self) -> Result<OptionalBuilder> {
-        Ok(optional(self.build()?))
+    /// Same as `build`, with the call site reported on error
+    #[allow(dead_code)]
+    #[track_caller]
+    fn build_traced(&self) -> Result<TypeId> {
+        let location = Location::caller();
+        self.build().map_err(|e| errors::at_location(e, location))
+    }
+
+    /// Builds the type, reusing the structurally identical interned type if
+    /// any, see [`Store::intern_type`]
+    #[allow(dead_code)]
+    fn intern(&self) -> Result<TypeId> {
+        Store::intern_type(self.build()?)
+    }
+
+    /// Builds the type and wraps it in an optional
+    fn optional(&self) -> Result<TypeId> {
+        optional(self.build()?).build()
+    }
+
+    /// Builds the type and wraps it in an array
+    #[allow(dead_code)]
+    fn array(&self) -> Result<TypeId> {
+        array(self.build()?).build()
+    }
+
+    /// Same as `array`, with bounds on the item count
+    #[allow(dead_code)]
+    fn array_of(&self, min: u32, max: u32) -> Result<TypeId> {
+        let mut builder = array(self.build()?);
+        builder.data.min = Some(min);
+        builder.data.max = Some(max);
+        builder.build()
     }
 }
 
@@ -31,6 +68,28 @@ impl TypeBuilder for TypeId {
     }
 }
 
+/// Builder that can no longer be modified, see [`ConcreteTypeBuilder::freeze`].
+/// The type is built once, frozen, and shared by all the users, until the
+/// store drops it on reset or at the end of the typegraph.
+pub struct Frozen<B> {
+    builder: B,
+    built: RefCell<Option<Type>>,
+}
+
+impl<B: TypeBuilder> TypeBuilder for Frozen<B> {
+    fn build(&self) -> Result<TypeId> {
+        if let Some(built) = self.built.borrow().as_ref() {
+            if built.is_registered() {
+                return Ok(built.get_id());
+            }
+        }
+        let type_id = self.builder.build()?;
+        type_id.freeze()?;
+        *self.built.borrow_mut() = Some(type_id.as_type()?);
+        Ok(type_id)
+    }
+}
+
 pub trait ConcreteTypeBuilder: TypeBuilder {
     fn base_mut(&mut self) -> &mut TypeBase;
 
@@ -44,7 +103,41 @@ pub trait ConcreteTypeBuilder: TypeBuilder {
         self
     }
 
-    fn config(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+    /// Marks the type as deprecated; the latest reason wins.
+    /// The SDKs set it through the type base.
+    #[allow(dead_code)]
+    fn deprecated(&mut self, reason: impl Into<String>) -> &mut Self {
+        self.base_mut().deprecated = Some(reason.into());
+        self
+    }
+
+    /// Pins the type to the runtime, instead of the one of its parent
+    #[allow(dead_code)]
+    fn runtime(&mut self, runtime_id: RuntimeId) -> &mut Self {
+        self.base_mut().runtime = Some(runtime_id);
+        self
+    }
+
+    /// Name of the table or column in the database (prisma `@@map`/`@map`)
+    #[allow(dead_code)]
+    fn db_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.config("db_name", name.into())
+    }
+
+    /// Prevents any further modification, for shared types
+    #[allow(dead_code)]
+    fn freeze(self) -> Frozen<Self>
+    where
+        Self: Sized,
+    {
+        Frozen {
+            builder: self,
+            built: RefCell::new(None),
+        }
+    }
+
+    /// Adds a runtime config entry; the value is stored as json
+    fn config(&mut self, key: impl Into<String>, value: impl serde::Serialize) -> &mut Self {
         let runtime_config = &mut self.base_mut().runtime_config;
         if runtime_config.is_none() {
             *runtime_config = Some(Default::default());
@@ -52,7 +145,17 @@ pub trait ConcreteTypeBuilder: TypeBuilder {
         runtime_config
             .as_mut()
             .unwrap()
-            .push((key.into(), value.into()));
+            .push((key.into(), serde_json::to_string(&value).unwrap()));
+        self
+    }
+}
+
+#[allow(dead_code)]
+pub trait ScalarTypeBuilder: ConcreteTypeBuilder {
+    /// Adds an example value; may be called multiple times
+    fn example(&mut self, value: serde_json::Value) -> &mut Self {
+        let examples = self.base_mut().examples.get_or_insert_with(Vec::new);
+        examples.push(serde_json::to_string(&value).unwrap());
         self
     }
 }
@@ -66,6 +169,17 @@ pub fn boolean() -> BooleanBuilder {
     Default::default()
 }
 
+#[derive(Default)]
+pub struct JsonBuilder {
+    base: TypeBase,
+}
+
+/// Arbitrary JSON value
+#[allow(dead_code)]
+pub fn json() -> JsonBuilder {
+    Default::default()
+}
+
 #[derive(Default)]
 pub struct IntegerBuilder {
     base: TypeBase,
@@ -110,6 +224,13 @@ impl IntegerBuilder {
         self.data.exclusive_maximum = Some(max);
         self
     }
+
+    /// Restricts the type to the given values
+    #[allow(dead_code)]
+    pub fn enum_values(mut self, values: Vec<i32>) -> Self {
+        self.data.enumeration = Some(values);
+        self
+    }
 }
 
 pub fn integer() -> IntegerBuilder {
@@ -160,6 +281,20 @@ impl FloatBuilder {
         self.data.exclusive_maximum = Some(max);
         self
     }
+
+    /// Restricts the type to the given values
+    #[allow(dead_code)]
+    pub fn enum_values(mut self, values: Vec<f64>) -> Self {
+        self.data.enumeration = Some(values);
+        self
+    }
+
+    /// Native column type for prisma, e.g. `Decimal(10, 2)`
+    #[allow(dead_code)]
+    pub fn db_type(mut self, db_type: impl Into<String>) -> Self {
+        self.config("db_type", db_type.into());
+        self
+    }
 }
 
 pub fn float() -> FloatBuilder {
@@ -181,6 +316,7 @@ impl Default for TypeString {
             format: None,
             pattern: None,
             enumeration: None,
+            max_size: None,
         }
     }
 }
@@ -189,13 +325,88 @@ pub fn string() -> StringBuilder {
     Default::default()
 }
 
+fn string_with_format(format: &str) -> StringBuilder {
+    let mut builder = string();
+    builder.format(format);
+    builder
+}
+
+/// Binary content, as a string with the `binary` format
+#[allow(dead_code)]
+pub fn bytes() -> StringBuilder {
+    string_with_format("binary")
+}
+
+#[allow(dead_code)]
+pub fn uuid() -> StringBuilder {
+    string_with_format("uuid")
+}
+
+#[allow(dead_code)]
+pub fn email() -> StringBuilder {
+    string_with_format("email")
+}
+
+#[allow(dead_code)]
+pub fn uri() -> StringBuilder {
+    string_with_format("uri")
+}
+
+#[allow(dead_code)]
+pub fn hostname() -> StringBuilder {
+    string_with_format("hostname")
+}
+
+/// String holding serialized JSON; see [`json`] for arbitrary JSON values
+#[allow(dead_code)]
+pub fn json_string() -> StringBuilder {
+    string_with_format("json")
+}
+
 impl StringBuilder {
+    #[allow(dead_code)]
+    pub fn min(&mut self, min: u32) -> &mut Self {
+        self.data.min = Some(min);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn max(&mut self, max: u32) -> &mut Self {
+        self.data.max = Some(max);
+        self
+    }
+
+    /// Exactly `len` characters
+    #[allow(dead_code)]
+    pub fn length(&mut self, len: u32) -> &mut Self {
+        self.min(len).max(len)
+    }
+
     #[allow(dead_code)]
     pub fn format(&mut self, format: impl Into<String>) -> &mut Self {
         self.data.format = Some(format.into());
         self
     }
 
+    #[allow(dead_code)]
+    pub fn pattern(&mut self, pattern: impl Into<String>) -> &mut Self {
+        self.data.pattern = Some(pattern.into());
+        self
+    }
+
+    /// Maximum size in bytes, for binary strings
+    #[allow(dead_code)]
+    pub fn max_size(&mut self, max_size: u32) -> &mut Self {
+        self.data.max_size = Some(max_size);
+        self
+    }
+
+    /// Native column type for prisma, e.g. `VarChar(255)`
+    #[allow(dead_code)]
+    pub fn db_type(&mut self, db_type: impl Into<String>) -> &mut Self {
+        self.config("db_type", db_type.into())
+    }
+
     pub fn enum_(&mut self, values: Vec<String>) -> &mut Self {
         self.data.enumeration = Some(
             values
@@ -218,6 +429,7 @@ impl Default for TypeOptional {
         Self {
             of: u32::max_value(),
             default_item: None,
+            kind: None,
         }
     }
 }
@@ -227,11 +439,33 @@ pub fn optional(ty: TypeId) -> OptionalBuilder {
         base: TypeBase::default(),
         data: TypeOptional {
             of: ty.into(),
-            default_item: None,
+            ..Default::default()
         },
     }
 }
 
+impl OptionalBuilder {
+    #[allow(dead_code)]
+    pub fn default_item(&mut self, value: serde_json::Value) -> &mut Self {
+        self.data.default_item = Some(serde_json::to_string(&value).unwrap());
+        self
+    }
+
+    /// The value must be present, but may be null
+    #[allow(dead_code)]
+    pub fn nullable(&mut self) -> &mut Self {
+        self.data.kind = Some(OptionalKind::Nullable);
+        self
+    }
+
+    /// The value may be omitted, but not null
+    #[allow(dead_code)]
+    pub fn absent(&mut self) -> &mut Self {
+        self.data.kind = Some(OptionalKind::Absent);
+        self
+    }
+}
+
 pub fn optionalx(item_builder: impl TypeBuilder) -> Result<OptionalBuilder> {
     Ok(optional(item_builder.build()?))
 }
@@ -267,6 +501,64 @@ pub fn arrayx(item_builder: impl TypeBuilder) -> Result<ArrayBuilder> {
     Ok(array(item_builder.build()?))
 }
 
+impl ArrayBuilder {
+    #[allow(dead_code)]
+    pub fn min(&mut self, min: u32) -> &mut Self {
+        self.data.min = Some(min);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn max(&mut self, max: u32) -> &mut Self {
+        self.data.max = Some(max);
+        self
+    }
+
+    /// Exactly `len` items
+    #[allow(dead_code)]
+    pub fn length(&mut self, len: u32) -> &mut Self {
+        self.min(len).max(len)
+    }
+}
+
+pub struct MapBuilder {
+    base: TypeBase,
+    data: TypeMap,
+}
+
+/// Dictionary type; `key` must be a string type
+#[allow(dead_code)]
+pub fn map(key: TypeId, value: TypeId) -> MapBuilder {
+    MapBuilder {
+        base: TypeBase::default(),
+        data: TypeMap {
+            key: key.into(),
+            value: value.into(),
+        },
+    }
+}
+
+pub struct CustomScalarBuilder {
+    base: TypeBase,
+    data: TypeScalar,
+}
+
+/// User-defined scalar, checked by the `validator` materializer and
+/// serialized by the `serializer` one
+#[allow(dead_code)]
+pub fn scalar(name: impl Into<String>, validator: u32, serializer: u32) -> CustomScalarBuilder {
+    CustomScalarBuilder {
+        base: TypeBase {
+            name: Some(name.into()),
+            ..Default::default()
+        },
+        data: TypeScalar {
+            validator,
+            serializer,
+        },
+    }
+}
+
 #[derive(Default)]
 pub struct UnionBuilder {
     base: TypeBase,
@@ -278,6 +570,7 @@ impl Default for TypeUnion {
     fn default() -> Self {
         Self {
             variants: Default::default(),
+            discriminator: None,
         }
     }
 }
@@ -286,11 +579,21 @@ pub fn union(variants: impl IntoIterator<Item = TypeId>) -> UnionBuilder {
     UnionBuilder {
         data: TypeUnion {
             variants: variants.into_iter().map(|tid| tid.0).collect(),
+            ..Default::default()
         },
         ..Default::default()
     }
 }
 
+impl UnionBuilder {
+    /// Tags the variants with a string field
+    #[allow(dead_code)]
+    pub fn discriminator(&mut self, field: impl Into<String>) -> &mut Self {
+        self.data.discriminator = Some(field.into());
+        self
+    }
+}
+
 macro_rules! unionx {
     [ $($ty:expr),* ] => {
         $crate::t::union(vec![$($ty.build()?),*])
@@ -352,6 +655,8 @@ impl Default for TypeStruct {
             min: None,
             max: None,
             enumeration: None,
+            max_props: None,
+            max_depth: None,
         }
     }
 }
@@ -380,12 +685,167 @@ pub fn struct_extends(ty: TypeId) -> Result<StructBuilder> {
     })
 }
 
+pub struct PartialStructBuilder {
+    source: TypeId,
+    deep: bool,
+}
+
+/// Struct with the same props as `ty`, all made optional
+#[allow(dead_code)]
+pub fn struct_from_partial(ty: TypeId) -> PartialStructBuilder {
+    PartialStructBuilder {
+        source: ty,
+        deep: false,
+    }
+}
+
+impl PartialStructBuilder {
+    /// Also make nested structs partial
+    #[allow(dead_code)]
+    pub fn deep(&mut self, deep: bool) -> &mut Self {
+        self.deep = deep;
+        self
+    }
+}
+
+/// `visiting` maps the source structs being made partial to whether their
+/// partial is referenced by one of its nested props: the partial is then named
+/// and the reference is a proxy to it.
+fn partial(ty: TypeId, deep: bool, visiting: &mut HashMap<TypeId, bool>) -> Result<TypeId> {
+    let source = source_struct(ty)?;
+    let partial_name = format!("_{}_partial", source.id.0);
+    if let Some(referenced) = visiting.get_mut(&source.id) {
+        *referenced = true;
+        return proxy(partial_name).build();
+    }
+    if let Some(id) = Store::get_type_by_name(&partial_name) {
+        return Ok(id);
+    }
+
+    visiting.insert(source.id, false);
+    let mut builder = struct_();
+    for (name, prop) in source.iter_props() {
+        let concrete_type = prop.attrs()?.concrete_type;
+        let prop = match concrete_type.as_type()? {
+            Type::Optional(inner) => {
+                let item = inner.item().attrs()?.concrete_type;
+                match item.as_type()? {
+                    Type::Struct(_) if deep => optional(partial(item, deep, visiting)?).build()?,
+                    _ => prop,
+                }
+            }
+            Type::Struct(_) if deep => optional(partial(concrete_type, deep, visiting)?).build()?,
+            _ => optional(prop).build()?,
+        };
+        builder.prop(name, prop);
+    }
+    if visiting.remove(&source.id).unwrap_or_default() {
+        builder.named(partial_name);
+    }
+    builder.build()
+}
+
+impl TypeBuilder for PartialStructBuilder {
+    fn build(&self) -> Result<TypeId> {
+        partial(self.source, self.deep, &mut HashMap::new())
+    }
+}
+
+fn source_struct(ty: TypeId) -> Result<Rc<Struct>> {
+    let ty = ty.resolve_proxy()?;
+    match ty.as_type()? {
+        Type::Struct(source) => Ok(source),
+        _ => Err(errors::invalid_input_type(&ty.repr()?)),
+    }
+}
+
+fn check_fields(ty: TypeId, fields: &[&str]) -> Result<()> {
+    let source = source_struct(ty)?;
+    for field in fields {
+        if source.data.get_prop(field).is_none() {
+            return Err(errors::unknown_field(field));
+        }
+    }
+    Ok(())
+}
+
 impl StructBuilder {
+    /// Primary key on multiple fields, for prisma models (`@@id`)
+    #[allow(dead_code)]
+    pub fn composite_id(&mut self, fields: Vec<&str>) -> &mut Self {
+        self.config("composite_id", fields)
+    }
+
+    /// Adds an index on the fields, for prisma models (`@@index`)
+    #[allow(dead_code)]
+    pub fn index(&mut self, fields: Vec<&str>) -> &mut Self {
+        self.push_index("indexes", fields)
+    }
+
+    /// Adds a unique constraint on the fields, for prisma models (`@@unique`)
+    #[allow(dead_code)]
+    pub fn unique_index(&mut self, fields: Vec<&str>) -> &mut Self {
+        self.push_index("unique_indexes", fields)
+    }
+
+    #[allow(dead_code)]
+    fn push_index(&mut self, key: &str, fields: Vec<&str>) -> &mut Self {
+        let config = self.base.runtime_config.get_or_insert_with(Vec::new);
+        let fields = fields.into_iter().map(|f| f.to_string()).collect();
+        match config.iter_mut().find(|(k, _)| k == key) {
+            Some((_, value)) => {
+                let mut indexes: Vec<Vec<String>> = serde_json::from_str(value).unwrap();
+                indexes.push(fields);
+                *value = serde_json::to_string(&indexes).unwrap();
+            }
+            None => {
+                config.push((key.to_string(), serde_json::to_string(&[fields]).unwrap()));
+            }
+        }
+        self
+    }
+
     pub fn prop(&mut self, name: impl Into<String>, ty: TypeId) -> &mut Self {
         self.data.props.push((name.into(), ty.into()));
         self
     }
 
+    /// Property wrapped in an optional
+    #[allow(dead_code)]
+    pub fn prop_optional(&mut self, name: impl Into<String>, ty: TypeId) -> Result<&mut Self> {
+        Ok(self.prop(name, ty.optional()?))
+    }
+
+    /// Property with its own policy chain, set on the property type
+    #[allow(dead_code)]
+    pub fn prop_with_policy(
+        &mut self,
+        name: impl Into<String>,
+        ty: TypeId,
+        chain: Vec<PolicySpec>,
+    ) -> Result<&mut Self> {
+        let ty = crate::Lib::with_policy(TypePolicy {
+            tpe: ty.into(),
+            chain,
+        })?;
+        Ok(self.prop(name, ty.into()))
+    }
+
+    /// Property computed by the materializer on each request instead of
+    /// being stored; it is emitted as a function without arguments
+    #[allow(dead_code)]
+    pub fn prop_resolver(
+        &mut self,
+        name: impl Into<String>,
+        out: TypeId,
+        mat: u32,
+    ) -> Result<&mut Self> {
+        let resolver = func_builder(struct_().build()?, out, mat)
+            .resolver()
+            .build()?;
+        Ok(self.prop(name, resolver))
+    }
+
     pub fn propx(
         &mut self,
         name: impl Into<String>,
@@ -395,6 +855,40 @@ impl StructBuilder {
         Ok(self)
     }
 
+    /// Copies all the props of an existing struct
+    #[allow(dead_code)]
+    pub fn extend(&mut self, ty: TypeId) -> Result<&mut Self> {
+        self.copy_props(ty, |_| true)
+    }
+
+    /// Copies the listed props of an existing struct
+    #[allow(dead_code)]
+    pub fn pick(&mut self, ty: TypeId, fields: Vec<&str>) -> Result<&mut Self> {
+        check_fields(ty, &fields)?;
+        self.copy_props(ty, |name| fields.contains(&name))
+    }
+
+    /// Copies all the props of an existing struct except the listed ones
+    #[allow(dead_code)]
+    pub fn omit(&mut self, ty: TypeId, fields: Vec<&str>) -> Result<&mut Self> {
+        check_fields(ty, &fields)?;
+        self.copy_props(ty, |name| !fields.contains(&name))
+    }
+
+    fn copy_props(&mut self, ty: TypeId, filter: impl Fn(&str) -> bool) -> Result<&mut Self> {
+        let source = source_struct(ty)?;
+        for (name, prop) in source.data.props.iter() {
+            if !filter(name) {
+                continue;
+            }
+            if self.data.props.iter().any(|(k, _)| k == name) {
+                return Err(errors::duplicate_key(name));
+            }
+            self.data.props.push((name.clone(), *prop));
+        }
+        Ok(self)
+    }
+
     #[allow(dead_code)]
     pub fn props(&mut self, props: impl IntoIterator<Item = (String, TypeId)>) {
         self.data
@@ -411,11 +905,24 @@ impl StructBuilder {
         self.data.max = Some(max);
         self
     }
+
+    /// Limits the number of declared properties
+    #[allow(dead_code)]
+    pub fn max_props(&mut self, max: u32) -> &mut Self {
+        self.data.max_props = Some(max);
+        self
+    }
+
+    /// Recursion depth hint for the gateway, on self-referential types
+    #[allow(dead_code)]
+    pub fn max_depth(&mut self, depth: u32) -> &mut Self {
+        self.data.max_depth = Some(depth);
+        self
+    }
 }
 
 #[derive(Default)]
 pub struct FuncBuilder {
-    #[allow(dead_code)]
     base: TypeBase,
     data: TypeFunc,
 }
@@ -428,12 +935,13 @@ impl Default for TypeFunc {
             mat: u32::max_value(),
             rate_calls: false,
             rate_weight: None,
+            resolver: false,
+            auto_wrap_input: false,
         }
     }
 }
 
-#[allow(dead_code)]
-pub fn func(inp: TypeId, out: TypeId, mat: u32) -> Result<TypeId> {
+pub fn func_builder(inp: TypeId, out: TypeId, mat: u32) -> FuncBuilder {
     FuncBuilder {
         data: TypeFunc {
             inp: inp.into(),
@@ -443,7 +951,36 @@ pub fn func(inp: TypeId, out: TypeId, mat: u32) -> Result<TypeId> {
         },
         ..Default::default()
     }
-    .build()
+}
+
+#[allow(dead_code)]
+impl FuncBuilder {
+    pub fn rate_weight(&mut self, weight: u32) -> &mut Self {
+        self.data.rate_weight = Some(weight);
+        self
+    }
+
+    pub fn rate_calls(&mut self, rate_calls: bool) -> &mut Self {
+        self.data.rate_calls = rate_calls;
+        self
+    }
+
+    /// Marks the function as a computed field of a struct
+    pub fn resolver(&mut self) -> &mut Self {
+        self.data.resolver = true;
+        self
+    }
+
+    /// Wraps a non-struct input as the `_arg` field of a struct
+    pub fn auto_wrap_input(&mut self) -> &mut Self {
+        self.data.auto_wrap_input = true;
+        self
+    }
+}
+
+#[allow(dead_code)]
+pub fn func(inp: TypeId, out: TypeId, mat: u32) -> Result<TypeId> {
+    func_builder(inp, out, mat).build()
 }
 
 #[derive(Default)]
@@ -477,6 +1014,52 @@ pub fn proxy(name: impl Into<String>) -> ProxyBuilder {
     }
 }
 
+/// Type whose definition is deferred until [`resolve_lazy`], so that it can
+/// refer to types defined afterwards
+#[allow(dead_code)]
+pub fn lazy(build: impl LazyTypeBuild + 'static) -> Result<TypeId> {
+    proxy(Store::register_lazy_type(build)).build()
+}
+
+/// Builds the pending lazy types, which cannot be used before.
+/// This is also done when exposing functions.
+#[allow(dead_code)]
+pub fn resolve_lazy() -> Result<()> {
+    Store::resolve_lazy_types()
+}
+
+/// Scalar type specification for [`crate::Lib::build_struct_batch`]
+pub enum TypeBuilderSpec {
+    Boolean(TypeBase),
+    Integer(TypeInteger, TypeBase),
+    Float(TypeFloat, TypeBase),
+    String(TypeString, TypeBase),
+}
+
+impl From<BooleanBuilder> for TypeBuilderSpec {
+    fn from(builder: BooleanBuilder) -> Self {
+        Self::Boolean(builder.base)
+    }
+}
+
+impl From<IntegerBuilder> for TypeBuilderSpec {
+    fn from(builder: IntegerBuilder) -> Self {
+        Self::Integer(builder.data, builder.base)
+    }
+}
+
+impl From<FloatBuilder> for TypeBuilderSpec {
+    fn from(builder: FloatBuilder) -> Self {
+        Self::Float(builder.data, builder.base)
+    }
+}
+
+impl From<StringBuilder> for TypeBuilderSpec {
+    fn from(builder: StringBuilder) -> Self {
+        Self::String(builder.data, builder.base)
+    }
+}
+
 macro_rules! impl_type_builder {
     ( $ty:ty, $build:ident ) => {
         impl TypeBuilder for $ty {
@@ -515,13 +1098,107 @@ impl ConcreteTypeBuilder for BooleanBuilder {
     }
 }
 
+impl TypeBuilder for JsonBuilder {
+    fn build(&self) -> Result<TypeId> {
+        Ok(crate::Lib::jsonb(self.base.clone())?.into())
+    }
+}
+
+impl ConcreteTypeBuilder for JsonBuilder {
+    fn base_mut(&mut self) -> &mut TypeBase {
+        &mut self.base
+    }
+}
+
 impl_type_builder!(IntegerBuilder, integerb);
 impl_type_builder!(FloatBuilder, floatb);
 impl_type_builder!(OptionalBuilder, optionalb);
 impl_type_builder!(StringBuilder, stringb);
 impl_type_builder!(ArrayBuilder, arrayb);
+impl_type_builder!(MapBuilder, mapb);
+impl_type_builder!(CustomScalarBuilder, scalarb);
 impl_type_builder!(UnionBuilder, unionb);
 impl_type_builder!(EitherBuilder, eitherb);
 impl_type_builder!(StructBuilder, structb);
-impl_type_builder!(FuncBuilder, funcb, true);
+impl_type_builder!(FuncBuilder, funcb);
 impl_type_builder!(ProxyBuilder, proxyb, true);
+
+impl ScalarTypeBuilder for BooleanBuilder {}
+impl ScalarTypeBuilder for IntegerBuilder {}
+impl ScalarTypeBuilder for FloatBuilder {}
+impl ScalarTypeBuilder for StringBuilder {}
+impl ScalarTypeBuilder for CustomScalarBuilder {}
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::types::{Type, TypeId};
+    use crate::wit::core::Core;
+    use crate::Lib;
+
+    #[test]
+    fn test_frozen_type() -> Result<(), String> {
+        Store::reset()?;
+        let mut builder = t::integer().min(0);
+        builder.named("Age");
+        let age = builder.freeze();
+        let id = age.build()?;
+        // shared: built only once
+        assert_eq!(age.build()?, id);
+        assert_eq!(id.repr()?, format!("integer(#{}, min=0)", id.0));
+        assert!(id.is_frozen()?);
+
+        let Type::Integer(integer) = id.as_type()? else {
+            panic!("expected an integer");
+        };
+        let res = integer.constrained(|_, data| data.max = Some(120));
+        assert_eq!(res, Err(errors::type_frozen("Age")));
+
+        // the copies are not frozen
+        let copy: TypeId = Lib::duplicate_type(id.into())?.into();
+        assert!(!copy.is_frozen()?);
+        let Type::Integer(copy) = copy.as_type()? else {
+            panic!("expected an integer");
+        };
+        let bounded = copy.constrained(|_, data| data.max = Some(120))?;
+        assert_eq!(
+            bounded.repr()?,
+            format!("integer(#{}, min=0, max=120)", bounded.0)
+        );
+        let renamed: TypeId = Lib::rename_type(id.into(), "Years".to_string())?.into();
+        assert!(!renamed.is_frozen()?);
+        assert_eq!(renamed.type_name()?, Some("Years".to_string()));
+        assert!(id.is_frozen()?);
+
+        // not frozen: the builder is not shared
+        let other = t::integer().min(0).build()?;
+        assert!(!other.is_frozen()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_frozen_type_in_typegraphs() -> Result<(), String> {
+        Store::reset()?;
+        let mut builder = t::integer().min(0);
+        builder.named("Age");
+        let age = builder.freeze();
+
+        for name in ["first", "second"] {
+            setup(Some(name))?;
+            // may take the slot of the type from the previous typegraph
+            t::string().build()?;
+            let id = age.build()?;
+            assert!(id.is_frozen()?);
+            assert_eq!(id.repr()?, format!("integer(#{}, min=0)", id.0));
+            assert_eq!(age.build()?, id);
+            let func = t::func(t::struct_().prop("age", id).build()?, id, dummy_mat()?)?;
+            Lib::expose(vec![("one".to_string(), func.into())], None)?;
+            Lib::finalize_typegraph()?;
+            assert_eq!(Store::get_type_by_name("Age"), None);
+        }
+        Ok(())
+    }
+}
@@ -1,15 +1,23 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
+use crate::wit::core::Cors;
 pub(crate) use crate::wit::runtimes::{Effect, MaterializerDenoFunc};
+use crate::TypegraphInitParams;
 
 impl MaterializerDenoFunc {
     pub fn with_code(code: impl Into<String>) -> Self {
         Self {
             code: code.into(),
             secrets: vec![],
+            timeout_sec: None,
         }
     }
+
+    pub fn timeout(mut self, secs: u32) -> Self {
+        self.timeout_sec = Some(secs);
+        self
+    }
 }
 
 impl Default for Effect {
@@ -18,6 +26,31 @@ impl Default for Effect {
     }
 }
 
+impl Default for TypegraphInitParams {
+    fn default() -> Self {
+        Self {
+            name: "".to_string(),
+            dynamic: None,
+            folder: None,
+            path: ".".to_string(),
+            query_extensions: None,
+            dry_run: false,
+            strict: false,
+            prefix: None,
+            cors: Cors {
+                allow_origin: vec![],
+                allow_headers: vec![],
+                expose_headers: vec![],
+                allow_methods: vec![],
+                allow_credentials: false,
+                max_age_sec: None,
+            },
+            auths: vec![],
+            rate: None,
+        }
+    }
+}
+
 pub mod models {
     use crate::errors::Result;
     use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
@@ -31,7 +64,7 @@ pub mod models {
                 t::string()
                     .as_id(true)
                     .format("uuid")
-                    .config("auto", "true")
+                    .config("auto", true)
                     .build()?,
             )
             .prop("name", t::string().build()?)
@@ -48,10 +81,7 @@ pub mod models {
             .build()?;
 
         let post = t::struct_()
-            .prop(
-                "id",
-                t::integer().as_id(true).config("auto", "true").build()?,
-            )
+            .prop("id", t::integer().as_id(true).config("auto", true).build()?)
             .prop("title", t::string().build()?)
             .prop("author", t::proxy("User").build()?)
             .named("Post")
@@ -73,6 +103,27 @@ pub fn setup(name: Option<&str>) -> crate::errors::Result<()> {
     })
 }
 
+/// Registers the placeholder deno materializer most test functions resolve with.
+pub fn dummy_mat() -> crate::errors::Result<crate::wit::core::MaterializerId> {
+    use crate::wit::runtimes::Runtimes;
+
+    crate::Lib::register_deno_func(MaterializerDenoFunc::with_code("() => 12"), Effect::None)
+}
+
+/// Starts from an empty store, with the placeholder materializer registered.
+pub fn reset_with_mat() -> crate::errors::Result<crate::wit::core::MaterializerId> {
+    crate::global_store::Store::reset()?;
+    dummy_mat()
+}
+
+/// Exposes `func` as the only root function of a new `test` typegraph.
+pub fn expose_one(name: &str, func: crate::types::TypeId) -> crate::errors::Result<()> {
+    use crate::wit::core::Core;
+
+    setup(None)?;
+    crate::Lib::expose(vec![(name.to_string(), func.into())], None)
+}
+
 pub mod tree {
     use std::{borrow::Cow, io::Write, rc::Rc};
 
@@ -168,7 +219,9 @@ pub mod tree {
                     | Type::Float(_)
                     | Type::String(_)
                     | Type::File(_)
-                    | Type::Boolean(_) => Cow::Owned(vec![]),
+                    | Type::Boolean(_)
+                    | Type::Json(_)
+                    | Type::Scalar(_) => Cow::Owned(vec![]),
                     Type::Struct(ty) => Cow::Owned(
                         ty.data
                             .props
@@ -197,6 +250,18 @@ pub mod tree {
                         type_id: ty.data.of.into(),
                         parents: parents,
                     }]),
+                    Type::Map(ty) => Cow::Owned(vec![
+                        Node {
+                            label: "key".to_string(),
+                            type_id: ty.data.key.into(),
+                            parents: Rc::clone(&parents),
+                        },
+                        Node {
+                            label: "value".to_string(),
+                            type_id: ty.data.value.into(),
+                            parents,
+                        },
+                    ]),
                     Type::Optional(ty) => Cow::Owned(vec![Node {
                         label: "item".to_string(),
                         type_id: ty.data.of.into(),
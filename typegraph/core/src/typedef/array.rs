@@ -4,7 +4,7 @@
 use common::typegraph::{ArrayTypeData, TypeNode};
 
 use crate::{
-    conversion::types::{gen_base, TypeConversion},
+    conversion::types::{expect_runtime, gen_base, TypeConversion},
     errors::Result,
     typegraph::TypegraphContext,
     types::{Array, TypeData, TypeId},
@@ -20,8 +20,9 @@ impl TypeConversion for Array {
                     .clone()
                     .unwrap_or_else(|| format!("array_{}", self.id.0)),
                 self.base.runtime_config.clone(),
-                runtime_id.unwrap(),
+                expect_runtime(runtime_id, self.id)?,
             )
+            .deprecated(self.base.deprecated.clone())
             .build(),
             data: ArrayTypeData {
                 items: ctx
@@ -55,3 +56,51 @@ impl TypeData for TypeArray {
 
     super::impl_into_type!(concrete, Array);
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::types::Type;
+
+    #[test]
+    fn test_chained_array() -> Result<(), String> {
+        Store::reset()?;
+        // the item type is registered right before the array
+        let list = t::string().array()?;
+        assert_eq!(
+            list.repr()?,
+            format!("array(#{}, items={})", list.0, list.0 - 1)
+        );
+
+        let sized = t::string().array_of(1, 3)?;
+        assert_eq!(
+            sized.repr()?,
+            format!(
+                "array(#{}, items={}, minItems=1, maxItems=3)",
+                sized.0,
+                sized.0 - 1
+            )
+        );
+        assert_eq!(t::string().array_of(3, 1), Err(errors::invalid_max_value()));
+
+        let named = t::string().named("Tag").array()?;
+        assert_eq!(named.type_name()?, Some(format!("_{}_Tag[]", named.0)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_array_length() -> Result<(), String> {
+        let int = t::integer().build()?;
+        let triple = t::array(int).length(3).build()?;
+        let Type::Array(triple) = triple.as_type()? else {
+            panic!("expected an array");
+        };
+        assert_eq!((triple.data.min, triple.data.max), (Some(3), Some(3)));
+
+        let res = t::array(int).min(3).max(2).build();
+        assert_eq!(res, Err(errors::invalid_max_value()));
+        Ok(())
+    }
+}
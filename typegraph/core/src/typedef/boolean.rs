@@ -5,7 +5,7 @@ use common::typegraph::TypeNode;
 use errors::Result;
 
 use crate::{
-    conversion::types::{gen_base, TypeConversion},
+    conversion::types::{expect_runtime, gen_base, TypeConversion},
     errors,
     typegraph::TypegraphContext,
     types::{Boolean, TypeBoolean, TypeData},
@@ -20,8 +20,10 @@ impl TypeConversion for Boolean {
                     .clone()
                     .unwrap_or_else(|| format!("boolean_{}", self.id.0)),
                 self.base.runtime_config.clone(),
-                runtime_id.unwrap(),
+                expect_runtime(runtime_id, self.id)?,
             )
+            .examples(self.base.examples.clone())
+            .deprecated(self.base.deprecated.clone())
             .build(),
         })
     }
@@ -3,17 +3,21 @@
 
 use common::typegraph::{EitherTypeData, TypeNode};
 use errors::Result;
+use std::collections::{BTreeSet, HashSet};
 
 use crate::{
-    conversion::types::{gen_base, TypeConversion},
-    errors,
+    conversion::types::{expect_runtime, gen_base, TypeConversion},
+    errors, log,
     typegraph::TypegraphContext,
-    types::{Either, TypeData, TypeId},
+    types::{Either, Type, TypeData, TypeId},
     wit::core::TypeEither,
 };
 
 impl TypeConversion for Either {
     fn convert(&self, ctx: &mut TypegraphContext, runtime_id: Option<u32>) -> Result<TypeNode> {
+        self.check_exclusive_variants()?;
+        let variants = self.flat_variants()?;
+
         Ok(TypeNode::Either {
             base: gen_base(
                 self.base
@@ -21,24 +25,61 @@ impl TypeConversion for Either {
                     .clone()
                     .unwrap_or_else(|| format!("either_{}", self.id.0)),
                 self.base.runtime_config.clone(),
-                runtime_id.unwrap(),
+                expect_runtime(runtime_id, self.id)?,
             )
+            .deprecated(self.base.deprecated.clone())
             .build(),
             data: EitherTypeData {
-                one_of: self
-                    .data
-                    .variants
-                    .iter()
-                    .map(|&vid| -> Result<_> {
-                        let id = TypeId(vid).resolve_proxy()?;
-                        Ok(ctx.register_type(id, runtime_id)?.into())
-                    })
+                one_of: variants
+                    .into_iter()
+                    .map(|id| -> Result<_> { Ok(ctx.register_type(id, runtime_id)?.into()) })
                     .collect::<Result<Vec<_>>>()?,
             },
         })
     }
 }
 
+impl Either {
+    /// Variants with the nested eithers inlined
+    pub(crate) fn flat_variants(&self) -> Result<Vec<TypeId>> {
+        let mut variants = vec![];
+        for vid in self.data.variants.iter() {
+            let id = TypeId(*vid).resolve_proxy()?;
+            match id.as_type()? {
+                Type::Either(inner) => variants.extend(inner.flat_variants()?),
+                _ => variants.push(id),
+            }
+        }
+        let mut seen = HashSet::new();
+        variants.retain(|id| seen.insert(*id));
+        Ok(variants)
+    }
+
+    /// Warns about struct variants that a single value could match
+    fn check_exclusive_variants(&self) -> Result<()> {
+        let mut required_sets: Vec<(TypeId, BTreeSet<String>)> = vec![];
+        for variant in self.flat_variants()? {
+            let concrete_type = variant.attrs()?.concrete_type;
+            let Type::Struct(inner) = concrete_type.as_type()? else {
+                continue;
+            };
+            let required: BTreeSet<_> = inner.required_props()?.into_iter().collect();
+            for (other, other_required) in required_sets.iter() {
+                if *other_required == required {
+                    log!(
+                        "warning: variants #{} and #{} of either #{} have the same required fields",
+                        other.0,
+                        variant.0,
+                        self.id.0,
+                    );
+                }
+            }
+            required_sets.push((variant, required));
+        }
+        Ok(())
+    }
+}
+
 impl TypeData for TypeEither {
     fn get_display_params_into(&self, params: &mut Vec<String>) {
         for (i, tpe_id) in self.variants.iter().enumerate() {
@@ -52,3 +93,45 @@ impl TypeData for TypeEither {
 
     super::impl_into_type!(concrete, Either);
 }
+
+#[cfg(test)]
+mod test {
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::core::Core;
+    use crate::Lib;
+
+    #[test]
+    fn test_either_overlapping_variants() -> Result<(), String> {
+        Store::reset()?;
+        let a = t::struct_()
+            .propx("id", t::integer())?
+            .propx("name", t::string())?
+            .build()?;
+        let b = t::struct_()
+            .propx("id", t::integer())?
+            .propx("name", t::string())?
+            .propx("nickname", t::optionalx(t::string())?)?
+            .build()?;
+        let out = t::either([a, b]).build()?;
+
+        let mat = dummy_mat()?;
+        let inp = t::struct_().build()?;
+        expose_one("one", t::func(inp, out, mat)?)?;
+        Lib::finalize_typegraph()?;
+
+        let warnings = crate::host::abi::take_logs()
+            .into_iter()
+            .filter(|msg| msg.starts_with("warning:"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            warnings,
+            vec![format!(
+                "warning: variants #{} and #{} of either #{} have the same required fields",
+                a.0, b.0, out.0
+            )]
+        );
+        Ok(())
+    }
+}
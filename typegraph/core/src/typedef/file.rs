@@ -3,7 +3,7 @@
 
 use common::typegraph::{FileTypeData, TypeNode};
 
-use crate::conversion::types::{gen_base, TypeConversion};
+use crate::conversion::types::{expect_runtime, gen_base, TypeConversion};
 use crate::errors::Result;
 use crate::typegraph::TypegraphContext;
 use crate::types::{File, TypeData};
@@ -43,9 +43,10 @@ impl TypeConversion for File {
                     .clone()
                     .unwrap_or_else(|| format!("file_{}", self.id.0)),
                 self.base.runtime_config.clone(),
-                runtime_id.unwrap(),
+                expect_runtime(runtime_id, self.id)?,
             )
             .id(self.base.as_id)
+            .deprecated(self.base.deprecated.clone())
             .build(),
             data: FileTypeData {
                 min_size: self.data.min,
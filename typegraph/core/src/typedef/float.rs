@@ -5,7 +5,7 @@ use common::typegraph::types::{FloatTypeData, TypeNode};
 use errors::Result;
 
 use crate::{
-    conversion::types::{gen_base, TypeConversion},
+    conversion::types::{expect_runtime, gen_base, TypeConversion},
     errors,
     typegraph::TypegraphContext,
     types::{Float, TypeData},
@@ -26,9 +26,11 @@ impl TypeConversion for Float {
                     .clone()
                     .unwrap_or_else(|| format!("float_{}", self.id.0)),
                 self.base.runtime_config.clone(),
-                runtime_id.unwrap(),
+                expect_runtime(runtime_id, self.id)?,
             )
             .enum_(enumeration)
+            .examples(self.base.examples.clone())
+            .deprecated(self.base.deprecated.clone())
             .build(),
             data: FloatTypeData {
                 minimum: self.data.min,
@@ -50,10 +52,10 @@ impl TypeData for TypeFloat {
             params.push(format!("max={}", max));
         }
         if let Some(exclusive_minimum) = self.exclusive_minimum {
-            params.push(format!("exclusiveMin={}", exclusive_minimum));
+            params.push(format!("x_min={}", exclusive_minimum));
         }
         if let Some(exclusive_maximum) = self.exclusive_maximum {
-            params.push(format!("exclusiveMax={}", exclusive_maximum));
+            params.push(format!("x_max={}", exclusive_maximum));
         }
         if let Some(multiple_of) = self.multiple_of {
             params.push(format!("multipleOf={}", multiple_of));
@@ -5,7 +5,7 @@ use common::typegraph::{FunctionTypeData, TypeNode};
 use errors::Result;
 
 use crate::{
-    conversion::types::{gen_base, TypeConversion},
+    conversion::types::{check_output_unions, gen_base, TypeConversion},
     errors,
     typegraph::TypegraphContext,
     types::{Func, Type, TypeData, TypeId},
@@ -27,6 +27,7 @@ impl TypeConversion for Func {
         .into();
 
         let out_id = TypeId(self.data.out).resolve_proxy()?;
+        check_output_unions(out_id)?;
         let output = ctx.register_type(out_id, Some(runtime_id))?.into();
 
         Ok(TypeNode::Function {
@@ -38,6 +39,7 @@ impl TypeConversion for Func {
                 self.base.runtime_config.clone(),
                 runtime_id,
             )
+            .deprecated(self.base.deprecated.clone())
             .build(),
             data: FunctionTypeData {
                 input,
@@ -61,3 +63,122 @@ impl TypeData for TypeFunc {
 
     super::impl_into_type!(concrete, Func);
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::types::{Type, TypeId};
+    use crate::wit::core::Core;
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+
+    #[test]
+    fn test_deprecated() -> Result<(), String> {
+        Store::reset()?;
+        let inp = t::struct_()
+            .propx("id", t::integer())?
+            .propx(
+                "name",
+                t::string()
+                    .deprecated("use fullName")
+                    .deprecated("use displayName"),
+            )?
+            .build()?;
+
+        let mat = dummy_mat()?;
+        let get = t::func_builder(inp, t::integer().build()?, mat)
+            .deprecated("use getUser")
+            .build()?;
+        expose_one("get", get)?;
+        let typegraph = Lib::finalize_typegraph()?;
+        insta::assert_snapshot!(typegraph);
+        Ok(())
+    }
+
+    #[test]
+    fn test_func_in_input() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let int = t::integer().build()?;
+        let callback = t::func(t::struct_().build()?, int, mat)?;
+        let inp = t::struct_()
+            .prop("id", int)
+            .propx(
+                "options",
+                t::optionalx(t::struct_().prop("callback", callback))?,
+            )?
+            .build()?;
+        assert_eq!(
+            t::func(inp, int, mat),
+            Err(errors::func_in_input("options.callback"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_func_as_output() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let inp = t::struct_().build()?;
+        let int = t::integer().build()?;
+        let inner = t::func(inp, int, mat)?;
+        assert_eq!(t::func(inp, inner, mat), Err(errors::func_as_output("")));
+
+        let out = t::arrayx(t::struct_().prop("id", int).prop("next", inner))?.build()?;
+        assert_eq!(t::func(inp, out, mat), Err(errors::func_as_output("next")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_rate_weight() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let inp = t::struct_().build()?;
+        let out = t::integer().build()?;
+        assert_eq!(
+            t::func_builder(inp, out, mat).rate_weight(0).build(),
+            Err(errors::invalid_rate_weight())
+        );
+
+        let func = t::func_builder(inp, out, mat)
+            .rate_weight(5)
+            .rate_calls(true)
+            .build()?;
+        expose_one("expensive", func)?;
+        let typegraph = Lib::finalize_typegraph()?;
+        insta::assert_snapshot!(typegraph);
+        Ok(())
+    }
+
+    #[test]
+    fn test_auto_wrap_input() -> Result<(), String> {
+        Store::reset()?;
+        let by_id = t::union([
+            t::struct_().propx("id", t::integer())?.build()?,
+            t::struct_().propx("email", t::string())?.build()?,
+        ])
+        .build()?;
+        let mat =
+            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => true"), Effect::None)?;
+        let out = t::boolean().build()?;
+
+        // rejected by default
+        assert_eq!(
+            t::func(by_id, out, mat),
+            Err(errors::invalid_input_type(&by_id.repr()?))
+        );
+
+        let find_user = t::func_builder(by_id, out, mat).auto_wrap_input().build()?;
+        assert!(crate::host::abi::take_logs()
+            .iter()
+            .any(|log| log.starts_with("note: ") && log.contains("`_arg`")));
+        let Type::Func(f) = find_user.as_type()? else {
+            panic!("expected a func");
+        };
+        let Type::Struct(inp) = TypeId(f.data.inp).as_type()? else {
+            panic!("expected a struct input");
+        };
+        assert_eq!(inp.iter_props().collect::<Vec<_>>(), vec![("_arg", by_id)]);
+        Ok(())
+    }
+}
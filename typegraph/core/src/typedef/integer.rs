@@ -5,7 +5,7 @@ use common::typegraph::{IntegerTypeData, TypeNode};
 use errors::Result;
 
 use crate::{
-    conversion::types::{gen_base, TypeConversion},
+    conversion::types::{expect_runtime, gen_base, TypeConversion},
     errors,
     typegraph::TypegraphContext,
     types::{Integer, TypeData},
@@ -27,10 +27,12 @@ impl TypeConversion for Integer {
                     .clone()
                     .unwrap_or_else(|| format!("integer_{}", self.id.0)),
                 self.base.runtime_config.clone(),
-                runtime_id.unwrap(),
+                expect_runtime(runtime_id, self.id)?,
             )
             .enum_(enumeration)
+            .examples(self.base.examples.clone())
             .id(self.base.as_id)
+            .deprecated(self.base.deprecated.clone())
             .build(),
             data: IntegerTypeData {
                 minimum: self.data.min,
@@ -52,10 +54,10 @@ impl TypeData for TypeInteger {
             params.push(format!("max={}", max));
         }
         if let Some(exclusive_minimum) = self.exclusive_minimum {
-            params.push(format!("exclusiveMin={}", exclusive_minimum));
+            params.push(format!("x_min={}", exclusive_minimum));
         }
         if let Some(exclusive_maximum) = self.exclusive_maximum {
-            params.push(format!("exclusiveMax={}", exclusive_maximum));
+            params.push(format!("x_max={}", exclusive_maximum));
         }
         if let Some(multiple_of) = self.multiple_of {
             params.push(format!("multipleOf={}", multiple_of));
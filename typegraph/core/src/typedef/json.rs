@@ -0,0 +1,71 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use common::typegraph::TypeNode;
+use errors::Result;
+
+use crate::{
+    conversion::types::{expect_runtime, gen_base, TypeConversion},
+    errors,
+    typegraph::TypegraphContext,
+    types::{Json, TypeData, TypeJson},
+};
+
+impl TypeConversion for Json {
+    fn convert(&self, _ctx: &mut TypegraphContext, runtime_id: Option<u32>) -> Result<TypeNode> {
+        Ok(TypeNode::Any {
+            base: gen_base(
+                self.base
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("json_{}", self.id.0)),
+                self.base.runtime_config.clone(),
+                expect_runtime(runtime_id, self.id)?,
+            )
+            .examples(self.base.examples.clone())
+            .deprecated(self.base.deprecated.clone())
+            .build(),
+        })
+    }
+}
+
+impl TypeData for TypeJson {
+    fn get_display_params_into(&self, _params: &mut Vec<String>) {}
+
+    fn variant_name(&self) -> String {
+        "json".to_string()
+    }
+
+    super::impl_into_type!(concrete, Json);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::Lib;
+    use common::typegraph::TypeNode;
+
+    #[test]
+    fn test_json() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let json = t::json().build()?;
+        assert_eq!(json.repr()?, format!("json(#{})", json.0));
+
+        let res = t::func(json, json, mat);
+        assert_eq!(res, Err(errors::invalid_input_type(&json.repr()?)));
+
+        let inp = t::struct_().prop("payload", json).build()?;
+        expose_one("one", t::func(inp, json, mat)?)?;
+        let typegraph = Lib::finalize_typegraph_struct()?;
+        let node = typegraph
+            .types
+            .iter()
+            .find(|t| matches!(t, TypeNode::Any { .. }))
+            .unwrap();
+        let serialized = serde_json::to_value(node).map_err(|e| e.to_string())?;
+        assert_eq!(serialized["type"], "any");
+        Ok(())
+    }
+}
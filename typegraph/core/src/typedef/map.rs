@@ -0,0 +1,127 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use common::typegraph::{MapTypeData, TypeNode};
+
+use crate::{
+    conversion::types::{expect_runtime, gen_base, TypeConversion},
+    errors::{self, Result},
+    typegraph::TypegraphContext,
+    types::{Map, Type, TypeData, TypeId},
+    wit::core::TypeMap,
+};
+
+impl TypeConversion for Map {
+    fn convert(&self, ctx: &mut TypegraphContext, runtime_id: Option<u32>) -> Result<TypeNode> {
+        Ok(TypeNode::Map {
+            base: gen_base(
+                self.base
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("map_{}", self.id.0)),
+                self.base.runtime_config.clone(),
+                expect_runtime(runtime_id, self.id)?,
+            )
+            .deprecated(self.base.deprecated.clone())
+            .build(),
+            data: MapTypeData {
+                key: ctx
+                    .register_type(TypeId(self.data.key).resolve_proxy()?, runtime_id)?
+                    .into(),
+                value: ctx
+                    .register_type(TypeId(self.data.value).resolve_proxy()?, runtime_id)?
+                    .into(),
+            },
+        })
+    }
+}
+
+impl TypeMap {
+    /// Keys must be strings, possibly restricted to an enum
+    pub fn validate(&self) -> Result<()> {
+        let key = TypeId(self.key);
+        match key.attrs()?.concrete_type.as_type()? {
+            Type::String(_) => Ok(()),
+            _ => Err(errors::invalid_map_key(&key.repr()?)),
+        }
+    }
+}
+
+impl TypeData for TypeMap {
+    fn get_display_params_into(&self, params: &mut Vec<String>) {
+        params.push(format!("key={}", self.key));
+        params.push(format!("value={}", self.value));
+    }
+
+    fn variant_name(&self) -> String {
+        "map".to_string()
+    }
+
+    super::impl_into_type!(concrete, Map);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::core::Core;
+    use crate::Lib;
+    use common::typegraph::TypeNode;
+
+    #[test]
+    fn test_map() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let key = t::string().build()?;
+        let value = t::integer().build()?;
+        let map = t::map(key, value).build()?;
+        assert_eq!(
+            map.repr()?,
+            format!("map(#{}, key={}, value={})", map.0, key.0, value.0)
+        );
+
+        let int = t::integer().build()?;
+        let res = t::map(int, int).build();
+        assert_eq!(res, Err(errors::invalid_map_key(&int.repr()?)));
+
+        let inp = t::struct_().prop("scores", map).build()?;
+        expose_one("one", t::func(inp, map, mat)?)?;
+        let typegraph = Lib::finalize_typegraph_struct()?;
+        let node = typegraph
+            .types
+            .iter()
+            .find_map(|t| match t {
+                TypeNode::Map { data, .. } => Some(data),
+                _ => None,
+            })
+            .unwrap();
+        assert!(matches!(
+            typegraph.types[node.key as usize],
+            TypeNode::String { .. }
+        ));
+        assert!(matches!(
+            typegraph.types[node.value as usize],
+            TypeNode::Integer { .. }
+        ));
+
+        let key = t::string()
+            .enum_(vec!["a".to_string(), "b".to_string()])
+            .build()?;
+        let map = t::map(key, value).build()?;
+        let valid = t::optional(map)
+            .default_item(serde_json::json!({ "a": 1 }))
+            .build()?;
+        let inp = t::struct_().prop("scores", valid).build()?;
+        expose_one("one", t::func(inp, map, mat)?)?;
+        let invalid = t::optional(map)
+            .default_item(serde_json::json!({ "c": 1 }))
+            .build()?;
+        let inp = t::struct_().prop("scores", invalid).build()?;
+        let res = Lib::expose(
+            vec![("two".to_string(), t::func(inp, map, mat)?.into())],
+            None,
+        );
+        assert_eq!(res, Err(errors::invalid_default_value(&invalid.repr()?)));
+        Ok(())
+    }
+}
@@ -8,8 +8,11 @@ pub mod file;
 pub mod float;
 pub mod func;
 pub mod integer;
+pub mod json;
+pub mod map;
 pub mod optional;
 pub mod proxy;
+pub mod scalar;
 pub mod string;
 pub mod struct_;
 pub mod union;
@@ -23,13 +26,12 @@ macro_rules! impl_into_type {
             type_id: $crate::types::TypeId,
             base: Option<$crate::wit::core::TypeBase>,
         ) -> $crate::errors::Result<$crate::types::Type> {
-            Ok($crate::types::Type::$variant(std::rc::Rc::new(
-                $crate::types::ConcreteType {
-                    id: type_id,
-                    base: base
-                        .ok_or_else(|| $crate::errors::base_required(stringify!($variant)))?,
-                    data: self,
-                },
+            Ok($crate::types::Type::$variant($crate::sync::Rc::new(
+                $crate::types::ConcreteType::new(
+                    type_id,
+                    base.ok_or_else(|| $crate::errors::base_required(stringify!($variant)))?,
+                    self,
+                ),
             )))
         }
     };
@@ -43,7 +45,7 @@ macro_rules! impl_into_type {
             if base.is_some() {
                 Err($crate::errors::base_not_allowed(stringify!($variant)))
             } else {
-                Ok($crate::types::Type::$variant(std::rc::Rc::new(
+                Ok($crate::types::Type::$variant($crate::sync::Rc::new(
                     $crate::types::WrapperType {
                         id: type_id,
                         data: self,
@@ -1,22 +1,43 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
-use common::typegraph::{OptionalTypeData, TypeNode};
+use common::typegraph::{OptionalKind, OptionalTypeData, TypeNode};
 use errors::Result;
 
 use crate::{
-    conversion::types::{gen_base, TypeConversion},
+    conversion::types::{expect_runtime, gen_base, TypeConversion},
     errors,
     typegraph::TypegraphContext,
     types::{Optional, TypeData, TypeId},
-    wit::core::TypeOptional,
+    validation::types::validate_value,
+    wit::core::{OptionalKind as WitOptionalKind, TypeOptional},
 };
 
+impl From<WitOptionalKind> for OptionalKind {
+    fn from(value: WitOptionalKind) -> Self {
+        match value {
+            WitOptionalKind::Absent => OptionalKind::Absent,
+            WitOptionalKind::Nullable => OptionalKind::Nullable,
+        }
+    }
+}
+
 impl TypeConversion for Optional {
     fn convert(&self, ctx: &mut TypegraphContext, runtime_id: Option<u32>) -> Result<TypeNode> {
         let default_value = match self.data.default_item.clone() {
             Some(value) => {
-                let ret = serde_json::from_str(&value).map_err(|s| s.to_string())?;
+                let ret: serde_json::Value =
+                    serde_json::from_str(&value).map_err(|s| s.to_string())?;
+                let valid = match ret {
+                    // explicit null default, kept distinct from no default
+                    serde_json::Value::Null => {
+                        !matches!(self.data.kind, Some(WitOptionalKind::Absent))
+                    }
+                    _ => validate_value(ret.clone(), self.item(), "<default>".to_string()).is_ok(),
+                };
+                if !valid {
+                    return Err(errors::invalid_default_value(&self.id.repr()?));
+                }
                 Some(ret)
             }
             None => None,
@@ -29,14 +50,17 @@ impl TypeConversion for Optional {
                     .clone()
                     .unwrap_or_else(|| format!("optional_{}", self.id.0)),
                 self.base.runtime_config.clone(),
-                runtime_id.unwrap(),
+                expect_runtime(runtime_id, self.id)?,
             )
+            .deprecated(self.base.deprecated.clone())
             .build(),
             data: OptionalTypeData {
                 item: ctx
                     .register_type(TypeId(self.data.of).resolve_proxy()?, runtime_id)?
                     .into(),
+                null_default: default_value == Some(serde_json::Value::Null),
                 default_value,
+                kind: self.data.kind.map(|kind| kind.into()),
             },
         })
     }
@@ -54,6 +78,11 @@ impl TypeData for TypeOptional {
         if let Some(default) = self.default_item.clone() {
             params.push(format!("defaultItem={}", default));
         }
+        match self.kind {
+            Some(WitOptionalKind::Absent) => params.push("kind=absent".to_string()),
+            Some(WitOptionalKind::Nullable) => params.push("kind=nullable".to_string()),
+            None => {}
+        }
     }
 
     fn variant_name(&self) -> String {
@@ -62,3 +91,143 @@ impl TypeData for TypeOptional {
 
     super::impl_into_type!(concrete, Optional);
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::types::{Type, TypeId};
+    use crate::wit::core::Core;
+    use crate::Lib;
+
+    #[test]
+    fn test_optional_kinds() -> Result<(), String> {
+        Store::reset()?;
+        let int = t::integer().build()?;
+        let out = t::struct_()
+            .propx("maybe", t::optional(int))?
+            .propx("absent", t::optional(int).absent())?
+            .propx("nullable", t::optional(int).nullable())?
+            .build()?;
+
+        let mat = dummy_mat()?;
+        let inp = t::struct_().build()?;
+        expose_one("one", t::func(inp, out, mat)?)?;
+        let typegraph = Lib::finalize_typegraph()?;
+        insta::assert_snapshot!(typegraph);
+        Ok(())
+    }
+
+    #[test]
+    fn test_optional_default_value() -> Result<(), String> {
+        let mat = dummy_mat()?;
+        let int = t::integer().build()?;
+
+        let valid = t::optional(int)
+            .default_item(serde_json::json!(12))
+            .build()?;
+        let inp = t::struct_().prop("a", valid).build()?;
+        expose_one("one", t::func(inp, int, mat)?)?;
+
+        let invalid = t::optional(int)
+            .default_item(serde_json::json!("x"))
+            .build()?;
+        let inp = t::struct_().prop("a", invalid).build()?;
+        let res = Lib::expose(
+            vec![("two".to_string(), t::func(inp, int, mat)?.into())],
+            None,
+        );
+        assert_eq!(res, Err(errors::invalid_default_value(&invalid.repr()?)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_chained_optional() -> Result<(), String> {
+        Store::reset()?;
+        let chained = t::integer().min(0).optional()?;
+        let explicit = t::optional(t::integer().min(0).build()?).build()?;
+        let item_min = |id: TypeId| -> Result<_, String> {
+            match id.as_type()? {
+                Type::Optional(inner) => match TypeId(inner.data.of).as_type()? {
+                    Type::Integer(int) => Ok(int.data.min),
+                    _ => Err("expected an integer".to_string()),
+                },
+                _ => Err("expected an optional".to_string()),
+            }
+        };
+        assert_eq!(item_min(chained)?, Some(0));
+        assert_eq!(item_min(explicit)?, Some(0));
+        assert_eq!(chained.type_name()?, None);
+
+        let named = t::integer().named("Age").optional()?;
+        assert_eq!(named.type_name()?, Some(format!("_{}_Age?", named.0)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_null_default() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let string = t::string().build()?;
+        let inp = t::struct_()
+            .prop(
+                "nulled",
+                t::optional(string)
+                    .default_item(serde_json::Value::Null)
+                    .build()?,
+            )
+            .prop("omitted", t::optional(string).build()?)
+            .build()?;
+
+        expose_one("one", t::func(inp, string, mat)?)?;
+        let tg: serde_json::Value = serde_json::from_str(&Lib::finalize_typegraph()?).unwrap();
+        let optionals = tg["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|node| node["type"] == "optional")
+            .collect::<Vec<_>>();
+        assert_eq!(optionals.len(), 2);
+        assert_eq!(
+            optionals[0].get("default_value"),
+            Some(&serde_json::Value::Null)
+        );
+        assert_eq!(
+            optionals[0].get("null_default"),
+            Some(&serde_json::Value::Bool(true))
+        );
+        assert_eq!(
+            optionals[1].get("default_value"),
+            Some(&serde_json::Value::Null)
+        );
+        assert_eq!(optionals[1].get("null_default"), None);
+
+        // a null default is kept through deserialization
+        let node: common::typegraph::TypeNode =
+            serde_json::from_value(optionals[0].clone()).map_err(|e| e.to_string())?;
+        let common::typegraph::TypeNode::Optional { data, .. } = node else {
+            panic!("expected an optional");
+        };
+        assert!(data.null_default);
+
+        // no default, as in the typegraphs serialized before `null_default`
+        let node: common::typegraph::TypeNode =
+            serde_json::from_value(optionals[1].clone()).map_err(|e| e.to_string())?;
+        let common::typegraph::TypeNode::Optional { data, .. } = node else {
+            panic!("expected an optional");
+        };
+        assert_eq!(data.default_value, None);
+        assert!(!data.null_default);
+
+        // but not allowed when the value may not be null
+        let absent = t::optional(string)
+            .default_item(serde_json::Value::Null)
+            .absent()
+            .build()?;
+        let inp = t::struct_().prop("absent", absent).build()?;
+        let res = expose_one("two", t::func(inp, string, mat)?);
+        assert_eq!(res, Err(errors::invalid_default_value(&absent.repr()?)));
+        Ok(())
+    }
+}
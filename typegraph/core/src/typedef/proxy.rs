@@ -38,8 +38,7 @@ impl WrapperTypeData for TypeProxy {
     }
 
     fn try_resolve(&self) -> Result<TypeId> {
-        self.resolve()
-            .ok_or_else(|| format!("could not resolve proxy '{}'", self.name))
+        Store::resolve_type_name(&self.name)
     }
 }
 
@@ -50,3 +49,96 @@ impl TypeProxy {
             .find_map(|(k, v)| if k == key { Some(v.as_str()) } else { None })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::types::TypeId;
+    use crate::wit::core::Core;
+    use crate::Lib;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_proxy_cycle() -> Result<(), String> {
+        Store::reset()?;
+        let user = t::struct_()
+            .propx("id", t::integer())?
+            .named("User")
+            .build()?;
+        let proxy = t::proxy("User").build()?;
+        assert_eq!(proxy.resolve_proxy()?, user);
+
+        let a = t::proxy("B").build()?;
+        let b = t::proxy("A").build()?;
+        Store::register_type_name("A", a)?;
+        Store::register_type_name("B", b)?;
+        assert_eq!(a.resolve_proxy(), Err(errors::proxy_cycle("B")));
+        assert!(a.attrs().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_types() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let calls = Arc::new(AtomicUsize::new(0));
+        // `a` refers to `b`, which is only defined afterwards
+        let b_ref: Arc<Mutex<Option<TypeId>>> = Default::default();
+
+        let a = t::lazy({
+            let (calls, b_ref) = (calls.clone(), b_ref.clone());
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                t::struct_()
+                    .propx("b", t::optional(b_ref.lock().unwrap().unwrap()))?
+                    .named("A")
+                    .build()
+            }
+        })?;
+        let b = t::lazy({
+            let calls = calls.clone();
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                t::struct_().propx("a", t::optional(a))?.named("B").build()
+            }
+        })?;
+        *b_ref.lock().unwrap() = Some(b);
+        Lib::list_types()?;
+        assert_eq!(
+            a.resolve_proxy(),
+            Err(errors::unresolved_lazy_type("__lazy_0"))
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        t::resolve_lazy()?;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        expose_one("one", t::func(a, b, mat)?)?;
+        let typegraph = Lib::finalize_typegraph_struct()?;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let names = typegraph
+            .types
+            .iter()
+            .map(|t| t.base().title.as_str())
+            .collect::<Vec<_>>();
+        assert!(names.contains(&"A"));
+        assert!(names.contains(&"B"));
+        assert!(!names.iter().any(|n| n.starts_with("__lazy")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_lazy_type_error() -> Result<(), String> {
+        Store::reset()?;
+        let lazy = t::lazy(|| t::integer().min(2).max(1).build())?;
+        assert_eq!(t::resolve_lazy(), Err(errors::invalid_max_value()));
+        assert_eq!(
+            lazy.resolve_proxy(),
+            Err(errors::unresolved_lazy_type("__lazy_0"))
+        );
+        Ok(())
+    }
+}
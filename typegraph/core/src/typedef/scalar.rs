@@ -0,0 +1,109 @@
+// Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
+// SPDX-License-Identifier: MPL-2.0
+
+use common::typegraph::{ScalarTypeData, TypeNode};
+
+use crate::{
+    conversion::types::{expect_runtime, gen_base, TypeConversion},
+    errors::{self, Result},
+    global_store::Store,
+    typegraph::TypegraphContext,
+    types::{Scalar, TypeData},
+    wit::core::{TypeBase, TypeScalar},
+};
+
+impl TypeConversion for Scalar {
+    fn convert(&self, ctx: &mut TypegraphContext, runtime_id: Option<u32>) -> Result<TypeNode> {
+        let (validator, _) = ctx.register_materializer(self.data.validator)?;
+        let (serializer, _) = ctx.register_materializer(self.data.serializer)?;
+        Ok(TypeNode::Scalar {
+            base: gen_base(
+                // named on creation
+                self.base.name.clone().unwrap(),
+                self.base.runtime_config.clone(),
+                expect_runtime(runtime_id, self.id)?,
+            )
+            .examples(self.base.examples.clone())
+            .deprecated(self.base.deprecated.clone())
+            .build(),
+            data: ScalarTypeData {
+                validator,
+                serializer,
+            },
+        })
+    }
+}
+
+impl TypeScalar {
+    /// Scalars are referenced by name, and their materializers must exist
+    pub fn validate(&self, base: &TypeBase) -> Result<()> {
+        if base.name.is_none() {
+            return Err(errors::unnamed_scalar());
+        }
+        Store::get_materializer(self.validator)?;
+        Store::get_materializer(self.serializer)?;
+        Ok(())
+    }
+}
+
+impl TypeData for TypeScalar {
+    fn get_display_params_into(&self, params: &mut Vec<String>) {
+        params.push(format!("validator={}", self.validator));
+        params.push(format!("serializer={}", self.serializer));
+    }
+
+    fn variant_name(&self) -> String {
+        "scalar".to_string()
+    }
+
+    super::impl_into_type!(concrete, Scalar);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+    use common::typegraph::TypeNode;
+
+    #[test]
+    fn test_custom_scalar() -> Result<(), String> {
+        use common::typegraph::ScalarTypeData;
+
+        Store::reset()?;
+        let validate = Lib::register_deno_func(
+            MaterializerDenoFunc::with_code("(v) => /^\\d+\\.\\d{2}$/.test(v)"),
+            Effect::None,
+        )?;
+        let serialize = Lib::register_deno_func(
+            MaterializerDenoFunc::with_code("(v) => `${v}`"),
+            Effect::None,
+        )?;
+        let money = t::scalar("Money", validate, serialize).build()?;
+        let mat = dummy_mat()?;
+
+        expose_one("price", t::func(t::struct_().build()?, money, mat)?)?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        let Some(TypeNode::Scalar {
+            base,
+            data:
+                ScalarTypeData {
+                    validator,
+                    serializer,
+                },
+        }) = tg.types.iter().find(|t| t.type_name() == "scalar")
+        else {
+            panic!("scalar node not found");
+        };
+        assert_eq!(base.title, "Money");
+        let script = |idx: &u32| tg.materializers[*idx as usize].data["script"].clone();
+        assert_eq!(
+            script(validator),
+            "var _my_lambda = (v) => /^\\d+\\.\\d{2}$/.test(v)"
+        );
+        assert_eq!(script(serializer), "var _my_lambda = (v) => `${v}`");
+        Ok(())
+    }
+}
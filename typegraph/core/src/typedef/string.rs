@@ -5,7 +5,7 @@ use common::typegraph::{StringFormat, StringTypeData, TypeNode};
 use errors::Result;
 
 use crate::{
-    conversion::types::{gen_base, TypeConversion},
+    conversion::types::{expect_runtime, gen_base, TypeConversion},
     errors,
     typegraph::TypegraphContext,
     types::{StringT, TypeData},
@@ -30,16 +30,19 @@ impl TypeConversion for StringT {
                     .clone()
                     .unwrap_or_else(|| format!("string_{}", self.id.0)),
                 self.base.runtime_config.clone(),
-                runtime_id.unwrap(),
+                expect_runtime(runtime_id, self.id)?,
             )
             .enum_(self.data.enumeration.clone())
+            .examples(self.base.examples.clone())
             .id(self.base.as_id)
+            .deprecated(self.base.deprecated.clone())
             .build(),
             data: StringTypeData {
                 min_length: self.data.min,
                 max_length: self.data.max,
                 pattern: self.data.pattern.to_owned(),
                 format,
+                max_size: self.data.max_size,
             },
         })
     }
@@ -59,6 +62,9 @@ impl TypeData for TypeString {
         if let Some(format) = self.format.to_owned() {
             params.push(format!("format={}", format));
         }
+        if let Some(max_size) = self.max_size {
+            params.push(format!("maxSize={}", max_size));
+        }
     }
 
     fn variant_name(&self) -> String {
@@ -67,3 +73,87 @@ impl TypeData for TypeString {
 
     super::impl_into_type!(concrete, String);
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::types::Type;
+    use crate::Lib;
+    use common::typegraph::TypeNode;
+
+    #[test]
+    fn test_bytes() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let file = t::bytes().max_size(5 * 1024 * 1024).build()?;
+        assert_eq!(
+            file.repr()?,
+            format!("string(#{}, format=binary, maxSize=5242880)", file.0)
+        );
+        assert_eq!(
+            t::bytes().max_size(0).build(),
+            Err(errors::invalid_max_value())
+        );
+        assert_eq!(
+            t::string().max_size(10).build(),
+            Err(errors::max_size_without_binary_format())
+        );
+
+        let inp = t::struct_().prop("content", file).build()?;
+        expose_one("upload", t::func(inp, file, mat)?)?;
+        let typegraph = Lib::finalize_typegraph_struct()?;
+        let node = typegraph
+            .types
+            .iter()
+            .find(|t| matches!(t, TypeNode::String { .. }))
+            .unwrap();
+        let serialized = serde_json::to_value(node).map_err(|e| e.to_string())?;
+        assert_eq!(serialized["format"], "binary");
+        assert_eq!(serialized["maxSize"], 5 * 1024 * 1024);
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_formats() -> Result<(), String> {
+        Store::reset()?;
+        let cases = [
+            (t::uuid(), "uuid"),
+            (t::email(), "email"),
+            (t::uri(), "uri"),
+            (t::hostname(), "hostname"),
+            (t::json_string(), "json"),
+        ];
+        for (mut builder, format) in cases {
+            let plain = builder.build()?;
+            assert_eq!(
+                plain.repr()?,
+                format!("string(#{}, format={format})", plain.0)
+            );
+            let chained = builder.min(3).build()?;
+            assert_eq!(
+                chained.repr()?,
+                format!("string(#{}, min=3, format={format})", chained.0)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_length() -> Result<(), String> {
+        t::string().min(2).max(2).build()?;
+        let country_code = t::string().length(2).build()?;
+        let Type::String(country_code) = country_code.as_type()? else {
+            panic!("expected a string");
+        };
+        assert_eq!(
+            (country_code.data.min, country_code.data.max),
+            (Some(2), Some(2))
+        );
+
+        let res = t::string().min(3).max(2).build();
+        assert_eq!(res, Err(errors::invalid_max_value()));
+        Ok(())
+    }
+}
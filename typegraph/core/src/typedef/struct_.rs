@@ -10,8 +10,8 @@ use crate::{
     errors,
     global_store::Store,
     typegraph::TypegraphContext,
-    types::{Struct, TypeData, TypeId},
-    wit::core::TypeStruct,
+    types::{Struct, Type, TypeData, TypeId},
+    wit::core::{OptionalKind, TypeStruct},
 };
 
 impl TypeStruct {
@@ -41,6 +41,7 @@ impl TypeConversion for Struct {
                 },
             )
             .enum_(self.data.enumeration.clone())
+            .deprecated(self.base.deprecated.clone())
             .build(),
             data: ObjectTypeData {
                 properties: self
@@ -50,7 +51,8 @@ impl TypeConversion for Struct {
                         Ok((name.to_string(), ctx.register_type(id, runtime_id)?.into()))
                     })
                     .collect::<Result<IndexMap<_, _>>>()?,
-                required: Vec::new(),
+                required: self.required_props()?,
+                max_depth: self.data.max_depth,
             },
         })
     }
@@ -60,6 +62,22 @@ impl Struct {
     pub fn iter_props(&self) -> impl Iterator<Item = (&str, TypeId)> {
         self.data.props.iter().map(|(k, v)| (k.as_str(), v.into()))
     }
+
+    /// Props that must be present, in declaration order
+    pub fn required_props(&self) -> Result<Vec<String>> {
+        self.iter_props()
+            .map(|(name, type_id)| -> Result<Option<String>> {
+                let concrete_type = type_id.attrs()?.concrete_type;
+                Ok(match concrete_type.as_type()? {
+                    Type::Optional(inner) if inner.data.kind != Some(OptionalKind::Nullable) => {
+                        None
+                    }
+                    _ => Some(name.to_string()),
+                })
+            })
+            .filter_map(Result::transpose)
+            .collect()
+    }
 }
 
 impl TypeData for TypeStruct {
@@ -83,3 +101,311 @@ impl TypeStruct {
             .find_map(|(n, t)| if n == name { Some(t.into()) } else { None })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::types::{Type, TypeFun, TypeId};
+    use crate::wit::core::Core;
+    use crate::wit::core::TypeStruct;
+    use crate::wit::runtimes::Runtimes;
+    use crate::Lib;
+    use common::typegraph::{TypeNode, Typegraph};
+
+    #[test]
+    fn test_struct_max_props() -> Result<(), String> {
+        t::struct_()
+            .prop("one", t::integer().build()?)
+            .prop("two", t::integer().build()?)
+            .max_props(2)
+            .build()?;
+
+        let res = t::struct_()
+            .prop("one", t::integer().build()?)
+            .prop("two", t::integer().build()?)
+            .prop("three", t::integer().build()?)
+            .max_props(2)
+            .build();
+        assert_eq!(res, Err(errors::too_many_props(3, 2)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_extend() -> Result<(), String> {
+        let timestamps = t::struct_()
+            .prop("createdAt", t::string().build()?)
+            .prop("updatedAt", t::string().build()?)
+            .named("Timestamps")
+            .build()?;
+        let post = t::struct_()
+            .prop("title", t::string().build()?)
+            .extend(timestamps)?
+            .named("Post")
+            .build()?;
+        let props = post
+            .as_struct()?
+            .iter_props()
+            .map(|(k, _)| k.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(props, vec!["title", "createdAt", "updatedAt"]);
+
+        let res = t::struct_()
+            .prop("createdAt", t::string().build()?)
+            .extend(timestamps)
+            .map(|_| ());
+        assert_eq!(res, Err(errors::duplicate_key("createdAt")));
+
+        let int = t::integer().build()?;
+        let res = t::struct_().extend(int).map(|_| ());
+        assert_eq!(res, Err(errors::invalid_input_type(&int.repr()?)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_pick_omit() -> Result<(), String> {
+        let user = t::struct_()
+            .prop("id", t::integer().build()?)
+            .prop("name", t::string().build()?)
+            .prop("email", t::string().build()?)
+            .build()?;
+        let prop_names = |id: TypeId| -> Result<Vec<String>, String> {
+            Ok(id
+                .as_struct()?
+                .iter_props()
+                .map(|(k, _)| k.to_string())
+                .collect())
+        };
+
+        let picked = t::struct_().pick(user, vec!["email", "id"])?.build()?;
+        assert_eq!(prop_names(picked)?, vec!["id", "email"]);
+
+        let omitted = t::struct_()
+            .omit(user, vec!["id"])?
+            .prop("password", t::string().build()?)
+            .build()?;
+        assert_eq!(prop_names(omitted)?, vec!["name", "email", "password"]);
+
+        let res = t::struct_().pick(user, vec!["age"]).map(|_| ());
+        assert_eq!(res, Err(errors::unknown_field("age")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_from_partial() -> Result<(), String> {
+        let address = t::struct_()
+            .prop("city", t::string().build()?)
+            .prop("zip", t::optional(t::string().build()?).build()?)
+            .build()?;
+        let user = t::struct_()
+            .prop("id", t::integer().build()?)
+            .prop("address", address)
+            .build()?;
+        let is_optional =
+            |id: TypeId| -> Result<bool, String> { Ok(matches!(id.as_type()?, Type::Optional(_))) };
+        let optional_item = |id: TypeId| -> Result<TypeId, String> {
+            match id.as_type()? {
+                Type::Optional(inner) => Ok(inner.item()),
+                _ => Err("expected optional".to_string()),
+            }
+        };
+
+        let shallow = t::struct_from_partial(user).build()?.as_struct()?;
+        for (_, prop) in shallow.iter_props() {
+            assert!(is_optional(prop)?);
+        }
+        let nested = optional_item(shallow.data.get_prop("address").unwrap())?;
+        assert_eq!(nested, address);
+
+        let deep = t::struct_from_partial(user)
+            .deep(true)
+            .build()?
+            .as_struct()?;
+        let nested = optional_item(deep.data.get_prop("address").unwrap())?.as_struct()?;
+        for (_, prop) in nested.iter_props() {
+            assert!(is_optional(prop)?);
+        }
+
+        // self-referential structs refer to their own partial
+        let node = t::struct_()
+            .prop("value", t::integer().build()?)
+            .prop("parent", t::proxy("Node").optional()?)
+            .named("Node")
+            .build()?;
+        let partial = t::struct_from_partial(node).deep(true).build()?;
+        let parent = optional_item(partial.as_struct()?.data.get_prop("parent").unwrap())?;
+        assert_eq!(parent.resolve_proxy()?, partial);
+        assert_eq!(t::struct_from_partial(node).deep(true).build()?, partial);
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_required_order() -> Result<(), String> {
+        Store::reset()?;
+        let int = t::integer().build()?;
+        let opt = t::optional(int).build()?;
+        let s = t::struct_()
+            .prop("a", opt)
+            .prop("b", int)
+            .prop("c", opt)
+            .prop("d", int)
+            .prop("e", int)
+            .named("Interleaved")
+            .build()?;
+
+        let mat = dummy_mat()?;
+        expose_one("one", t::func(s, int, mat)?)?;
+        let typegraph: Typegraph =
+            serde_json::from_str(&Lib::finalize_typegraph()?).map_err(|e| e.to_string())?;
+        let required = typegraph
+            .types
+            .iter()
+            .find_map(|node| match node {
+                TypeNode::Object { base, data } if base.title == "Interleaved" => {
+                    Some(data.required.clone())
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(required, vec!["b", "d", "e"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_prop_optional() -> Result<(), String> {
+        Store::reset()?;
+        let int = t::integer().build()?;
+        let s = t::struct_()
+            .prop("id", int)
+            .prop_optional("age", int)?
+            .build()?;
+        assert_eq!(s.as_struct()?.required_props()?, vec!["id".to_string()]);
+
+        let res = t::struct_().prop_optional("a-b", int)?.build();
+        assert_eq!(res, Err(errors::invalid_prop_key("a-b")));
+        let res = t::struct_()
+            .prop("age", int)
+            .prop_optional("age", int)?
+            .build();
+        assert_eq!(res, Err(errors::duplicate_key("age")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_prop_resolver() -> Result<(), String> {
+        Store::reset()?;
+        let full_name = Lib::register_deno_func(
+            MaterializerDenoFunc::with_code("(_, { parent }) => `${parent.first} ${parent.last}`"),
+            Effect::None,
+        )?;
+        let user = t::struct_()
+            .propx("first", t::string())?
+            .propx("last", t::string())?
+            .prop_resolver("fullName", t::string().build()?, full_name)?
+            .named("User")
+            .build()?;
+        let mat =
+            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => ({})"), Effect::None)?;
+
+        expose_one("user", t::func(t::struct_().build()?, user, mat)?)?;
+        let typegraph = Lib::finalize_typegraph()?;
+        insta::assert_snapshot!(typegraph);
+
+        // not allowed as arguments
+        let inp = t::struct_()
+            .prop_resolver("fullName", t::string().build()?, full_name)?
+            .build()?;
+        assert_eq!(
+            t::func(inp, t::string().build()?, mat),
+            Err(errors::func_in_input("fullName"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_multiple_errors() -> Result<(), String> {
+        Store::reset()?;
+        let int = t::integer().build()?;
+        let data = TypeStruct {
+            props: vec![
+                ("".to_string(), int.into()),
+                ("a".to_string(), int.into()),
+                ("a".to_string(), int.into()),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            Lib::structb(data.clone(), Default::default()),
+            Err(errors::invalid_prop_key(""))
+        );
+        assert_eq!(
+            Lib::structb_checked(data, Default::default()),
+            Err(errors::multiple(vec![
+                errors::invalid_prop_key(""),
+                errors::duplicate_key("a"),
+            ]))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_max_depth() -> Result<(), String> {
+        Store::reset()?;
+        let comment = t::struct_()
+            .propx("text", t::string())?
+            .propx("replies", t::arrayx(t::proxy("Comment"))?)?
+            .named("Comment")
+            .max_depth(5)
+            .build()?;
+        let mat =
+            Lib::register_deno_func(MaterializerDenoFunc::with_code("() => []"), Effect::None)?;
+
+        expose_one("comments", t::func(t::struct_().build()?, comment, mat)?)?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        let max_depth = tg.types.iter().find_map(|node| match node {
+            TypeNode::Object { base, data } if base.title == "Comment" => Some(data.max_depth),
+            _ => None,
+        });
+        assert_eq!(max_depth, Some(Some(5)));
+
+        assert_eq!(
+            t::struct_().max_depth(0).build(),
+            Err(errors::invalid_max_depth())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_runtime_config() -> Result<(), String> {
+        Store::reset()?;
+        let config = |type_id: TypeId| -> Result<Vec<(String, serde_json::Value)>, String> {
+            Ok(type_id
+                .as_type()?
+                .get_base()
+                .unwrap()
+                .runtime_config
+                .iter()
+                .flatten()
+                .map(|(k, v)| (k.clone(), serde_json::from_str(v).unwrap()))
+                .collect())
+        };
+
+        let int = t::integer().config("precision", 2).build()?;
+        assert_eq!(
+            config(int)?,
+            vec![("precision".to_string(), serde_json::json!(2))]
+        );
+
+        let record = t::struct_()
+            .propx("id", t::integer())?
+            .config("table", "records")
+            .build()?;
+        assert_eq!(
+            config(record)?,
+            vec![("table".to_string(), serde_json::json!("records"))]
+        );
+        Ok(())
+    }
+}
@@ -3,17 +3,20 @@
 
 use common::typegraph::{TypeNode, UnionTypeData};
 use errors::Result;
+use std::collections::HashSet;
 
 use crate::{
-    conversion::types::{gen_base, TypeConversion},
+    conversion::types::{expect_runtime, gen_base, TypeConversion},
     errors,
     typegraph::TypegraphContext,
-    types::{TypeData, TypeId, Union},
+    types::{Type, TypeData, TypeId, Union},
     wit::core::TypeUnion,
 };
 
 impl TypeConversion for Union {
     fn convert(&self, ctx: &mut TypegraphContext, runtime_id: Option<u32>) -> Result<TypeNode> {
+        let variants = self.flat_variants()?;
+
         Ok(TypeNode::Union {
             base: gen_base(
                 self.base
@@ -21,24 +24,44 @@ impl TypeConversion for Union {
                     .clone()
                     .unwrap_or_else(|| format!("union_{}", self.id.0)),
                 self.base.runtime_config.clone(),
-                runtime_id.unwrap(),
+                expect_runtime(runtime_id, self.id)?,
             )
+            .deprecated(self.base.deprecated.clone())
             .build(),
             data: UnionTypeData {
-                any_of: self
-                    .data
-                    .variants
-                    .iter()
-                    .map(|vid| -> Result<_> {
-                        let id = TypeId(*vid).resolve_proxy()?;
-                        Ok(ctx.register_type(id, runtime_id)?.into())
-                    })
+                any_of: variants
+                    .into_iter()
+                    .map(|id| -> Result<_> { Ok(ctx.register_type(id, runtime_id)?.into()) })
                     .collect::<Result<Vec<_>>>()?,
+                discriminator: self.data.discriminator.clone(),
             },
         })
     }
 }
 
+impl Union {
+    /// Variants with the nested unions inlined; a nested union with its own
+    /// discriminator is only inlined when it is the same as this one's.
+    pub(crate) fn flat_variants(&self) -> Result<Vec<TypeId>> {
+        let mut variants = vec![];
+        for vid in self.data.variants.iter() {
+            let id = TypeId(*vid).resolve_proxy()?;
+            match id.as_type()? {
+                Type::Union(inner)
+                    if inner.data.discriminator.is_none()
+                        || inner.data.discriminator == self.data.discriminator =>
+                {
+                    variants.extend(inner.flat_variants()?)
+                }
+                _ => variants.push(id),
+            }
+        }
+        let mut seen = HashSet::new();
+        variants.retain(|id| seen.insert(*id));
+        Ok(variants)
+    }
+}
+
 impl TypeData for TypeUnion {
     fn get_display_params_into(&self, params: &mut Vec<String>) {
         for (i, tpe_id) in self.variants.iter().enumerate() {
@@ -52,3 +75,103 @@ impl TypeData for TypeUnion {
 
     super::impl_into_type!(concrete, Union);
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::types::Type;
+    use crate::wit::core::Core;
+    use crate::Lib;
+
+    #[test]
+    fn test_union_discriminator() -> Result<(), String> {
+        let circle = t::struct_()
+            .propx("kind", t::string().enum_(vec!["circle".to_string()]))?
+            .propx("radius", t::float())?
+            .build()?;
+        let square = t::struct_()
+            .propx("kind", t::string().enum_(vec!["square".to_string()]))?
+            .propx("side", t::float())?
+            .build()?;
+        t::union([circle, square]).discriminator("kind").build()?;
+
+        let point = t::struct_()
+            .propx("x", t::float())?
+            .propx("y", t::float())?
+            .build()?;
+        let res = t::union([circle, square, point])
+            .discriminator("kind")
+            .build();
+        assert_eq!(
+            res,
+            Err(errors::missing_discriminator_field(&point.repr()?))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_discriminated_union() -> Result<(), String> {
+        let circle = t::struct_()
+            .propx("kind", t::string().enum_(vec!["circle".to_string()]))?
+            .propx("radius", t::float())?
+            .build()?;
+        let square = t::struct_()
+            .propx("kind", t::string().enum_(vec!["square".to_string()]))?
+            .propx("side", t::float())?
+            .build()?;
+        let shape = t::union([circle, square]).discriminator("kind").build()?;
+
+        // the inner discriminator would be lost by inlining its variants
+        let int = t::integer().build()?;
+        let res = t::union([shape, int]).build()?;
+        let Type::Union(union) = res.as_type()? else {
+            panic!("expected a union");
+        };
+        assert_eq!(union.flat_variants()?, vec![shape, int]);
+
+        let line = t::struct_()
+            .propx("kind", t::string().enum_(vec!["line".to_string()]))?
+            .build()?;
+        let res = t::union([shape, line]).discriminator("kind").build()?;
+        let Type::Union(union) = res.as_type()? else {
+            panic!("expected a union");
+        };
+        assert_eq!(union.flat_variants()?, vec![circle, square, line]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_duplicate_union_variant() -> Result<(), String> {
+        let int = t::integer().build()?;
+        let res = t::either([int, t::string().build()?, int]).build();
+        assert_eq!(res, Err(errors::duplicate_union_variant(&int.repr()?)));
+
+        t::union([int, t::integer().build()?, t::string().build()?]).build()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_flatten_nested_unions() -> Result<(), String> {
+        Store::reset()?;
+        let a = t::integer().build()?;
+        let b = t::string().build()?;
+        let c = t::boolean().build()?;
+        let nested = t::union([t::union([a, b]).build()?, c]).build()?;
+        // mixed nesting is kept as is
+        let mixed = t::union([t::either([a, b]).build()?, c]).build()?;
+        let out = t::struct_()
+            .prop("nested", nested)
+            .prop("mixed", mixed)
+            .build()?;
+
+        let mat = dummy_mat()?;
+        let inp = t::struct_().build()?;
+        expose_one("one", t::func(inp, out, mat)?)?;
+        let typegraph = Lib::finalize_typegraph()?;
+        insta::assert_snapshot!(typegraph);
+        Ok(())
+    }
+}
@@ -34,7 +34,7 @@ impl TypeData for TypePolicy {
                 .map(|p| match p {
                     PolicySpec::Simple(pol_id) => format!("'{}'", get_policy_name(*pol_id)),
                     PolicySpec::PerEffect(p) => format!(
-                        "{{create='{}', update='{}', delete='{}', none='{}'}}",
+                        "{{create='{}', update='{}', delete='{}', upsert='{}', none='{}'}}",
                         p.create
                             .map(get_policy_name)
                             .unwrap_or_else(|| "null".to_string()),
@@ -44,10 +44,27 @@ impl TypeData for TypePolicy {
                         p.delete
                             .map(get_policy_name)
                             .unwrap_or_else(|| "null".to_string()),
+                        p.upsert
+                            .map(get_policy_name)
+                            .unwrap_or_else(|| "null".to_string()),
                         p.none
                             .map(get_policy_name)
                             .unwrap_or_else(|| "null".to_string()),
                     ),
+                    PolicySpec::All(ids) => format!(
+                        "all({})",
+                        ids.iter()
+                            .map(|id| format!("'{}'", get_policy_name(*id)))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    PolicySpec::Any(ids) => format!(
+                        "any({})",
+                        ids.iter()
+                            .map(|id| format!("'{}'", get_policy_name(*id)))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
                 })
                 .collect::<Vec<_>>()
                 .join(", ")
@@ -5,26 +5,28 @@ use crate::conversion::runtimes::{convert_materializer, convert_runtime, Convert
 use crate::conversion::types::{gen_base, TypeConversion};
 use crate::global_store::SavedState;
 use crate::host::abi;
-use crate::types::{Type, TypeId};
-use crate::validation::validate_name;
+use crate::sync::{self, Rc};
+use crate::types::{Type, TypeFun, TypeId};
+use crate::validation::{validate_name, validate_typegraph_name};
 use crate::Lib;
 use crate::{
     errors::{self, Result},
     global_store::Store,
+    log,
 };
 use common::typegraph::runtimes::TGRuntime;
 use common::typegraph::{
-    Materializer, ObjectTypeData, Policy, PolicyIndices, PolicyIndicesByEffect, Queries, TypeMeta,
-    TypeNode, Typegraph,
+    Materializer, ObjectTypeData, Policy, PolicyIndices, PolicyIndicesAll, PolicyIndicesAny,
+    PolicyIndicesByEffect, Queries, TypeMeta, TypeNode, Typegraph,
 };
 use graphql_parser::parse_query;
 use indexmap::IndexMap;
+use sha2::{Digest, Sha256};
 use std::cell::RefCell;
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use std::path::Path;
-use std::rc::Rc;
+use std::path::{Path, PathBuf};
 
 use crate::wit::core::{
     Core, Error as TgError, MaterializerId, PolicyId, PolicySpec, RuntimeId, TypePolicy,
@@ -41,7 +43,7 @@ struct IdMapping {
 
 #[derive(Default)]
 struct RuntimeContexts {
-    prisma_typegen_cache: Rc<RefCell<HashMap<String, TypeId>>>,
+    prisma_typegen_cache: Rc<sync::RefCell<HashMap<String, TypeId>>>,
 }
 
 #[derive(Default)]
@@ -55,13 +57,16 @@ pub struct TypegraphContext {
     mapping: IdMapping,
     runtime_contexts: RuntimeContexts,
     saved_store_state: Option<SavedState>,
+    strict: bool,
+    /// files referenced by the materializers, e.g. wasm modules
+    deps: Vec<PathBuf>,
 }
 
 thread_local! {
     static TG: RefCell<Option<TypegraphContext>> = RefCell::new(None);
 }
 
-static TYPEGRAPH_VERSION: &str = "0.0.2";
+pub static TYPEGRAPH_VERSION: &str = "0.0.2";
 
 pub fn with_tg_mut<T>(f: impl FnOnce(&mut TypegraphContext) -> T) -> Result<T> {
     TG.with(|tg| {
@@ -84,7 +89,13 @@ pub fn init(params: TypegraphInitParams) -> Result<()> {
         }
     })?;
 
-    let endpoints = {
+    if !validate_typegraph_name(&params.name) {
+        return Err(errors::invalid_typegraph_name(&params.name));
+    }
+
+    let endpoints = if params.dry_run {
+        vec![]
+    } else {
         let glob = format!(
             "{}/**/*",
             Path::new(&params.path)
@@ -93,22 +104,31 @@ pub fn init(params: TypegraphInitParams) -> Result<()> {
                 .expect("Invalid path")
         );
 
-        abi::glob(&glob, &["graphql".to_string(), "gql".to_string()])?
-            .into_iter()
-            .flat_map(|p| {
-                let data = abi::read_file(&p).unwrap();
-                let ast = parse_query::<&str>(&data).unwrap();
-                ast.definitions
-                    .into_iter()
-                    .map(|op| {
-                        format!("{}", op)
-                            .split_whitespace()
-                            .collect::<Vec<_>>()
-                            .join(" ")
-                    })
+        let extensions = params
+            .query_extensions
+            .map(|exts| {
+                exts.into_iter()
+                    .filter(|e| !e.is_empty())
                     .collect::<Vec<_>>()
             })
-            .collect::<Vec<_>>()
+            .filter(|exts| !exts.is_empty())
+            .unwrap_or_else(|| vec!["graphql".to_string(), "gql".to_string()]);
+
+        let mut endpoints = vec![];
+        for p in abi::glob(&glob, &extensions)? {
+            let data = abi::read_file(&p).map_err(|e| errors::endpoint_read_failed(&p, &e))?;
+            let ast = parse_query::<&str>(&data)
+                .map_err(|e| errors::endpoint_parse_failed(&p, &e.to_string()))?;
+            endpoints.extend(ast.definitions.into_iter().map(|op| {
+                format!("{}", op)
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }));
+        }
+        // glob results come in filesystem order
+        endpoints.sort();
+        endpoints
     };
 
     let mut ctx = TypegraphContext {
@@ -129,9 +149,11 @@ pub fn init(params: TypegraphInitParams) -> Result<()> {
             prefix: params.prefix,
             rate: params.rate.map(|v| v.into()),
             secrets: vec![],
+            hash: None,
         },
         types: vec![],
-        saved_store_state: Some(Store::save()),
+        saved_store_state: Some(Store::save()?),
+        strict: params.strict,
         ..Default::default()
     };
 
@@ -143,6 +165,7 @@ pub fn init(params: TypegraphInitParams) -> Result<()> {
         data: ObjectTypeData {
             properties: IndexMap::new(),
             required: vec![],
+            max_depth: None,
         },
     }));
 
@@ -153,7 +176,18 @@ pub fn init(params: TypegraphInitParams) -> Result<()> {
     Ok(())
 }
 
+/// Drops the active typegraph context, if any, restoring the store state
+/// saved when it was initialized
+pub fn reset() {
+    TG.with(|tg| tg.borrow_mut().take());
+}
+
 pub fn finalize() -> Result<String> {
+    let tg = finalize_struct()?;
+    serde_json::to_string(&tg).map_err(|e| e.to_string())
+}
+
+pub fn finalize_struct() -> Result<Typegraph> {
     #[cfg(test)]
     eprintln!("Finalizing typegraph...");
 
@@ -163,7 +197,19 @@ pub fn finalize() -> Result<String> {
             .ok_or_else(errors::expected_typegraph_context)
     })?;
 
-    let tg = Typegraph {
+    let exposes_nothing = matches!(
+        ctx.types.first(),
+        Some(Some(TypeNode::Object { data, .. })) if data.properties.is_empty()
+    );
+    if exposes_nothing {
+        if ctx.strict {
+            // the saved store state is restored when the context is dropped
+            return Err(errors::empty_typegraph(&ctx.name));
+        }
+        log!("warning: typegraph '{}' does not expose anything", ctx.name);
+    }
+
+    let mut tg = Typegraph {
         id: format!("https://metatype.dev/specs/{TYPEGRAPH_VERSION}.json"),
         types: ctx
             .types
@@ -176,12 +222,50 @@ pub fn finalize() -> Result<String> {
         policies: ctx.policies,
         meta: ctx.meta,
         path: None,
-        deps: Default::default(),
+        deps: ctx.deps,
     };
 
-    Store::restore(ctx.saved_store_state.unwrap());
+    Store::restore(ctx.saved_store_state.unwrap())?;
 
-    serde_json::to_string(&tg).map_err(|e| e.to_string())
+    tg.meta.hash = Some(content_hash(&tg)?);
+
+    Ok(tg)
+}
+
+/// Type nodes of `type_id` and its dependencies, converted in a throwaway
+/// context, with the type itself first; the active typegraph, if any, is left
+/// untouched.
+pub fn convert_type(type_id: TypeId) -> Result<Vec<TypeNode>> {
+    let saved_store_state = Store::save()?;
+    let convert = || -> Result<_> {
+        let mut ctx = TypegraphContext::default();
+        let default_runtime_idx = ctx.register_runtime(Store::get_deno_runtime())?;
+        ctx.register_type(type_id, Some(default_runtime_idx))?;
+        ctx.types
+            .into_iter()
+            .enumerate()
+            .map(|(id, t)| t.ok_or_else(|| format!("Unexpected: type {id} was not finalized")))
+            .collect::<Result<Vec<_>>>()
+    };
+    let res = convert();
+    Store::restore(saved_store_state)?;
+    res
+}
+
+/// Hash of the serialized types, runtimes (with their materializers) and
+/// policies; `meta` is left out. All the maps in the typegraph are ordered,
+/// so the result is stable for identical inputs.
+fn content_hash(tg: &Typegraph) -> Result<String> {
+    let content = (&tg.types, &tg.runtimes, &tg.materializers, &tg.policies);
+    let json = serde_json::to_vec(&content).map_err(|e| e.to_string())?;
+    let mut sha256 = Sha256::new();
+    sha256.update(json);
+    Ok(format!("{:x}", sha256.finalize()))
+}
+
+/// Title of the namespace object at `path` from the root
+fn namespace_title(path: &[String]) -> String {
+    path.join("_")
 }
 
 fn ensure_valid_export(export_key: String, type_id: TypeId) -> Result<()> {
@@ -201,13 +285,45 @@ fn ensure_valid_export(export_key: String, type_id: TypeId) -> Result<()> {
     Ok(())
 }
 
+/// Export names starting with `__` are rejected unless `allow_reserved` is set
 pub fn expose(
     fields: Vec<(String, TypeId)>,
     default_policy: Option<Vec<PolicySpec>>,
+    allow_reserved: bool,
+) -> Result<()> {
+    let fields = fields
+        .into_iter()
+        .map(|(key, type_id)| (key.clone(), key, type_id))
+        .collect();
+    expose_aliased(fields, default_policy, allow_reserved)
+}
+
+/// Same as `expose`, with the fields given as `(key, alias, type_id)`:
+/// the alias is the exposed name, the key only identifies the export in
+/// the error messages.
+pub fn expose_aliased(
+    fields: Vec<(String, String, TypeId)>,
+    default_policy: Option<Vec<PolicySpec>>,
+    allow_reserved: bool,
+) -> Result<()> {
+    expose_at(&[], fields, default_policy, allow_reserved, false)
+}
+
+/// Same as `expose_aliased`, with the fields exposed under nested namespace
+/// objects, which are created if missing.
+/// With `merge_default_policy`, the default policy is appended to the policy
+/// chain of the fields that already have one instead of being ignored.
+pub fn expose_at(
+    namespace: &[String],
+    fields: Vec<(String, String, TypeId)>,
+    default_policy: Option<Vec<PolicySpec>>,
+    allow_reserved: bool,
+    merge_default_policy: bool,
 ) -> Result<()> {
+    Store::resolve_lazy_types()?;
     let fields = fields
         .into_iter()
-        .map(|(key, type_id)| -> Result<_> {
+        .map(|(key, alias, type_id)| -> Result<_> {
             let attrs = type_id.attrs()?;
 
             let has_policy = !attrs.policy_chain.is_empty();
@@ -220,43 +336,129 @@ pub fn expose(
                     chain: default_policy.to_vec(),
                 })?
                 .into(),
+                (true, Some(default_policy)) if merge_default_policy => {
+                    // the outermost chain replaces the inner ones on conversion
+                    let mut chain = attrs.policy_chain.clone();
+                    chain.extend(default_policy.iter().cloned());
+                    Lib::with_policy(TypePolicy {
+                        tpe: type_id.into(),
+                        chain,
+                    })?
+                    .into()
+                }
                 _ => type_id,
             };
 
-            Ok((key, type_id))
+            Ok((key, alias, type_id))
         })
         .collect::<Result<Vec<_>>>()?;
 
     with_tg_mut(|ctx| -> Result<_> {
-        let mut root = ctx.types.get_mut(0).unwrap().take().unwrap();
-        let root_data = match &mut root {
-            TypeNode::Object { data, .. } => data,
-            _ => return Err("expect root to be an object".to_string()),
-        };
-        let res = fields
-            .into_iter()
-            .map(|(key, type_id)| -> Result<_> {
-                if !validate_name(&key) {
-                    return Err(errors::invalid_export_name(&key));
-                }
-                if root_data.properties.contains_key(&key) {
-                    return Err(errors::duplicate_export_name(&key));
-                }
-                ensure_valid_export(key.clone(), type_id)?;
-
-                let type_idx = ctx.register_type(type_id, None)?;
-                root_data.properties.insert(key.clone(), type_idx.into());
-                root_data.required.push(key);
-                Ok(())
-            })
-            .collect::<Result<Vec<()>>>();
+        // everything is checked first: a failed expose leaves no empty
+        // namespace object behind
+        let existing = ctx.find_namespace_object(namespace)?;
+        let mut aliases = HashSet::new();
+        for (key, alias, type_id) in &fields {
+            if !validate_name(alias) {
+                return Err(errors::invalid_export_name(alias));
+            }
+            if !allow_reserved && alias.starts_with("__") {
+                return Err(errors::reserved_export_name(alias));
+            }
+            let exported = match existing.map(|idx| &ctx.types[idx]) {
+                Some(Some(TypeNode::Object { data, .. })) => data.properties.contains_key(alias),
+                _ => false,
+            };
+            if exported || !aliases.insert(alias.as_str()) {
+                return Err(errors::duplicate_export_name(alias));
+            }
+            ensure_valid_export(key.clone(), *type_id)?;
+        }
 
-        ctx.types[0] = Some(root);
-        res.map(|_| ())
+        let fields = fields
+            .iter()
+            .map(|(_, alias, type_id)| Ok((alias.clone(), ctx.register_type(*type_id, None)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let idx = ctx.namespace_object(namespace)?;
+        let Some(TypeNode::Object { data, .. }) = &mut ctx.types[idx] else {
+            return Err("expect root to be an object".to_string());
+        };
+        for (alias, type_idx) in fields {
+            data.properties.insert(alias.clone(), type_idx.into());
+            data.required.push(alias);
+        }
+        Ok(())
     })?
 }
 
 impl TypegraphContext {
+    /// Index of the namespace object at `path` from the root, if it exists.
+    /// Fails if `path` cannot be a namespace, as the namespace objects that
+    /// would be created by `namespace_object` are checked too.
+    fn find_namespace_object(&self, path: &[String]) -> Result<Option<usize>> {
+        if let Some(name) = path.iter().find(|name| !validate_name(name)) {
+            return Err(errors::invalid_export_name(name));
+        }
+        let mut idx = 0;
+        for (depth, name) in path.iter().enumerate() {
+            let existing = match &self.types[idx] {
+                Some(TypeNode::Object { data, .. }) => data.properties.get(name).copied(),
+                _ => return Err("expect namespace to be an object".to_string()),
+            };
+            idx = match existing {
+                Some(child) => match &self.types[child as usize] {
+                    Some(TypeNode::Object { .. }) => child as usize,
+                    _ => return Err(errors::duplicate_export_name(name)),
+                },
+                None => {
+                    // `["a_b"]` and `["a", "b"]` would get the same title
+                    for end in depth..path.len() {
+                        let title = namespace_title(&path[..=end]);
+                        if self.types.iter().flatten().any(|t| t.base().title == title) {
+                            return Err(errors::namespace_title_conflict(&title));
+                        }
+                    }
+                    return Ok(None);
+                }
+            };
+        }
+        Ok(Some(idx))
+    }
+
+    /// Index of the namespace object at `path` from the root. The missing
+    /// namespaces are created as required props of their parent; `path` must
+    /// have been checked with `find_namespace_object`.
+    fn namespace_object(&mut self, path: &[String]) -> Result<usize> {
+        let mut idx = 0;
+        for (depth, name) in path.iter().enumerate() {
+            let existing = match &self.types[idx] {
+                Some(TypeNode::Object { data, .. }) => data.properties.get(name).copied(),
+                _ => return Err("expect namespace to be an object".to_string()),
+            };
+            idx = match existing {
+                Some(child) => child as usize,
+                None => {
+                    let runtime_idx = self.register_runtime(Store::get_deno_runtime())?;
+                    let child = self.types.len();
+                    self.types.push(Some(TypeNode::Object {
+                        base: gen_base(namespace_title(&path[..=depth]), None, runtime_idx).build(),
+                        data: ObjectTypeData {
+                            properties: IndexMap::new(),
+                            required: vec![],
+                            max_depth: None,
+                        },
+                    }));
+                    if let Some(TypeNode::Object { data, .. }) = &mut self.types[idx] {
+                        data.properties.insert(name.clone(), child as u32);
+                        data.required.push(name.clone());
+                    }
+                    child
+                }
+            };
+        }
+        Ok(idx)
+    }
+
     pub fn register_type(
         &mut self,
         id: TypeId,
@@ -274,6 +476,10 @@ impl TypegraphContext {
                 self.types.push(None);
 
                 let tpe = id.as_type()?;
+                let runtime_id = match tpe.get_base().and_then(|base| base.runtime) {
+                    Some(runtime) => Some(self.register_runtime(runtime)?),
+                    None => runtime_id,
+                };
 
                 let type_node = tpe.convert(self, runtime_id)?;
 
@@ -335,13 +541,31 @@ impl TypegraphContext {
                                 .as_ref()
                                 .map(|id| self.register_policy(*id))
                                 .transpose()?,
+                            upsert: policies
+                                .upsert
+                                .as_ref()
+                                .map(|id| self.register_policy(*id))
+                                .transpose()?,
                         })
                     }
+                    PolicySpec::All(ids) => PolicyIndices::All(PolicyIndicesAll {
+                        all: self.register_policy_group(ids)?,
+                    }),
+                    PolicySpec::Any(ids) => PolicyIndices::Any(PolicyIndicesAny {
+                        any: self.register_policy_group(ids)?,
+                    }),
                 })
             })
             .collect()
     }
 
+    fn register_policy_group(&mut self, ids: &[u32]) -> Result<Vec<PolicyId>> {
+        if ids.is_empty() {
+            return Err(errors::empty_policy_group());
+        }
+        ids.iter().map(|id| self.register_policy(*id)).collect()
+    }
+
     pub fn register_policy(&mut self, id: u32) -> Result<PolicyId> {
         if let Some(idx) = self.mapping.policies.get(&id) {
             Ok(*idx)
@@ -392,7 +616,300 @@ impl TypegraphContext {
         self.meta.secrets.push(name.into());
     }
 
-    pub fn get_prisma_typegen_cache(&self) -> Rc<RefCell<HashMap<String, TypeId>>> {
+    pub fn add_dep(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        if !self.deps.contains(&path) {
+            self.deps.push(path);
+        }
+    }
+
+    pub fn get_prisma_typegen_cache(&self) -> Rc<sync::RefCell<HashMap<String, TypeId>>> {
         Rc::clone(&self.runtime_contexts.prisma_typegen_cache)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::wit::core::Core;
+    use crate::Lib;
+    use crate::TypegraphInitParams;
+
+    #[test]
+    fn test_empty_typegraph() -> Result<(), String> {
+        use crate::host::abi;
+        use crate::wit::core::TypegraphInitParams;
+
+        Store::reset()?;
+        abi::take_logs();
+        setup(None)?;
+        Lib::finalize_typegraph_struct()?;
+        assert_eq!(
+            abi::take_logs(),
+            vec!["warning: typegraph 'test' does not expose anything".to_string()]
+        );
+
+        Lib::init_typegraph(TypegraphInitParams {
+            name: "test".to_string(),
+            strict: true,
+            ..Default::default()
+        })?;
+        assert_eq!(
+            Lib::finalize_typegraph_struct().err(),
+            Some(errors::empty_typegraph("test"))
+        );
+        // the context is dropped on failure
+        setup(None)?;
+        Lib::finalize_typegraph_struct()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_finalize_struct() -> Result<(), String> {
+        let build = || -> Result<(), String> {
+            let mat = reset_with_mat()?;
+            let inp = t::struct_().propx("a", t::integer())?.build()?;
+            expose_one("one", t::func(inp, t::string().build()?, mat)?)
+        };
+
+        build()?;
+        let typegraph = Lib::finalize_typegraph_struct()?;
+        build()?;
+        let serialized: serde_json::Value =
+            serde_json::from_str(&Lib::finalize_typegraph()?).map_err(|e| e.to_string())?;
+        assert_eq!(
+            typegraph.types.len(),
+            serialized["types"].as_array().unwrap().len()
+        );
+        assert_eq!(
+            serde_json::to_value(&typegraph).map_err(|e| e.to_string())?,
+            serialized
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_finalize_pretty() -> Result<(), String> {
+        let build = || -> Result<(), String> {
+            let mat = reset_with_mat()?;
+            let inp = t::struct_().propx("a", t::integer().min(0))?.build()?;
+            expose_one("one", t::func(inp, t::string().build()?, mat)?)
+        };
+
+        build()?;
+        let compact = Lib::finalize_typegraph()?;
+        build()?;
+        let pretty = Lib::finalize_typegraph_pretty()?;
+        assert!(pretty.contains('\n'));
+        assert!(!compact.contains('\n'));
+
+        let parsed: common::typegraph::Typegraph =
+            serde_json::from_str(&pretty).map_err(|e| e.to_string())?;
+        assert_eq!(
+            serde_json::to_string(&parsed).map_err(|e| e.to_string())?,
+            compact
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_typegraph_hash() -> Result<(), String> {
+        let build = |min: i32, prefix: Option<&str>| -> Result<Option<String>, String> {
+            let mat = reset_with_mat()?;
+            let inp = t::struct_().propx("a", t::integer().min(min))?.build()?;
+            Lib::init_typegraph(TypegraphInitParams {
+                name: "test".to_string(),
+                path: ".".to_string(),
+                prefix: prefix.map(|p| p.to_string()),
+                ..Default::default()
+            })?;
+            Lib::expose(
+                vec![(
+                    "one".to_string(),
+                    t::func(inp, t::string().build()?, mat)?.into(),
+                )],
+                None,
+            )?;
+            Ok(Lib::finalize_typegraph_struct()?.meta.hash)
+        };
+
+        let hash = build(0, None)?;
+        assert!(hash.is_some());
+        assert_eq!(hash, build(0, None)?);
+        assert_ne!(hash, build(1, None)?);
+        // the metadata is not part of the content
+        assert_eq!(hash, build(0, Some("prefix_"))?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_typegraph_name() -> Result<(), String> {
+        Store::reset()?;
+        setup(Some("my-typegraph_2"))?;
+        Lib::finalize_typegraph()?;
+
+        assert_eq!(setup(Some("")), Err(errors::invalid_typegraph_name("")));
+        assert_eq!(
+            setup(Some("my typegraph")),
+            Err(errors::invalid_typegraph_name("my typegraph"))
+        );
+        assert_eq!(
+            setup(Some("../escape")),
+            Err(errors::invalid_typegraph_name("../escape"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_endpoint_files() -> Result<(), String> {
+        use crate::host::abi;
+
+        Store::reset()?;
+        abi::set_files(vec![("test/ok.graphql", Ok("query A { a }"))]);
+        setup(None)?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        assert_eq!(tg.meta.queries.endpoints, vec!["query A { a }".to_string()]);
+
+        abi::set_files(vec![("test/locked.gql", Err("permission denied"))]);
+        assert_eq!(
+            setup(None),
+            Err(errors::endpoint_read_failed(
+                "test/locked.gql",
+                "permission denied"
+            ))
+        );
+
+        abi::set_files(vec![("test/broken.graphql", Ok("query {"))]);
+        let err = setup(None).unwrap_err();
+        assert!(err.starts_with(r#"could not parse endpoint file "test/broken.graphql": "#));
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_extensions() -> Result<(), String> {
+        use crate::host::abi;
+        use crate::wit::core::TypegraphInitParams;
+
+        let init = |query_extensions: Option<Vec<&str>>| {
+            Lib::init_typegraph(TypegraphInitParams {
+                name: "test".to_string(),
+                path: ".".to_string(),
+                query_extensions: query_extensions
+                    .map(|exts| exts.into_iter().map(|e| e.to_string()).collect()),
+                ..Default::default()
+            })?;
+            Ok::<_, String>(Lib::finalize_typegraph_struct()?.meta.queries.endpoints)
+        };
+
+        Store::reset()?;
+        abi::set_files(vec![
+            ("test/a.graphql", Ok("query A { a }")),
+            ("test/b.gql", Ok("query B { b }")),
+        ]);
+
+        assert_eq!(init(Some(vec!["gql"]))?, vec!["query B { b }"]);
+        assert_eq!(init(Some(vec!["", "gql"]))?, vec!["query B { b }"]);
+        assert_eq!(init(Some(vec![""]))?.len(), 2);
+        assert_eq!(init(Some(vec![]))?.len(), 2);
+        assert_eq!(init(None)?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_endpoints_order() -> Result<(), String> {
+        use crate::host::abi;
+
+        let files = vec![
+            ("test/b.graphql", Ok("query B { b }\nquery A2 { a }")),
+            ("test/a.gql", Ok("query A { a }")),
+            ("test/c.graphql", Ok("mutation C { c }")),
+        ];
+        let expected = vec![
+            "mutation C { c }",
+            "query A { a }",
+            "query A2 { a }",
+            "query B { b }",
+        ];
+
+        Store::reset()?;
+        abi::set_files(files.clone());
+        setup(None)?;
+        let endpoints = Lib::finalize_typegraph_struct()?.meta.queries.endpoints;
+        assert_eq!(endpoints, expected);
+
+        abi::set_files(files.into_iter().rev().collect());
+        setup(None)?;
+        let endpoints = Lib::finalize_typegraph_struct()?.meta.queries.endpoints;
+        assert_eq!(endpoints, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dry_run() -> Result<(), String> {
+        use crate::host::abi;
+        use crate::wit::core::TypegraphInitParams;
+
+        Store::reset()?;
+        abi::set_files(vec![("test/a.graphql", Ok("query A { a }"))]);
+        abi::take_glob_calls();
+
+        Lib::init_typegraph(TypegraphInitParams {
+            name: "test".to_string(),
+            dry_run: true,
+            ..Default::default()
+        })?;
+        let tg = Lib::finalize_typegraph_struct()?;
+        assert!(tg.meta.queries.endpoints.is_empty());
+        assert_eq!(abi::take_glob_calls(), 0);
+
+        setup(None)?;
+        Lib::finalize_typegraph()?;
+        assert_eq!(abi::take_glob_calls(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_namespace_failed_expose() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let int = t::integer().build()?;
+        setup(None)?;
+        let path = vec!["admin".to_string(), "users".to_string()];
+        let res = Lib::expose_namespace(path, vec![("count".to_string(), int.into())], None);
+        assert_eq!(res, Err(errors::invalid_export_type("count", &int.repr()?)));
+
+        let func = t::func(t::struct_().build()?, int, mat)?;
+        Lib::expose(vec![("one".to_string(), func.into())], None)?;
+        let typegraph = Lib::finalize_typegraph_struct()?;
+        assert_eq!(typegraph.types[0].base().title, "test");
+        assert!(typegraph.types.iter().all(|t| t.base().title != "admin"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_namespace_title_conflict() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let func = t::func(t::struct_().build()?, t::integer().build()?, mat)?;
+        setup(None)?;
+        Lib::expose_namespace(
+            vec!["a_b".to_string()],
+            vec![("one".to_string(), func.into())],
+            None,
+        )?;
+        let res = Lib::expose_namespace(
+            vec!["a".to_string(), "b".to_string()],
+            vec![("two".to_string(), func.into())],
+            None,
+        );
+        assert_eq!(res, Err(errors::namespace_title_conflict("a_b")));
+
+        let typegraph = Lib::finalize_typegraph_struct()?;
+        let common::typegraph::TypeNode::Object { data, .. } = &typegraph.types[0] else {
+            panic!("expected an object");
+        };
+        assert_eq!(data.required, vec!["a_b"]);
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use common::typegraph::TypeNode;
 use enum_dispatch::enum_dispatch;
@@ -10,13 +11,17 @@ use enum_dispatch::enum_dispatch;
 use crate::conversion::types::TypeConversion;
 use crate::errors::{self, Result};
 use crate::global_store::Store;
+use crate::sync::Rc;
 use crate::typegraph::TypegraphContext;
 use crate::wit::core::{
     PolicySpec, TypeArray, TypeBase, TypeEither, TypeFile, TypeFloat, TypeFunc,
-    TypeId as CoreTypeId, TypeInteger, TypeOptional, TypePolicy, TypeProxy, TypeString, TypeStruct,
-    TypeUnion, TypeWithInjection,
+    TypeId as CoreTypeId, TypeInteger, TypeMap, TypeOptional, TypePolicy, TypeProxy, TypeScalar,
+    TypeString, TypeStruct, TypeUnion, TypeWithInjection,
 };
-use std::rc::Rc;
+use serde_json::Value;
+
+/// Maximum length of a proxy chain, beyond which it is considered a cycle
+const MAX_PROXY_HOPS: usize = 32;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TypeId(pub CoreTypeId);
@@ -65,13 +70,54 @@ pub struct ConcreteType<T: TypeData> {
     pub id: TypeId,
     pub base: TypeBase,
     pub data: T,
+    /// Set for the shared types, see [`TypeId::freeze`]
+    pub frozen: AtomicBool,
+}
+
+impl<T: TypeData> ConcreteType<T> {
+    pub fn new(id: TypeId, base: TypeBase, data: T) -> Self {
+        Self {
+            id,
+            base,
+            data,
+            frozen: AtomicBool::new(false),
+        }
+    }
 }
 
 impl<T: TypeData + Clone> ConcreteType<T> {
     pub fn rename(&self, new_name: String) -> Result<TypeId> {
+        self.modified(|base, _| base.name = Some(new_name))
+    }
+
+    /// Registers an unfrozen copy of the type, with the changes applied by `f`
+    pub fn modified(&self, f: impl FnOnce(&mut TypeBase, &mut T)) -> Result<TypeId> {
         let mut base = self.base.clone();
-        base.name = Some(new_name);
-        Store::register_type(|id| self.data.clone().into_type(id, Some(base)).unwrap())
+        let mut data = self.data.clone();
+        f(&mut base, &mut data);
+        Store::register_type(|id| data.into_type(id, Some(base)).unwrap())
+    }
+
+    /// Same as `modified`, for a change of the constraints or of the config;
+    /// fails for a frozen type, whose copies from `Lib::duplicate_type` can be
+    /// adjusted instead
+    #[allow(dead_code)]
+    pub fn constrained(&self, f: impl FnOnce(&mut TypeBase, &mut T)) -> Result<TypeId> {
+        if self.frozen.load(Ordering::Relaxed) {
+            return Err(errors::type_frozen(
+                self.base.name.as_deref().unwrap_or_default(),
+            ));
+        }
+        self.modified(f)
+    }
+}
+
+impl<T: TypeData + Debug> ConcreteType<T> {
+    fn content_key(&self) -> Option<String> {
+        self.base
+            .name
+            .is_none()
+            .then(|| format!("{}{:?}{:?}", self.data.variant_name(), self.base, self.data))
     }
 }
 
@@ -87,8 +133,10 @@ impl Default for TypeBase {
         Self {
             name: None,
             runtime_config: None,
-
             as_id: false,
+            examples: None,
+            deprecated: None,
+            runtime: None,
         }
     }
 }
@@ -96,15 +144,21 @@ impl Default for TypeBase {
 #[derive(Debug, Clone)]
 pub struct TypeBoolean;
 
+#[derive(Debug, Clone)]
+pub struct TypeJson;
+
 pub type Proxy = WrapperType<TypeProxy>;
 pub type Struct = ConcreteType<TypeStruct>;
 pub type Integer = ConcreteType<TypeInteger>;
 pub type Float = ConcreteType<TypeFloat>;
 pub type Func = ConcreteType<TypeFunc>;
 pub type Boolean = ConcreteType<TypeBoolean>;
+pub type Json = ConcreteType<TypeJson>;
 pub type StringT = ConcreteType<TypeString>;
 pub type File = ConcreteType<TypeFile>;
 pub type Array = ConcreteType<TypeArray>;
+pub type Map = ConcreteType<TypeMap>;
+pub type Scalar = ConcreteType<TypeScalar>;
 pub type Optional = ConcreteType<TypeOptional>;
 pub type Union = ConcreteType<TypeUnion>;
 pub type Either = ConcreteType<TypeEither>;
@@ -122,9 +176,12 @@ pub enum Type {
     Float(Rc<Float>),
     Func(Rc<Func>),
     Boolean(Rc<Boolean>),
+    Json(Rc<Json>),
     String(Rc<StringT>),
     File(Rc<File>),
     Array(Rc<Array>),
+    Map(Rc<Map>),
+    Scalar(Rc<Scalar>),
     Optional(Rc<Optional>),
     Union(Rc<Union>),
     Either(Rc<Either>),
@@ -133,6 +190,29 @@ pub enum Type {
 }
 
 impl Type {
+    /// Structural key of the unnamed scalar types, see [`Store::intern_type`]
+    pub fn intern_key(&self) -> Option<String> {
+        match self {
+            Type::Boolean(t) => t.content_key(),
+            Type::Integer(t) => t.content_key(),
+            Type::Float(t) => t.content_key(),
+            Type::String(t) => t.content_key(),
+            _ => None,
+        }
+    }
+
+    /// Whether the store still holds this very type under its id: a reset
+    /// or a restored store state may have dropped it, or reused the id.
+    /// Only the concrete types can tell, see [`TypeFun::frozen`].
+    pub fn is_registered(&self) -> bool {
+        self.get_id()
+            .as_type()
+            .is_ok_and(|typ| match (typ.frozen(), self.frozen()) {
+                (Some(a), Some(b)) => std::ptr::eq(a, b),
+                _ => false,
+            })
+    }
+
     fn get_name(&self) -> Option<&str> {
         match self {
             Type::Proxy(inner) => Some(&inner.data.name),
@@ -160,6 +240,11 @@ pub trait TypeFun {
         None
     }
 
+    /// The frozen flag, for the concrete types
+    fn frozen(&self) -> Option<&AtomicBool> {
+        None
+    }
+
     fn is_concrete_type(&self) -> bool {
         self.as_wrapper_type().is_none()
     }
@@ -189,6 +274,10 @@ impl<T: TypeFun> TypeFun for Rc<T> {
     fn get_concrete_type(&self) -> Option<TypeId> {
         (**self).get_concrete_type()
     }
+
+    fn frozen(&self) -> Option<&AtomicBool> {
+        (**self).frozen()
+    }
 }
 
 impl<T> TypeFun for ConcreteType<T>
@@ -217,6 +306,10 @@ where
     fn get_base(&self) -> Option<&TypeBase> {
         Some(&self.base)
     }
+
+    fn frozen(&self) -> Option<&AtomicBool> {
+        Some(&self.frozen)
+    }
 }
 
 impl<T> TypeFun for WrapperType<T>
@@ -290,10 +383,10 @@ impl TypeId {
         }
 
         let wrapped_type = match typ {
-            Type::Proxy(p) => match resolve_proxy {
+            Type::Proxy(_) => match resolve_proxy {
                 ProxyResolution::None => return Ok(None),
-                ProxyResolution::Try => p.data.resolve(),
-                ProxyResolution::Force => Some(p.data.try_resolve()?),
+                ProxyResolution::Try => self.resolve_proxy().ok(),
+                ProxyResolution::Force => Some(self.resolve_proxy()?),
             },
             x => Some(x.as_wrapper_type().unwrap().resolve().unwrap()),
         };
@@ -308,16 +401,38 @@ impl TypeId {
         self.as_type().map(|t| t.get_name().map(|s| s.to_string()))
     }
 
+    /// Prevents any further modification of the type
+    pub fn freeze(&self) -> Result<()> {
+        let typ = self.as_type()?;
+        let frozen = typ
+            .frozen()
+            .ok_or_else(|| format!("cannot freeze the wrapper type {}", typ.to_string()))?;
+        frozen.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn is_frozen(&self) -> Result<bool> {
+        Ok(self
+            .as_type()?
+            .frozen()
+            .is_some_and(|f| f.load(Ordering::Relaxed)))
+    }
+
     pub fn repr(&self) -> Result<String> {
         self.as_type().map(|t| t.to_string())
     }
 
     pub fn resolve_proxy(&self) -> Result<TypeId> {
-        match self.as_type()? {
-            Type::Proxy(inner) => Store::get_type_by_name(&inner.data.name)
-                .ok_or_else(|| errors::unregistered_type_name(&inner.data.name)),
-            _ => Ok(*self),
+        let mut type_id = *self;
+        let mut hops = 0;
+        while let Type::Proxy(inner) = type_id.as_type()? {
+            hops += 1;
+            if hops > MAX_PROXY_HOPS {
+                return Err(errors::proxy_cycle(&inner.data.name));
+            }
+            type_id = Store::resolve_type_name(&inner.data.name)?;
         }
+        Ok(type_id)
     }
 
     pub fn attrs(&self) -> Result<TypeAttributes> {
@@ -326,14 +441,18 @@ impl TypeId {
         let mut policy_chain = Vec::new();
         let mut injection: Option<String> = None;
         let mut name = None;
+        let mut proxy_hops = 0;
 
         loop {
             let typ = type_id.as_type()?;
             match typ {
                 Type::Proxy(p) => {
+                    proxy_hops += 1;
+                    if proxy_hops > MAX_PROXY_HOPS {
+                        return Err(errors::proxy_cycle(&p.data.name));
+                    }
                     proxy_data.extend(p.data.extras.clone());
-                    type_id = Store::get_type_by_name(&p.data.name)
-                        .ok_or_else(|| errors::unregistered_type_name(&p.data.name))?;
+                    type_id = Store::resolve_type_name(&p.data.name)?;
                     continue;
                 }
 
@@ -355,12 +474,15 @@ impl TypeId {
                 // exhaustively match all concrete type
                 // so that this emits an error when a new type is added
                 Type::Boolean(_)
+                | Type::Json(_)
                 | Type::Integer(_)
                 | Type::Float(_)
                 | Type::String(_)
                 | Type::File(_)
                 | Type::Optional(_)
                 | Type::Array(_)
+                | Type::Map(_)
+                | Type::Scalar(_)
                 | Type::Struct(_)
                 | Type::Union(_)
                 | Type::Either(_)
@@ -379,4 +501,351 @@ impl TypeId {
             }
         }
     }
+
+    /// Whether every valid value of this type is also valid for `other`.
+    /// Structs are compared by width: extra properties are accepted,
+    /// missing ones must be optional in `other`.
+    pub fn is_assignable_to(&self, other: TypeId) -> Result<bool> {
+        self.assignable(other, &mut HashSet::new())
+    }
+
+    /// Recursive types are compared coinductively: a pair already being
+    /// compared is assumed assignable.
+    fn assignable(&self, other: TypeId, assumed: &mut HashSet<(TypeId, TypeId)>) -> Result<bool> {
+        let sub = self.attrs()?.concrete_type;
+        let sup = other.attrs()?.concrete_type;
+        if sub == sup || !assumed.insert((sub, sup)) {
+            return Ok(true);
+        }
+
+        let res = match (sub.as_type()?, sup.as_type()?) {
+            (Type::Union(u), _) => all_assignable(&u.data.variants, sup, assumed)?,
+            (Type::Either(e), _) => all_assignable(&e.data.variants, sup, assumed)?,
+            (_, Type::Union(u)) => any_assignable(sub, &u.data.variants, assumed)?,
+            (_, Type::Either(e)) => any_assignable(sub, &e.data.variants, assumed)?,
+
+            (Type::Optional(a), Type::Optional(b)) => {
+                (b.data.kind.is_none() || a.data.kind == b.data.kind)
+                    && TypeId(a.data.of).assignable(b.data.of.into(), assumed)?
+            }
+            (Type::Optional(_), _) => false,
+            (_, Type::Optional(b)) => sub.assignable(b.data.of.into(), assumed)?,
+
+            (Type::Boolean(_), Type::Boolean(_)) => true,
+            (Type::Integer(a), Type::Integer(b)) => {
+                NumberConstraints::from(&a.data).narrows(&(&b.data).into())
+            }
+            (Type::Integer(a), Type::Float(b)) => {
+                NumberConstraints::from(&a.data).narrows(&(&b.data).into())
+            }
+            (Type::Float(a), Type::Float(b)) => {
+                NumberConstraints::from(&a.data).narrows(&(&b.data).into())
+            }
+            (Type::String(a), Type::String(b)) => string_narrows(&a.data, &b.data),
+            (Type::File(a), Type::File(b)) => {
+                let allow = match (&a.data.allow, &b.data.allow) {
+                    (_, None) => true,
+                    (Some(own), Some(allowed)) => own.iter().all(|mime| allowed.contains(mime)),
+                    (None, Some(_)) => false,
+                };
+                at_least(a.data.min, b.data.min) && at_most(a.data.max, b.data.max) && allow
+            }
+
+            (Type::Array(a), Type::Array(b)) => {
+                at_least(a.data.min, b.data.min)
+                    && at_most(a.data.max, b.data.max)
+                    && (b.data.unique_items != Some(true) || a.data.unique_items == Some(true))
+                    && TypeId(a.data.of).assignable(b.data.of.into(), assumed)?
+            }
+
+            (Type::Struct(a), Type::Struct(b)) => {
+                let mut res = true;
+                for (name, prop) in b.data.props.iter() {
+                    let prop = TypeId(*prop);
+                    res = match a.data.props.iter().find(|(n, _)| n == name) {
+                        Some((_, own)) => TypeId(*own).assignable(prop, assumed)?,
+                        None => matches!(prop.attrs()?.concrete_type.as_type()?, Type::Optional(_)),
+                    };
+                    if !res {
+                        break;
+                    }
+                }
+                res
+            }
+
+            _ => false,
+        };
+        if !res {
+            // only the pairs still being compared can be assumed
+            assumed.remove(&(sub, sup));
+        }
+        Ok(res)
+    }
+}
+
+fn any_assignable(
+    ty: TypeId,
+    variants: &[CoreTypeId],
+    assumed: &mut HashSet<(TypeId, TypeId)>,
+) -> Result<bool> {
+    for v in variants {
+        if ty.assignable(TypeId(*v), assumed)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn all_assignable(
+    variants: &[CoreTypeId],
+    other: TypeId,
+    assumed: &mut HashSet<(TypeId, TypeId)>,
+) -> Result<bool> {
+    for v in variants {
+        if !TypeId(*v).assignable(other, assumed)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Numeric constraints, common to integers and floats
+struct NumberConstraints {
+    min: Option<f64>,
+    max: Option<f64>,
+    exclusive_minimum: Option<f64>,
+    exclusive_maximum: Option<f64>,
+    multiple_of: Option<f64>,
+    enumeration: Option<Vec<f64>>,
+}
+
+impl From<&TypeInteger> for NumberConstraints {
+    fn from(data: &TypeInteger) -> Self {
+        Self {
+            min: data.min.map(f64::from),
+            max: data.max.map(f64::from),
+            exclusive_minimum: data.exclusive_minimum.map(f64::from),
+            exclusive_maximum: data.exclusive_maximum.map(f64::from),
+            multiple_of: data.multiple_of.map(f64::from),
+            enumeration: data
+                .enumeration
+                .as_ref()
+                .map(|e| e.iter().copied().map(f64::from).collect()),
+        }
+    }
+}
+
+impl From<&TypeFloat> for NumberConstraints {
+    fn from(data: &TypeFloat) -> Self {
+        Self {
+            min: data.min,
+            max: data.max,
+            exclusive_minimum: data.exclusive_minimum,
+            exclusive_maximum: data.exclusive_maximum,
+            multiple_of: data.multiple_of,
+            enumeration: data.enumeration.clone(),
+        }
+    }
+}
+
+impl NumberConstraints {
+    /// (bound, exclusive)
+    fn lower_bound(&self) -> Option<(f64, bool)> {
+        match (self.min, self.exclusive_minimum) {
+            (Some(m), Some(x)) if x >= m => Some((x, true)),
+            (Some(m), _) => Some((m, false)),
+            (None, x) => x.map(|x| (x, true)),
+        }
+    }
+
+    fn upper_bound(&self) -> Option<(f64, bool)> {
+        match (self.max, self.exclusive_maximum) {
+            (Some(m), Some(x)) if x <= m => Some((x, true)),
+            (Some(m), _) => Some((m, false)),
+            (None, x) => x.map(|x| (x, true)),
+        }
+    }
+
+    fn admits(&self, value: f64) -> bool {
+        let lower = match self.lower_bound() {
+            Some((b, x)) => value > b || (!x && value == b),
+            None => true,
+        };
+        let upper = match self.upper_bound() {
+            Some((b, x)) => value < b || (!x && value == b),
+            None => true,
+        };
+        let multiple_of = match self.multiple_of {
+            Some(m) => m == 0.0 || (value / m).fract() == 0.0,
+            None => true,
+        };
+        let enumerated = match &self.enumeration {
+            Some(e) => e.contains(&value),
+            None => true,
+        };
+        lower && upper && multiple_of && enumerated
+    }
+
+    fn narrows(&self, other: &Self) -> bool {
+        if let Some(values) = &self.enumeration {
+            return values.iter().all(|v| other.admits(*v));
+        }
+        let lower = match (self.lower_bound(), other.lower_bound()) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some((s, sx)), Some((o, ox))) => s > o || (s == o && (sx || !ox)),
+        };
+        let upper = match (self.upper_bound(), other.upper_bound()) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some((s, sx)), Some((o, ox))) => s < o || (s == o && (sx || !ox)),
+        };
+        let multiple_of = match (self.multiple_of, other.multiple_of) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(s), Some(o)) => o == 0.0 || (s / o).fract() == 0.0,
+        };
+        other.enumeration.is_none() && lower && upper && multiple_of
+    }
+}
+
+fn string_narrows(sub: &TypeString, sup: &TypeString) -> bool {
+    if let Some(values) = &sub.enumeration {
+        return values
+            .iter()
+            .all(|v| serde_json::from_str::<Value>(v).is_ok_and(|v| sup.check_value(&v).is_ok()));
+    }
+    sup.enumeration.is_none()
+        && (sup.format.is_none() || sub.format == sup.format)
+        && (sup.pattern.is_none() || sub.pattern == sup.pattern)
+        && at_least(sub.min, sup.min)
+        && at_most(sub.max, sup.max)
+}
+
+/// Whether the lower bound `own` is at least `bound`
+fn at_least(own: Option<u32>, bound: Option<u32>) -> bool {
+    match (own, bound) {
+        (_, None) => true,
+        (Some(own), Some(bound)) => own >= bound,
+        (None, Some(_)) => false,
+    }
+}
+
+/// Whether the upper bound `own` is at most `bound`
+fn at_most(own: Option<u32>, bound: Option<u32>) -> bool {
+    match (own, bound) {
+        (_, None) => true,
+        (Some(own), Some(bound)) => own <= bound,
+        (None, Some(_)) => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, ConcreteTypeBuilder, TypeBuilder};
+
+    #[test]
+    fn test_type_interning() -> Result<(), String> {
+        Store::reset()?;
+        let a = t::integer().min(0).intern()?;
+        let b = t::integer().min(0).intern()?;
+        assert_eq!(a, b);
+        assert_ne!(a, t::integer().min(1).intern()?);
+        assert_ne!(a, t::float().min(0.0).intern()?);
+        // interning is opt-in
+        assert_ne!(a, t::integer().min(0).build()?);
+
+        let named = t::integer().min(0).named("Positive").build()?;
+        assert_eq!(
+            Store::intern_type(named),
+            Err(errors::not_internable(&named.repr()?))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_assignable_scalars() -> Result<(), String> {
+        Store::reset()?;
+        let int = t::integer().build()?;
+        let range = t::integer().min(1).max(10).build()?;
+        let positive = t::integer().x_min(0).build()?;
+        assert!(range.is_assignable_to(int)?);
+        assert!(range.is_assignable_to(positive)?);
+        assert!(!int.is_assignable_to(range)?);
+        assert!(!t::integer().min(0).build()?.is_assignable_to(positive)?);
+
+        let float = t::float().max(10.0).build()?;
+        assert!(range.is_assignable_to(float)?);
+        assert!(!float.is_assignable_to(int)?);
+
+        let string = t::string().build()?;
+        let email = t::string().format("email").build()?;
+        assert!(email.is_assignable_to(string)?);
+        assert!(!string.is_assignable_to(email)?);
+        assert!(!int.is_assignable_to(string)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_assignable_optional() -> Result<(), String> {
+        Store::reset()?;
+        let int = t::integer().build()?;
+        let opt = t::optional(int).build()?;
+        assert!(int.is_assignable_to(opt)?);
+        assert!(!opt.is_assignable_to(int)?);
+        assert!(t::optional(t::integer().min(0).build()?)
+            .build()?
+            .is_assignable_to(opt)?);
+        assert!(!opt.is_assignable_to(t::optional(int).absent().build()?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_assignable_struct_width() -> Result<(), String> {
+        Store::reset()?;
+        let int = t::integer().build()?;
+        let string = t::string().build()?;
+        let point = t::struct_().prop("x", int).prop("y", int).build()?;
+        let named_point = t::struct_()
+            .prop("x", int)
+            .prop("y", int)
+            .prop("name", string)
+            .build()?;
+        assert!(named_point.is_assignable_to(point)?);
+        assert!(!point.is_assignable_to(named_point)?);
+
+        let with_opt = t::struct_()
+            .prop("x", int)
+            .prop("y", int)
+            .prop("name", t::optional(string).build()?)
+            .build()?;
+        assert!(point.is_assignable_to(with_opt)?);
+
+        let float_point = t::struct_()
+            .prop("x", t::float().build()?)
+            .prop("y", string)
+            .build()?;
+        assert!(!point.is_assignable_to(float_point)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_assignable_recursive() -> Result<(), String> {
+        Store::reset()?;
+        let node = t::struct_()
+            .propx("value", t::integer().min(0))?
+            .prop("next", t::proxy("Node").optional()?)
+            .named("Node")
+            .build()?;
+        let list = t::struct_()
+            .propx("value", t::integer())?
+            .prop("next", t::proxy("List").optional()?)
+            .named("List")
+            .build()?;
+        assert!(node.is_assignable_to(list)?);
+        assert!(!list.is_assignable_to(node)?);
+        Ok(())
+    }
 }
@@ -8,8 +8,17 @@ pub mod types;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-static NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[_a-zA-Z0-9]+$").unwrap());
+static NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[_A-Za-z][_0-9A-Za-z]*$").unwrap());
 
+/// GraphQL name rule, shared by struct props and exports
 pub fn validate_name(name: &str) -> bool {
     NAME_REGEX.is_match(name)
 }
+
+static TYPEGRAPH_NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[_a-zA-Z0-9][-_a-zA-Z0-9]*$").unwrap());
+
+/// Typegraph names may also contain dashes, but not as their first character
+pub fn validate_typegraph_name(name: &str) -> bool {
+    TYPEGRAPH_NAME_REGEX.is_match(name)
+}
@@ -1,10 +1,18 @@
 // Copyright Metatype OÜ, licensed under the Mozilla Public License Version 2.0.
 // SPDX-License-Identifier: MPL-2.0
 
+use crate::errors::{self, Result};
 use crate::global_store::Store;
-use crate::types::{Type, TypeId};
-use crate::wit::core::TypeFunc;
-use crate::{errors, Result};
+use crate::types::{ProxyResolution, Type, TypeBoolean, TypeId};
+use crate::validation::validate_name;
+use crate::wit::core::Error as TgError;
+use crate::wit::core::{
+    TypeBase, TypeEither, TypeFloat, TypeFunc, TypeInteger, TypeOptional, TypeString, TypeStruct,
+    TypeUnion,
+};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashSet;
 
 impl TypeFunc {
     pub fn validate(&self) -> Result<()> {
@@ -22,7 +30,451 @@ impl TypeFunc {
     }
 }
 
+/// Path of the first function type nested in the given type, if any.
+/// Unresolved proxies are skipped, as well as the resolvers of computed
+/// fields when `allow_resolvers` is set.
+pub fn find_nested_func(type_id: TypeId, allow_resolvers: bool) -> Result<Option<Vec<String>>> {
+    fn visit(
+        type_id: TypeId,
+        allow_resolvers: bool,
+        path: &mut Vec<String>,
+        visited: &mut HashSet<TypeId>,
+    ) -> Result<bool> {
+        let Some(type_id) = type_id.concrete_type(ProxyResolution::Try)? else {
+            return Ok(false);
+        };
+        if !visited.insert(type_id) {
+            return Ok(false);
+        }
+        match type_id.as_type()? {
+            Type::Func(f) if allow_resolvers && f.data.resolver => (),
+            Type::Func(_) => return Ok(true),
+            Type::Struct(t) => {
+                for (name, prop) in t.data.props.iter() {
+                    path.push(name.clone());
+                    if visit(TypeId(*prop), allow_resolvers, path, visited)? {
+                        return Ok(true);
+                    }
+                    path.pop();
+                }
+            }
+            Type::Array(t) => return visit(TypeId(t.data.of), allow_resolvers, path, visited),
+            Type::Map(t) => return visit(TypeId(t.data.value), allow_resolvers, path, visited),
+            Type::Optional(t) => return visit(TypeId(t.data.of), allow_resolvers, path, visited),
+            Type::Union(t) => {
+                for variant in t.data.variants.iter() {
+                    if visit(TypeId(*variant), allow_resolvers, path, visited)? {
+                        return Ok(true);
+                    }
+                }
+            }
+            Type::Either(t) => {
+                for variant in t.data.variants.iter() {
+                    if visit(TypeId(*variant), allow_resolvers, path, visited)? {
+                        return Ok(true);
+                    }
+                }
+            }
+            _ => (),
+        }
+        Ok(false)
+    }
+
+    let mut path = vec![];
+    let found = visit(type_id, allow_resolvers, &mut path, &mut HashSet::new())?;
+    Ok(found.then_some(path))
+}
+
+impl TypeStruct {
+    pub fn validate(&self) -> Result<()> {
+        match self.collect_errors().into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Like `validate`, but reports all the errors at once
+    pub fn validate_all(&self) -> Result<()> {
+        let mut errs = self.collect_errors();
+        match errs.len() {
+            0 => Ok(()),
+            1 => Err(errs.pop().unwrap()),
+            _ => Err(errors::multiple(errs)),
+        }
+    }
+
+    fn collect_errors(&self) -> Vec<TgError> {
+        let mut errs = vec![];
+        let mut prop_names = HashSet::new();
+        for (name, _) in self.props.iter() {
+            if !validate_name(name) {
+                errs.push(errors::invalid_prop_key(name));
+            }
+            if !prop_names.insert(name) {
+                errs.push(errors::duplicate_key(name));
+            }
+        }
+        if let Some(max) = self.max_props {
+            if self.props.len() > max as usize {
+                errs.push(errors::too_many_props(self.props.len(), max));
+            }
+        }
+        if self.max_depth == Some(0) {
+            errs.push(errors::invalid_max_depth());
+        }
+        errs
+    }
+}
+
+fn validate_variants(variants: &[u32]) -> Result<()> {
+    let mut seen = HashSet::new();
+    for variant in variants.iter() {
+        if !seen.insert(*variant) {
+            return Err(errors::duplicate_union_variant(&TypeId(*variant).repr()?));
+        }
+    }
+    Ok(())
+}
+
+impl TypeEither {
+    pub fn validate(&self) -> Result<()> {
+        validate_variants(&self.variants)
+    }
+}
+
+impl TypeUnion {
+    pub fn validate(&self) -> Result<()> {
+        validate_variants(&self.variants)?;
+
+        let Some(tag) = &self.discriminator else {
+            return Ok(());
+        };
+        check_discriminated_variants(&self.variants, tag)
+    }
+}
+
+/// Nested unions are inlined into the discriminated union when they have no
+/// discriminator or the same one, so their variants must carry the tag too.
+fn check_discriminated_variants(variants: &[u32], tag: &str) -> Result<()> {
+    for variant in variants.iter() {
+        let variant = TypeId(*variant);
+        let has_tag = match variant.attrs()?.concrete_type.as_type()? {
+            Type::Struct(inner) => match inner.data.get_prop(tag) {
+                Some(prop) => matches!(prop.attrs()?.concrete_type.as_type()?, Type::String(_)),
+                None => false,
+            },
+            Type::Union(inner) => match inner.data.discriminator.as_deref() {
+                None => {
+                    check_discriminated_variants(&inner.data.variants, tag)?;
+                    true
+                }
+                Some(inner_tag) => inner_tag == tag,
+            },
+            _ => false,
+        };
+        if !has_tag {
+            return Err(errors::missing_discriminator_field(&variant.repr()?));
+        }
+    }
+    Ok(())
+}
+
+/// Check that the examples of a type satisfy its constraints
+pub fn validate_examples(base: &TypeBase, check: impl Fn(&Value) -> Result<()>) -> Result<()> {
+    for example in base.examples.iter().flatten() {
+        let value: Value = serde_json::from_str(example)
+            .map_err(|e| errors::invalid_example(example, &e.to_string()))?;
+        check(&value).map_err(|reason| errors::invalid_example(example, &reason))?;
+    }
+    Ok(())
+}
+
+impl TypeBoolean {
+    pub fn validate(&self, base: &TypeBase) -> Result<()> {
+        validate_examples(base, |v| self.check_value(v))
+    }
+
+    pub fn check_value(&self, value: &Value) -> Result<()> {
+        if !value.is_boolean() {
+            return Err("expected a boolean".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl TypeInteger {
+    pub fn validate(&self, base: &TypeBase) -> Result<()> {
+        if self.min.is_some() && self.exclusive_minimum.is_some() {
+            return Err(errors::conflicting_bounds("minimum"));
+        }
+        if self.max.is_some() && self.exclusive_maximum.is_some() {
+            return Err(errors::conflicting_bounds("maximum"));
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min >= max {
+                return Err(errors::invalid_max_value());
+            }
+        }
+        if let (Some(min), Some(max)) = (self.exclusive_minimum, self.exclusive_maximum) {
+            if min >= max {
+                return Err(errors::invalid_max_value());
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min, self.exclusive_maximum) {
+            if min >= max {
+                return Err(errors::invalid_max_value());
+            }
+        }
+        if let (Some(min), Some(max)) = (self.exclusive_minimum, self.max) {
+            if min >= max {
+                return Err(errors::invalid_max_value());
+            }
+        }
+        if let Some(enumeration) = &self.enumeration {
+            for value in enumeration {
+                self.check_bounds(*value as i64)
+                    .map_err(|e| errors::enum_value_out_of_range(&value.to_string(), &e))?;
+            }
+        }
+        validate_examples(base, |v| self.check_value(v))
+    }
+
+    pub fn check_value(&self, value: &Value) -> Result<()> {
+        self.check(value, true)
+    }
+
+    /// Same as `check_value`, with the enumerated values left unchecked
+    /// unless `check_enum`
+    fn check(&self, value: &Value, check_enum: bool) -> Result<()> {
+        let Some(value) = value.as_i64() else {
+            return Err("expected an integer".to_string());
+        };
+        self.check_bounds(value)?;
+        if let Some(multiple_of) = self.multiple_of {
+            if multiple_of != 0 && value % multiple_of as i64 != 0 {
+                return Err(format!("expected a multiple of {multiple_of}"));
+            }
+        }
+        if let Some(enumeration) = self.enumeration.as_ref().filter(|_| check_enum) {
+            if !enumeration.iter().any(|v| *v as i64 == value) {
+                return Err("expected one of the enumerated values".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    fn check_bounds(&self, value: i64) -> Result<()> {
+        if let Some(min) = self.min {
+            if value < min as i64 {
+                return Err(format!("expected a value >= {min}"));
+            }
+        }
+        if let Some(max) = self.max {
+            if value > max as i64 {
+                return Err(format!("expected a value <= {max}"));
+            }
+        }
+        if let Some(x_min) = self.exclusive_minimum {
+            if value <= x_min as i64 {
+                return Err(format!("expected a value > {x_min}"));
+            }
+        }
+        if let Some(x_max) = self.exclusive_maximum {
+            if value >= x_max as i64 {
+                return Err(format!("expected a value < {x_max}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TypeFloat {
+    pub fn validate(&self, base: &TypeBase) -> Result<()> {
+        if self.min.is_some() && self.exclusive_minimum.is_some() {
+            return Err(errors::conflicting_bounds("minimum"));
+        }
+        if self.max.is_some() && self.exclusive_maximum.is_some() {
+            return Err(errors::conflicting_bounds("maximum"));
+        }
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min >= max {
+                return Err(errors::invalid_max_value());
+            }
+        }
+        if let (Some(min), Some(max)) = (self.exclusive_minimum, self.exclusive_maximum) {
+            if min >= max {
+                return Err(errors::invalid_max_value());
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min, self.exclusive_maximum) {
+            if min >= max {
+                return Err(errors::invalid_max_value());
+            }
+        }
+        if let (Some(min), Some(max)) = (self.exclusive_minimum, self.max) {
+            if min >= max {
+                return Err(errors::invalid_max_value());
+            }
+        }
+        if let Some(enumeration) = &self.enumeration {
+            for value in enumeration {
+                self.check_bounds(*value)
+                    .map_err(|e| errors::enum_value_out_of_range(&value.to_string(), &e))?;
+            }
+        }
+        validate_examples(base, |v| self.check_value(v))
+    }
+
+    pub fn check_value(&self, value: &Value) -> Result<()> {
+        self.check(value, true)
+    }
+
+    /// Same as `check_value`, with the enumerated values left unchecked
+    /// unless `check_enum`
+    fn check(&self, value: &Value, check_enum: bool) -> Result<()> {
+        let Some(value) = value.as_f64() else {
+            return Err("expected a float".to_string());
+        };
+        self.check_bounds(value)?;
+        if let Some(multiple_of) = self.multiple_of {
+            if multiple_of != 0.0 && (value / multiple_of).fract() != 0.0 {
+                return Err(format!("expected a multiple of {multiple_of}"));
+            }
+        }
+        if let Some(enumeration) = self.enumeration.as_ref().filter(|_| check_enum) {
+            if !enumeration.contains(&value) {
+                return Err("expected one of the enumerated values".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    fn check_bounds(&self, value: f64) -> Result<()> {
+        if let Some(min) = self.min {
+            if value < min {
+                return Err(format!("expected a value >= {min}"));
+            }
+        }
+        if let Some(max) = self.max {
+            if value > max {
+                return Err(format!("expected a value <= {max}"));
+            }
+        }
+        if let Some(x_min) = self.exclusive_minimum {
+            if value <= x_min {
+                return Err(format!("expected a value > {x_min}"));
+            }
+        }
+        if let Some(x_max) = self.exclusive_maximum {
+            if value >= x_max {
+                return Err(format!("expected a value < {x_max}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TypeOptional {
+    /// The default value must be one of the enumerated values of the item, if any;
+    /// the other checks run at conversion for the items without enumeration
+    pub fn validate(&self) -> Result<()> {
+        let Some(default) = &self.default_item else {
+            return Ok(());
+        };
+        let Some(item) = TypeId(self.of).concrete_type(ProxyResolution::Try)? else {
+            return Ok(());
+        };
+        let default: Value = serde_json::from_str(default).map_err(|e| e.to_string())?;
+        if default.is_null() {
+            return Ok(());
+        }
+        let enumerated = match item.as_type()? {
+            Type::Integer(t) => t.data.enumeration.is_some(),
+            Type::Float(t) => t.data.enumeration.is_some(),
+            Type::String(t) => t.data.enumeration.is_some(),
+            Type::Struct(t) => t.data.enumeration.is_some(),
+            _ => false,
+        };
+        if !enumerated {
+            return Ok(());
+        }
+        // any other failure is reported as is
+        validate_value_at(default.clone(), item, "<default>".to_string(), false)?;
+        if validate_value(default.clone(), item, "<default>".to_string()).is_err() {
+            return Err(errors::default_not_in_enum(
+                &serde_json::to_string(&default).map_err(|e| e.to_string())?,
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl TypeString {
+    pub fn validate(&self, base: &TypeBase) -> Result<()> {
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            // equal for fixed-length strings
+            if min > max {
+                return Err(errors::invalid_max_value());
+            }
+        }
+        if self.max_size == Some(0) {
+            return Err(errors::invalid_max_value());
+        }
+        if self.max_size.is_some() && self.format.as_deref() != Some("binary") {
+            return Err(errors::max_size_without_binary_format());
+        }
+        validate_examples(base, |v| self.check_value(v))
+    }
+
+    pub fn check_value(&self, value: &Value) -> Result<()> {
+        self.check(value, true)
+    }
+
+    /// Same as `check_value`, with the enumerated values left unchecked
+    /// unless `check_enum`
+    fn check(&self, value: &Value, check_enum: bool) -> Result<()> {
+        let Some(value) = value.as_str() else {
+            return Err("expected a string".to_string());
+        };
+        let len = value.chars().count() as u32;
+        if let Some(min) = self.min {
+            if len < min {
+                return Err(format!("expected a length >= {min}"));
+            }
+        }
+        if let Some(max) = self.max {
+            if len > max {
+                return Err(format!("expected a length <= {max}"));
+            }
+        }
+        if let Some(pattern) = &self.pattern {
+            let re = Regex::new(pattern).map_err(|e| e.to_string())?;
+            if !re.is_match(value) {
+                return Err(format!("expected a match for the pattern {pattern:?}"));
+            }
+        }
+        if let Some(enumeration) = self.enumeration.as_ref().filter(|_| check_enum) {
+            let value = serde_json::to_string(value).unwrap();
+            if !enumeration.contains(&value) {
+                return Err("expected one of the enumerated values".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
 pub fn validate_value(value: serde_json::Value, type_id: TypeId, path: String) -> Result<()> {
+    validate_value_at(value, type_id, path, true)
+}
+
+/// Same as `validate_value`, with the enumerated values of the type itself
+/// left unchecked unless `check_enum`; the nested types are fully checked
+fn validate_value_at(
+    value: serde_json::Value,
+    type_id: TypeId,
+    path: String,
+    check_enum: bool,
+) -> Result<()> {
     let attrs = type_id.attrs()?;
     let typ = attrs.concrete_type.as_type()?;
     match typ {
@@ -42,6 +494,19 @@ pub fn validate_value(value: serde_json::Value, type_id: TypeId, path: String) -
                     format!("{path}.{key}"),
                 )?;
             }
+            // enumerated values are stored as json strings
+            if let Some(enumeration) = inner.data.enumeration.as_ref().filter(|_| check_enum) {
+                let value = serde_json::Value::Object(value.clone());
+                if !enumeration
+                    .iter()
+                    .any(|v| serde_json::from_str::<Value>(v).is_ok_and(|v| v == value))
+                {
+                    return Err(format!(
+                        "expected one of the enumerated values at {path:?}, got: {}",
+                        serde_json::to_string(&value).map_err(|e| e.to_string())?,
+                    ));
+                }
+            }
             // TODO min max?
             Ok(())
         }
@@ -60,6 +525,21 @@ pub fn validate_value(value: serde_json::Value, type_id: TypeId, path: String) -
             Ok(())
         }
 
+        Type::Map(inner) => {
+            let Some(value) = value.as_object() else {
+                return Err(format!(
+                    "expected map at {path:?}, got: {}",
+                    serde_json::to_string(&value).map_err(|e| e.to_string())?,
+                ));
+            };
+            for (key, value) in value.iter() {
+                let path = format!("{path}.{key}");
+                validate_value(key.clone().into(), inner.data.key.into(), path.clone())?;
+                validate_value(value.clone(), inner.data.value.into(), path)?;
+            }
+            Ok(())
+        }
+
         Type::Optional(inner) => {
             if value.is_null() {
                 return Ok(());
@@ -102,37 +582,43 @@ pub fn validate_value(value: serde_json::Value, type_id: TypeId, path: String) -
             ))
         }
 
-        Type::String(_inner) => {
-            let Some(_value) = value.as_str() else {
+        Type::String(inner) => {
+            let Some(value) = value.as_str() else {
                 return Err(format!(
                     "expected string at {path:?}, got: {}",
                     serde_json::to_string(&value).map_err(|e| e.to_string())?,
                 ));
             };
-            // TODO min max
-            Ok(())
+            inner
+                .data
+                .check(&value.into(), check_enum)
+                .map_err(|e| format!("invalid string {value:?} at {path:?}: {e}"))
         }
 
-        Type::Integer(_inner) => {
-            let Some(_value) = value.as_i64() else {
+        Type::Integer(inner) => {
+            let Some(value) = value.as_i64() else {
                 return Err(format!(
                     "expected integer at {path:?}, got: {}",
                     serde_json::to_string(&value).map_err(|e| e.to_string())?,
                 ));
             };
-            // TODO min max
-            Ok(())
+            inner
+                .data
+                .check(&value.into(), check_enum)
+                .map_err(|e| format!("invalid integer {value} at {path:?}: {e}"))
         }
 
-        Type::Float(_inner) => {
-            let Some(_value) = value.as_f64() else {
+        Type::Float(inner) => {
+            let Some(value) = value.as_f64() else {
                 return Err(format!(
                     "expected float at {path:?}, got: {}",
                     serde_json::to_string(&value).map_err(|e| e.to_string())?,
                 ));
             };
-            // TODO min max
-            Ok(())
+            inner
+                .data
+                .check(&value.into(), check_enum)
+                .map_err(|e| format!("invalid float {value} at {path:?}: {e}"))
         }
 
         Type::Boolean(_inner) => {
@@ -145,6 +631,11 @@ pub fn validate_value(value: serde_json::Value, type_id: TypeId, path: String) -
             Ok(())
         }
 
+        Type::Json(_) => Ok(()),
+
+        // checked by the validator materializer at runtime
+        Type::Scalar(_) => Ok(()),
+
         _ => unreachable!(),
     }
 }
@@ -175,3 +666,183 @@ pub(super) mod utils {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::errors;
+    use crate::global_store::Store;
+    use crate::t::{self, ScalarTypeBuilder, TypeBuilder};
+    use crate::test_utils::*;
+    use crate::types::Type;
+    use crate::Lib;
+    use common::typegraph::TypeNode;
+
+    #[test]
+    fn test_examples() -> Result<(), String> {
+        t::string()
+            .example(serde_json::json!("hello"))
+            .example(serde_json::json!("world"))
+            .build()?;
+
+        let res = t::integer().min(12).example(serde_json::json!(7)).build();
+        assert_eq!(
+            res,
+            Err(errors::invalid_example("7", "expected a value >= 12"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bound_inclusivity() -> Result<(), String> {
+        let mat = reset_with_mat()?;
+        let int = t::integer().x_min(0).max(10).build()?;
+        assert_eq!(int.repr()?, format!("integer(#{}, max=10, x_min=0)", int.0));
+        let float = t::float().min(0.5).x_max(1.5).build()?;
+        assert_eq!(
+            float.repr()?,
+            format!("float(#{}, min=0.5, x_max=1.5)", float.0)
+        );
+
+        let inp = t::struct_().prop("a", int).prop("b", float).build()?;
+        expose_one("one", t::func(inp, int, mat)?)?;
+        let typegraph = Lib::finalize_typegraph_struct()?;
+        let serialized = |pred: fn(&TypeNode) -> bool| {
+            let node = typegraph.types.iter().find(|t| pred(t)).unwrap();
+            serde_json::to_value(node).unwrap()
+        };
+
+        let int = serialized(|t| matches!(t, TypeNode::Integer { .. }));
+        assert_eq!(int["exclusiveMinimum"], 0);
+        assert_eq!(int["maximum"], 10);
+        assert!(int.get("minimum").is_none());
+        assert!(int.get("exclusiveMaximum").is_none());
+
+        let float = serialized(|t| matches!(t, TypeNode::Float { .. }));
+        assert_eq!(float["minimum"], 0.5);
+        assert_eq!(float["exclusiveMaximum"], 1.5);
+        assert!(float.get("exclusiveMinimum").is_none());
+        assert!(float.get("maximum").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_conflicting_bounds() -> Result<(), String> {
+        Store::reset()?;
+        let res = t::integer().min(0).x_min(0).build();
+        assert_eq!(res, Err(errors::conflicting_bounds("minimum")));
+        let res = t::float().max(1.0).x_max(2.0).build();
+        assert_eq!(res, Err(errors::conflicting_bounds("maximum")));
+        t::integer().x_min(0).build()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_mixed_empty_bounds() -> Result<(), String> {
+        Store::reset()?;
+        let res = t::integer().min(5).x_max(5).build();
+        assert_eq!(res, Err(errors::invalid_max_value()));
+        let res = t::integer().x_min(5).max(5).build();
+        assert_eq!(res, Err(errors::invalid_max_value()));
+        let res = t::float().min(1.5).x_max(1.5).build();
+        assert_eq!(res, Err(errors::invalid_max_value()));
+        let res = t::float().x_min(2.0).max(1.0).build();
+        assert_eq!(res, Err(errors::invalid_max_value()));
+        t::integer().min(5).x_max(6).build()?;
+        t::float().x_min(1.0).max(1.5).build()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_location() -> Result<(), String> {
+        Store::reset()?;
+        let line = line!() + 1;
+        let err = t::integer().min(10).max(0).build_traced().unwrap_err();
+        let location = format!("\n  at {}:{line}:", file!());
+        assert!(err.starts_with(&errors::invalid_max_value()));
+        assert!(
+            err.contains(&location),
+            "{err:?} should contain {location:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_numeric_enum() -> Result<(), String> {
+        Store::reset()?;
+        let level = t::integer()
+            .min(1)
+            .max(3)
+            .enum_values(vec![1, 2, 3])
+            .build()?;
+        let Type::Integer(level) = level.as_type()? else {
+            panic!("expected an integer");
+        };
+        assert_eq!(level.data.enumeration, Some(vec![1, 2, 3]));
+        t::float().enum_values(vec![0.5, 1.5]).build()?;
+
+        assert_eq!(
+            t::integer().max(3).enum_values(vec![1, 4]).build(),
+            Err(errors::enum_value_out_of_range(
+                "4",
+                "expected a value <= 3"
+            ))
+        );
+        assert_eq!(
+            t::float().x_min(0.0).enum_values(vec![0.0, 1.5]).build(),
+            Err(errors::enum_value_out_of_range("0", "expected a value > 0"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_not_in_enum() -> Result<(), String> {
+        Store::reset()?;
+        let color = t::string()
+            .enum_(vec!["red".to_string(), "green".to_string()])
+            .build()?;
+        t::optional(color)
+            .default_item(serde_json::json!("green"))
+            .build()?;
+        assert_eq!(
+            t::optional(color)
+                .default_item(serde_json::json!("blue"))
+                .build(),
+            Err(errors::default_not_in_enum("\"blue\""))
+        );
+
+        let level = t::integer().enum_values(vec![1, 2, 3]).build()?;
+        t::optional(level)
+            .default_item(serde_json::json!(2))
+            .build()?;
+        assert_eq!(
+            t::optional(level)
+                .default_item(serde_json::json!(5))
+                .build(),
+            Err(errors::default_not_in_enum("5"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_default_of_enum() -> Result<(), String> {
+        Store::reset()?;
+        let color = t::string()
+            .enum_(vec!["red".to_string(), "green".to_string()])
+            .build()?;
+        assert_eq!(
+            t::optional(color)
+                .default_item(serde_json::json!(12))
+                .build(),
+            Err("expected string at \"<default>\", got: 12".to_string())
+        );
+
+        let level = t::integer().enum_values(vec![1, 2, 3]).build()?;
+        assert_eq!(
+            t::optional(level)
+                .default_item(serde_json::json!("two"))
+                .build(),
+            Err("expected integer at \"<default>\", got: \"two\"".to_string())
+        );
+        Ok(())
+    }
+}
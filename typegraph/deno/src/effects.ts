@@ -6,6 +6,7 @@ import {
   EffectDelete,
   EffectNone,
   EffectUpdate,
+  EffectUpsert,
 } from "./gen/interfaces/metatype-typegraph-runtimes.d.ts";
 
 export function none(): EffectNone {
@@ -24,13 +25,19 @@ export function update(idempotent = true): EffectUpdate {
   return { tag: "update", val: idempotent };
 }
 
+export function upsert(idempotent = true): EffectUpsert {
+  return { tag: "upsert", val: idempotent };
+}
+
 export const UPDATE = Symbol("update");
 export const DELETE = Symbol("delete");
 export const CREATE = Symbol("create");
+export const UPSERT = Symbol("upsert");
 export const NONE = Symbol("none");
 export type PerEffect = {
   [CREATE]?: string;
   [UPDATE]?: string;
   [DELETE]?: string;
+  [UPSERT]?: string;
   [NONE]?: string;
 };
@@ -15,10 +15,12 @@ export default class Policy {
     );
   }
 
-  static context(key: string, check: string | RegExp): Policy {
+  static context(key: string, check?: string | RegExp): Policy {
     const [id, name] = core.registerContextPolicy(
       key,
-      typeof check === "string"
+      check === undefined
+        ? { tag: "present" }
+        : typeof check === "string"
         ? { tag: "value", val: check }
         : { tag: "pattern", val: check.source },
     );
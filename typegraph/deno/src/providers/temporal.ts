@@ -10,17 +10,17 @@ import {
 } from "../gen/interfaces/metatype-typegraph-runtimes.d.ts";
 
 export class TemporalRuntime extends Runtime {
-  host: string;
+  hostSecret: string;
   name: string;
 
-  constructor(name: string, host: string) {
+  constructor(name: string, hostSecret: string) {
     const id = runtimes.registerTemporalRuntime({
       name,
-      host,
+      hostSecret,
     });
     super(id);
     this.name = name;
-    this.host = host;
+    this.hostSecret = hostSecret;
   }
 
   #genericTemporalFunc(
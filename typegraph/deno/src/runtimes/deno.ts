@@ -28,6 +28,7 @@ export interface DenoFunc {
   code: string;
   secrets?: Array<string>;
   effect?: Effect;
+  timeoutSec?: number;
 }
 
 export interface DenoImport {
@@ -49,9 +50,12 @@ export class DenoRuntime extends Runtime {
   >(
     inp: I,
     out: O,
-    { code, secrets = [], effect = { tag: "none" } }: DenoFunc,
+    { code, secrets = [], effect = { tag: "none" }, timeoutSec }: DenoFunc,
   ): t.Func<P, I, O, FunMat> {
-    const matId = runtimes.registerDenoFunc({ code, secrets }, effect);
+    const matId = runtimes.registerDenoFunc(
+      { code, secrets, timeoutSec },
+      effect,
+    );
     const mat: FunMat = {
       _id: matId,
       code,
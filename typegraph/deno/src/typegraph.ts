@@ -20,6 +20,9 @@ interface TypegraphArgs {
   name: string;
   dynamic?: boolean;
   folder?: string;
+  queryExtensions?: Array<string>;
+  dryRun?: boolean;
+  strict?: boolean;
   builder: TypegraphBuilder;
   prefix?: string;
   secrets?: Array<string>;
@@ -30,6 +33,17 @@ interface TypegraphArgs {
 
 interface TypegraphBuilderArgs {
   expose: (exports: Exports, defaultPolicy?: Policy) => void;
+  exposeReserved: (exports: Exports, defaultPolicy?: Policy) => void;
+  exposeAliased: (
+    exports: Array<[string, string, t.Func]>,
+    defaultPolicy?: Policy,
+  ) => void;
+  exposeNamespace: (
+    path: string[],
+    exports: Exports,
+    defaultPolicy?: Policy,
+  ) => void;
+  exposeMerged: (exports: Exports, defaultPolicy?: Policy) => void;
   inherit: () => InheritDef;
 }
 
@@ -86,6 +100,9 @@ export function typegraph(
     name,
     dynamic,
     folder,
+    queryExtensions,
+    dryRun,
+    strict,
     auths,
     cors,
     prefix,
@@ -123,7 +140,16 @@ export function typegraph(
     rate,
   };
 
-  core.initTypegraph({ name, dynamic, path, folder, ...tgParams });
+  core.initTypegraph({
+    name,
+    dynamic,
+    path,
+    folder,
+    queryExtensions,
+    dryRun: dryRun ?? false,
+    strict: strict ?? false,
+    ...tgParams,
+  });
 
   const g: TypegraphBuilderArgs = {
     expose: (exports, defaultPolicy) => {
@@ -132,6 +158,31 @@ export function typegraph(
         defaultPolicy ? getPolicyChain(defaultPolicy) : [],
       );
     },
+    exposeReserved: (exports, defaultPolicy) => {
+      core.exposeReserved(
+        Object.entries(exports).map(([name, fn]) => [name, fn._id]),
+        defaultPolicy ? getPolicyChain(defaultPolicy) : [],
+      );
+    },
+    exposeAliased: (exports, defaultPolicy) => {
+      core.exposeAliased(
+        exports.map(([name, alias, fn]) => [name, alias, fn._id]),
+        defaultPolicy ? getPolicyChain(defaultPolicy) : [],
+      );
+    },
+    exposeNamespace: (path, exports, defaultPolicy) => {
+      core.exposeNamespace(
+        path,
+        Object.entries(exports).map(([name, fn]) => [name, fn._id]),
+        defaultPolicy ? getPolicyChain(defaultPolicy) : [],
+      );
+    },
+    exposeMerged: (exports, defaultPolicy) => {
+      core.exposeMerged(
+        Object.entries(exports).map(([name, fn]) => [name, fn._id]),
+        defaultPolicy ? getPolicyChain(defaultPolicy) : [],
+      );
+    },
     inherit: () => {
       return new InheritDef();
     },
@@ -11,7 +11,9 @@ import {
   TypeFile,
   TypeFloat,
   TypeInteger,
+  TypeMap,
   TypeOptional,
+  TypeScalar,
   TypeString,
   TypeUnion,
 } from "./gen/interfaces/metatype-typegraph-core.d.ts";
@@ -34,10 +36,16 @@ export type PolicySpec = Policy | {
   create: Policy;
   update: Policy;
   delete: Policy;
+  upsert?: Policy;
 };
 
 export type Simplified<T> = Omit<T, "of">;
 
+/** `defaultItem` is any JSON value, `null` included */
+export type OptionalData =
+  & { defaultItem?: unknown }
+  & Omit<Simplified<TypeOptional>, "defaultItem">;
+
 export type SimplifiedBase<T> =
   & { config?: Record<string, unknown> }
   & Omit<T, "runtimeConfig" | "asId">;
@@ -119,7 +127,7 @@ export class Typedef {
     return new Typedef(this._id, { name: this.name });
   }
 
-  optional(data: Simplified<TypeOptional> = {}): Optional {
+  optional(data: OptionalData = {}): Optional {
     if (this instanceof Optional) {
       return this;
     }
@@ -286,6 +294,7 @@ class StringT extends Typedef implements Readonly<TypeString> {
   readonly format?: string;
   readonly pattern?: string;
   readonly enumeration?: string[];
+  readonly maxSize?: number;
 
   constructor(_id: number, data: TypeString, base: TypeBase) {
     super(_id, base);
@@ -294,6 +303,7 @@ class StringT extends Typedef implements Readonly<TypeString> {
     this.pattern = data.pattern;
     this.format = data.format;
     this.enumeration = data.enumeration;
+    this.maxSize = data.maxSize;
   }
 }
 
@@ -333,6 +343,10 @@ export function datetime() {
   return string({ format: "date-time" });
 }
 
+export function bytes(maxSize?: number) {
+  return string({ format: "binary", maxSize });
+}
+
 // Note: enum is a reserved word
 export function enum_(variants: string[], base: SimplifiedBase<TypeBase> = {}) {
   return string({
@@ -414,7 +428,7 @@ class Optional extends Typedef {
 
 export function optional(
   variant: Typedef,
-  data: Simplified<TypeOptional> = {},
+  data: OptionalData = {},
   base: SimplifiedBase<TypeBase> = {},
 ) {
   const completeData = {
@@ -490,6 +504,87 @@ export function either(
   );
 }
 
+class AnyT extends Typedef {
+  constructor(_id: number, base: TypeBase) {
+    super(_id, base);
+  }
+}
+
+export function any(base: SimplifiedBase<TypeBase> = {}) {
+  const completeBase = {
+    ...base,
+    asId: false,
+    runtimeConfig: base.config && serializeRecordValues(base.config),
+  };
+  return new AnyT(core.jsonb(completeBase), completeBase);
+}
+
+class MapT extends Typedef {
+  readonly key: number;
+  readonly value: number;
+
+  constructor(_id: number, data: TypeMap, base: TypeBase) {
+    super(_id, base);
+    this.key = data.key;
+    this.value = data.value;
+  }
+}
+
+export function map(
+  key: Typedef,
+  value: Typedef,
+  base: SimplifiedBase<TypeBase> = {},
+) {
+  const data = {
+    key: key._id,
+    value: value._id,
+  };
+  const completeBase = {
+    ...base,
+    asId: false,
+    runtimeConfig: base.config && serializeRecordValues(base.config),
+  };
+  return new MapT(
+    core.mapb(data, completeBase),
+    data,
+    completeBase,
+  );
+}
+
+class ScalarT extends Typedef {
+  readonly validator: number;
+  readonly serializer: number;
+
+  constructor(_id: number, data: TypeScalar, base: TypeBase) {
+    super(_id, base);
+    this.validator = data.validator;
+    this.serializer = data.serializer;
+  }
+}
+
+export function scalar(
+  name: string,
+  validator: Materializer,
+  serializer: Materializer,
+  base: SimplifiedBase<TypeBase> = {},
+) {
+  const data = {
+    validator: validator._id,
+    serializer: serializer._id,
+  };
+  const completeBase = {
+    ...base,
+    name,
+    asId: false,
+    runtimeConfig: base.config && serializeRecordValues(base.config),
+  };
+  return new ScalarT(
+    core.scalarb(data, completeBase),
+    data,
+    completeBase,
+  );
+}
+
 export class Struct<P extends { [key: string]: Typedef }> extends Typedef {
   props: P;
   constructor(_id: number, { props }: { props: P }, base: TypeBase) {
@@ -565,6 +660,9 @@ export class Func<
 type FuncConfig = {
   rateCalls?: boolean;
   rateWeight?: number;
+  // wraps a non-struct input as the `_arg` field of a struct
+  autoWrapInput?: boolean;
+  deprecated?: string;
 };
 
 export function func<
@@ -576,8 +674,17 @@ export function func<
   inp: I,
   out: O,
   mat: M,
-  { rateCalls = false, rateWeight = undefined }: FuncConfig = {},
+  {
+    rateCalls = false,
+    rateWeight = undefined,
+    autoWrapInput = false,
+    deprecated = undefined,
+  }: FuncConfig = {},
 ) {
+  const completeBase: TypeBase = {
+    asId: false,
+    deprecated,
+  };
   return new Func<P, I, O, M>(
     core.funcb({
       inp: inp._id,
@@ -585,7 +692,9 @@ export function func<
       mat: mat._id,
       rateCalls,
       rateWeight,
-    }) as number,
+      resolver: false,
+      autoWrapInput,
+    }, completeBase) as number,
     inp,
     out,
     mat,
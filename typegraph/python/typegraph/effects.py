@@ -6,6 +6,7 @@ from typegraph.gen.exports.runtimes import (
     EffectDelete,
     EffectNone,
     EffectUpdate,
+    EffectUpsert,
 )
 from enum import auto, Enum
 
@@ -26,15 +27,21 @@ def delete(idempotent: bool = True):
     return EffectDelete(idempotent)
 
 
+def upsert(idempotent: bool = True):
+    return EffectUpsert(idempotent)
+
+
 # For injections
 class EffectType(Enum):
     CREATE = auto()
     UPDATE = auto()
     DELETE = auto()
+    UPSERT = auto()
     NONE = auto()
 
 
 CREATE = EffectType.CREATE
 UPDATE = EffectType.UPDATE
 DELETE = EffectType.DELETE
+UPSERT = EffectType.UPSERT
 NONE = EffectType.NONE
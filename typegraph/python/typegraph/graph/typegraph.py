@@ -4,7 +4,7 @@
 from dataclasses import dataclass
 import inspect
 from pathlib import Path
-from typing import TYPE_CHECKING, Callable, List, Optional, Union
+from typing import TYPE_CHECKING, Callable, List, Optional, Tuple, Union
 
 from typegraph.gen.exports.core import (
     Auth,
@@ -33,6 +33,9 @@ class Typegraph:
     dynamic: Optional[bool]
     folder: Optional[str]
     path: str
+    query_extensions: Optional[List[str]]
+    dry_run: bool
+    strict: bool
     _context: List["Typegraph"] = []
     auths: Optional[List[Auth]]
     rate: Optional[Rate]
@@ -49,11 +52,17 @@ class Typegraph:
         rate: Optional[Rate] = None,
         cors: Optional[Cors] = None,
         prefix: Optional[str] = None,
+        query_extensions: Optional[List[str]] = None,
+        dry_run: bool = False,
+        strict: bool = False,
     ):
         self.name = name
         self.dynamic = dynamic
         self.folder = folder
         self.path = str(Path(inspect.stack()[2].filename).resolve().parent)
+        self.query_extensions = query_extensions
+        self.dry_run = dry_run
+        self.strict = strict
 
         self.auths = auths or []
         self.rate = rate
@@ -89,6 +98,52 @@ class Typegraph:
             default_policy=get_policy_chain(default_policy) if default_policy else None,
         )
 
+    def expose_reserved(
+        self,
+        default_policy: Optional[PolicySpec] = None,
+        **kwargs: ExposeItem,
+    ):
+        core.expose_reserved(
+            store,
+            [(k, v.id) for k, v in kwargs.items()],
+            default_policy=get_policy_chain(default_policy) if default_policy else None,
+        )
+
+    def expose_aliased(
+        self,
+        fns: List[Tuple[str, str, ExposeItem]],
+        default_policy: Optional[PolicySpec] = None,
+    ):
+        core.expose_aliased(
+            store,
+            [(k, alias, v.id) for k, alias, v in fns],
+            default_policy=get_policy_chain(default_policy) if default_policy else None,
+        )
+
+    def expose_namespace(
+        self,
+        path: List[str],
+        default_policy: Optional[PolicySpec] = None,
+        **kwargs: ExposeItem,
+    ):
+        core.expose_namespace(
+            store,
+            path,
+            [(k, v.id) for k, v in kwargs.items()],
+            default_policy=get_policy_chain(default_policy) if default_policy else None,
+        )
+
+    def expose_merged(
+        self,
+        default_policy: Optional[PolicySpec] = None,
+        **kwargs: ExposeItem,
+    ):
+        core.expose_merged(
+            store,
+            [(k, v.id) for k, v in kwargs.items()],
+            default_policy=get_policy_chain(default_policy) if default_policy else None,
+        )
+
 
 @dataclass
 class Graph:
@@ -101,6 +156,35 @@ class Graph:
     ):
         self.typegraph.expose(default_policy, **kwargs)
 
+    def expose_reserved(
+        self,
+        default_policy: Optional[Union[Policy, PolicyPerEffect]] = None,
+        **kwargs: ExposeItem,
+    ):
+        self.typegraph.expose_reserved(default_policy, **kwargs)
+
+    def expose_aliased(
+        self,
+        fns: List[Tuple[str, str, ExposeItem]],
+        default_policy: Optional[Union[Policy, PolicyPerEffect]] = None,
+    ):
+        self.typegraph.expose_aliased(fns, default_policy)
+
+    def expose_namespace(
+        self,
+        path: List[str],
+        default_policy: Optional[Union[Policy, PolicyPerEffect]] = None,
+        **kwargs: ExposeItem,
+    ):
+        self.typegraph.expose_namespace(path, default_policy, **kwargs)
+
+    def expose_merged(
+        self,
+        default_policy: Optional[Union[Policy, PolicyPerEffect]] = None,
+        **kwargs: ExposeItem,
+    ):
+        self.typegraph.expose_merged(default_policy, **kwargs)
+
     def inherit(self):
         from typegraph.injection import InheritDef
 
@@ -116,6 +200,9 @@ def typegraph(
     rate: Optional[Rate] = None,
     cors: Optional[Cors] = None,
     prefix: Optional[str] = None,
+    query_extensions: Optional[List[str]] = None,
+    dry_run: bool = False,
+    strict: bool = False,
 ) -> Callable[[Callable[[Graph], None]], Typegraph]:
     def decorator(builder: Callable[[Graph], None]) -> Typegraph:
         actual_name = name
@@ -133,6 +220,9 @@ def typegraph(
             rate=rate,
             cors=cors,
             prefix=prefix,
+            query_extensions=query_extensions,
+            dry_run=dry_run,
+            strict=strict,
         )
 
         Typegraph._context.append(tg)
@@ -144,6 +234,9 @@ def typegraph(
                 dynamic=tg.dynamic,
                 folder=tg.folder,
                 path=tg.path,
+                query_extensions=tg.query_extensions,
+                dry_run=tg.dry_run,
+                strict=tg.strict,
                 auths=tg.auths,
                 rate=tg.rate,
                 cors=tg.cors,
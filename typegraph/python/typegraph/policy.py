@@ -7,6 +7,7 @@ from typing import Optional, Union, List
 
 from typegraph.gen.exports.core import (
     ContextCheckPattern,
+    ContextCheckPresent,
     ContextCheckValue,
     Err,
     MaterializerId,
@@ -48,8 +49,10 @@ class Policy:
         return cls.__public
 
     @classmethod
-    def context(cls, key: str, check: Union[str, Pattern]) -> "Policy":
-        if isinstance(check, str):
+    def context(cls, key: str, check: Union[str, Pattern, None] = None) -> "Policy":
+        if check is None:
+            res = core.register_context_policy(store, key, ContextCheckPresent())
+        elif isinstance(check, str):
             res = core.register_context_policy(store, key, ContextCheckValue(check))
         else:
             res = core.register_context_policy(
@@ -86,9 +89,12 @@ class Policy:
         update: Optional["Policy"] = None,
         delete: Optional["Policy"] = None,
         create: Optional["Policy"] = None,
+        upsert: Optional["Policy"] = None,
         none: Optional["Policy"] = None,
     ) -> "PolicyPerEffect":
-        return PolicyPerEffect(create=create, update=update, delete=delete, none=none)
+        return PolicyPerEffect(
+            create=create, update=update, delete=delete, upsert=upsert, none=none
+        )
 
 
 @dataclass
@@ -96,6 +102,7 @@ class PolicyPerEffect:
     create: Optional[Policy] = None
     update: Optional[Policy] = None
     delete: Optional[Policy] = None
+    upsert: Optional[Policy] = None
     none: Optional[Policy] = None
 
 
@@ -116,6 +123,7 @@ def get_policy_chain(policies: PolicySpec) -> List[WitPolicySpec]:
                 create=p.create and p.create.id,
                 update=p.update and p.update.id,
                 delete=p.delete and p.delete.id,
+                upsert=p.upsert and p.upsert.id,
                 none=p.none and p.none.id,
             )
         )
@@ -21,14 +21,14 @@ from typegraph import t
 
 
 class TemporalRuntime(Runtime):
-    host: str
+    host_secret: str
     name: str
 
-    def __init__(self, name: str, host: str):
-        data = TemporalRuntimeData(name=name, host=host)
+    def __init__(self, name: str, host_secret: str):
+        data = TemporalRuntimeData(name=name, host_secret=host_secret)
         super().__init__(runtimes.register_temporal_runtime(store, data))
         self.name = name
-        self.host = host
+        self.host_secret = host_secret
 
     def _generic_temporal_func(
         self,
@@ -56,12 +56,13 @@ class DenoRuntime(Runtime):
         code: str,
         secrets: Optional[List[str]] = None,
         effect: Optional[Effect] = None,
+        timeout_sec: Optional[int] = None,
     ):
         secrets = secrets or []
         effect = effect or EffectNone()
         mat_id = runtimes.register_deno_func(
             store,
-            MaterializerDenoFunc(code=code, secrets=secrets),
+            MaterializerDenoFunc(code=code, secrets=secrets, timeout_sec=timeout_sec),
             effect,
         )
 
@@ -130,7 +131,7 @@ class DenoRuntime(Runtime):
         secrets = secrets or []
         mat_id = runtimes.register_deno_func(
             store,
-            MaterializerDenoFunc(code=code, secrets=secrets),
+            MaterializerDenoFunc(code=code, secrets=secrets, timeout_sec=None),
             EffectNone(),
         )
 
@@ -16,9 +16,11 @@ from typegraph.gen.exports.core import (
     TypeFile,
     TypeFunc,
     TypeInteger,
+    TypeMap,
     TypeOptional,
     TypePolicy,
     TypeProxy,
+    TypeScalar,
     TypeString,
     TypeStruct,
     TypeUnion,
@@ -187,6 +189,7 @@ class integer(typedef):
         enum: Optional[List[int]] = None,
         name: Optional[str] = None,
         config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
         as_id: bool = False,
     ):
         data = TypeInteger(
@@ -202,7 +205,14 @@ class integer(typedef):
         res = core.integerb(
             store,
             data,
-            TypeBase(name=name, runtime_config=runtime_config, as_id=as_id),
+            TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=as_id,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
         )
         if isinstance(res, Err):
             raise Exception(res.value)
@@ -213,6 +223,7 @@ class integer(typedef):
         self.exclusive_maximum = exclusive_maximum
         self.multiple_of = multiple_of
         self.enumeration = enum
+        self.max_size = max_size
         self.runtime_config = runtime_config
         self.as_id = as_id
 
@@ -236,6 +247,7 @@ class float(typedef):
         enum: Optional[List[float]] = None,
         name: Optional[str] = None,
         config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
     ):
         data = TypeFloat(
             min=min,
@@ -249,7 +261,14 @@ class float(typedef):
         res = core.floatb(
             store,
             data,
-            TypeBase(name=name, runtime_config=runtime_config, as_id=False),
+            TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
         )
         if isinstance(res, Err):
             raise Exception(res.value)
@@ -265,11 +284,23 @@ class float(typedef):
 
 class boolean(typedef):
     def __init__(
-        self, *, name: Optional[str] = None, config: Optional[ConfigSpec] = None
+        self,
+        *,
+        name: Optional[str] = None,
+        config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
     ):
         runtime_config = serialize_config(config)
         res = core.booleanb(
-            store, TypeBase(name=name, runtime_config=runtime_config, as_id=False)
+            store,
+            TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
         )
         if isinstance(res, Err):
             raise Exception(res.value)
@@ -283,6 +314,7 @@ class string(typedef):
     pattern: Optional[str] = None
     format: Optional[str] = None
     enumeration: Optional[List[str]] = None
+    max_size: Optional[int] = None
     as_id: bool
 
     def __init__(
@@ -293,8 +325,10 @@ class string(typedef):
         pattern: Optional[str] = None,
         format: Optional[str] = None,
         enum: Optional[List[str]] = None,
+        max_size: Optional[int] = None,
         name: Optional[str] = None,
         config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
         as_id: bool = False,
     ):
         enum_variants = None
@@ -302,14 +336,26 @@ class string(typedef):
             enum_variants = list(JsonLib.dumps(variant) for variant in enum)
 
         data = TypeString(
-            min=min, max=max, pattern=pattern, format=format, enumeration=enum_variants
+            min=min,
+            max=max,
+            pattern=pattern,
+            format=format,
+            enumeration=enum_variants,
+            max_size=max_size,
         )
 
         runtime_config = serialize_config(config)
         res = core.stringb(
             store,
             data,
-            TypeBase(name=name, runtime_config=runtime_config, as_id=as_id),
+            TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=as_id,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
         )
         if isinstance(res, Err):
             raise Exception(res.value)
@@ -365,6 +411,10 @@ def json() -> string:
     return string(format="json")
 
 
+def bytes(*, max_size: Optional[int] = None) -> string:
+    return string(format="binary", max_size=max_size)
+
+
 def enum(
     variants: List[str],
     name: Optional[str] = None,
@@ -384,6 +434,7 @@ class file(typedef):
         max: Optional[int] = None,
         allow: Optional[List[str]] = None,
         config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
     ):
         data = TypeFile(
             min=min,
@@ -395,7 +446,14 @@ class file(typedef):
         res = core.fileb(
             store,
             data,
-            TypeBase(name=None, runtime_config=runtime_config, as_id=False),
+            TypeBase(
+                name=None,
+                runtime_config=runtime_config,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
         )
         if isinstance(res, Err):
             raise Exception(res.value)
@@ -420,6 +478,7 @@ class array(typedef):
         unique_items: Optional[bool] = None,
         name: Optional[str] = None,
         config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
     ):
         data = TypeArray(
             of=items.id,
@@ -432,7 +491,14 @@ class array(typedef):
         res = core.arrayb(
             store,
             data,
-            TypeBase(name=name, runtime_config=runtime_config, as_id=False),
+            TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
         )
         if isinstance(res, Err):
             raise Exception(res.value)
@@ -454,17 +520,26 @@ class optional(typedef):
         default_item: Optional[Any] = None,
         name: Optional[str] = None,
         config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
     ):
         data = TypeOptional(
             of=item.id,
             default_item=None if default_item is None else JsonLib.dumps(default_item),
+            kind=None,
         )
 
         runtime_config = serialize_config(config)
         res = core.optionalb(
             store,
             data,
-            TypeBase(name=name, runtime_config=runtime_config, as_id=False),
+            TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
         )
         if isinstance(res, Err):
             raise Exception(res.value)
@@ -482,14 +557,25 @@ class union(typedef):
         variants: List[typedef],
         name: Optional[str] = None,
         config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
     ):
-        data = TypeUnion(variants=list(map(lambda v: v.id, variants)))
+        data = TypeUnion(
+            variants=[v.id for v in variants],
+            discriminator=None,
+        )
 
         runtime_config = serialize_config(config)
         res = core.unionb(
             store,
             data,
-            TypeBase(name=name, runtime_config=runtime_config, as_id=False),
+            TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
         )
         if isinstance(res, Err):
             raise Exception(res.value)
@@ -506,14 +592,22 @@ class either(typedef):
         variants: List[typedef],
         name: Optional[str] = None,
         config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
     ):
-        data = TypeEither(variants=list(map(lambda v: v.id, variants)))
+        data = TypeEither(variants=[v.id for v in variants])
 
         runtime_config = serialize_config(config)
         res = core.eitherb(
             store,
             data,
-            TypeBase(name=name, runtime_config=runtime_config, as_id=False),
+            TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
         )
         if isinstance(res, Err):
             raise Exception(res.value)
@@ -522,6 +616,103 @@ class either(typedef):
         self.runtime_config = runtime_config
 
 
+class any(typedef):
+    def __init__(
+        self,
+        *,
+        name: Optional[str] = None,
+        config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
+    ):
+        runtime_config = serialize_config(config)
+        res = core.jsonb(
+            store,
+            TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
+        )
+        if isinstance(res, Err):
+            raise Exception(res.value)
+        super().__init__(res.value)
+        self.runtime_config = runtime_config
+
+
+class map(typedef):
+    key: typedef
+    value: typedef
+
+    def __init__(
+        self,
+        key: typedef,
+        value: typedef,
+        name: Optional[str] = None,
+        config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
+    ):
+        data = TypeMap(key=key.id, value=value.id)
+
+        runtime_config = serialize_config(config)
+        res = core.mapb(
+            store,
+            data,
+            TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
+        )
+        if isinstance(res, Err):
+            raise Exception(res.value)
+        super().__init__(res.value)
+        self.key = key
+        self.value = value
+        self.runtime_config = runtime_config
+
+
+class scalar(typedef):
+    validator: Materializer
+    serializer: Materializer
+
+    def __init__(
+        self,
+        name: str,
+        validator: Materializer,
+        serializer: Materializer,
+        *,
+        config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
+    ):
+        data = TypeScalar(validator=validator.id, serializer=serializer.id)
+
+        runtime_config = serialize_config(config)
+        res = core.scalarb(
+            store,
+            data,
+            TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
+        )
+        if isinstance(res, Err):
+            raise Exception(res.value)
+        super().__init__(res.value)
+        self.validator = validator
+        self.serializer = serializer
+        self.runtime_config = runtime_config
+
+
 class struct(typedef):
     props: Dict[str, typedef]
     additional_props: bool
@@ -538,7 +729,9 @@ class struct(typedef):
         max: Optional[int] = None,
         name: Optional[str] = None,
         config: Optional[ConfigSpec] = None,
+        deprecated: Optional[str] = None,
         enum: Optional[List[Dict[str, Any]]] = None,
+        max_depth: Optional[int] = None,
     ):
         if self.__class__ != struct:  # custom class
             if len(self.__class__.__bases__) > 1:
@@ -586,13 +779,22 @@ class struct(typedef):
             min=min,
             max=max,
             enumeration=[JsonLib.dumps(v) for v in enum] if enum else None,
+            max_props=None,
+            max_depth=max_depth,
         )
 
         runtime_config = serialize_config(config)
         res = core.structb(
             store,
             data,
-            base=TypeBase(name=name, runtime_config=runtime_config, as_id=False),
+            base=TypeBase(
+                name=name,
+                runtime_config=runtime_config,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
         )
         if isinstance(res, Err):
             raise Exception(res.value)
@@ -619,6 +821,8 @@ class func(typedef):
         mat: Materializer,
         rate_calls: bool = False,
         rate_weight: Optional[int] = None,
+        auto_wrap_input: bool = False,
+        deprecated: Optional[str] = None,
     ):
         data = TypeFunc(
             inp=inp.id,
@@ -626,8 +830,21 @@ class func(typedef):
             mat=mat.id,
             rate_calls=rate_calls,
             rate_weight=rate_weight,
+            resolver=False,
+            auto_wrap_input=auto_wrap_input,
+        )
+        res = core.funcb(
+            store,
+            data,
+            TypeBase(
+                name=None,
+                runtime_config=None,
+                as_id=False,
+                examples=None,
+                deprecated=deprecated,
+                runtime=None,
+            ),
         )
-        res = core.funcb(store, data)
         if isinstance(res, Err):
             raise Exception(res.value)
         id = res.value